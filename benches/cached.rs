@@ -0,0 +1,54 @@
+//! Compare repeated [mcom::Rc::try_cast]s against [mcom::Cached::try_cast] on a Rust-implemented COM object.  Runs on any host.
+//!
+//! `cargo bench --bench cached` for meaningful numbers.  Under `cargo test`, runs a handful of iterations as a smoke test.
+
+use mcom::{Cached, ComObject, Rc};
+use mcom::abi::*;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+
+
+mcom::interface! {
+    #[uuid(0x7e1a4c92, 0x3b5d, 0x4f08, 0x96, 0x2c, 0x5a, 0x0b, 0xe3, 0x71, 0x4d, 0x01)]
+    pub interface IFrame(IFrameVtbl, IFrameImpl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x7e1a4c92, 0x3b5d, 0x4f08, 0x96, 0x2c, 0x5a, 0x0b, 0xe3, 0x71, 0x4d, 0x02)]
+    pub interface IDraw(IDrawVtbl, IDrawImpl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x7e1a4c92, 0x3b5d, 0x4f08, 0x96, 0x2c, 0x5a, 0x0b, 0xe3, 0x71, 0x4d, 0x03)]
+    pub interface IUpdate(IUpdateVtbl, IUpdateImpl): IUnknown(IUnknownVtbl) {}
+}
+
+struct Object;
+mcom::implement!(Object: IFrame, IDraw, IUpdate);
+impl IFrameImpl for Object {}
+impl IDrawImpl for Object {}
+impl IUpdateImpl for Object {}
+
+fn main() {
+    let iterations = if std::env::args().any(|arg| arg == "--bench") { 1_000_000 } else { 10 };
+    let frame : Rc<IFrame> = ComObject::new(Object).into_rc();
+
+    let try_cast = time(iterations, || {
+        black_box(frame.try_cast::<IDraw>().unwrap());
+        black_box(frame.try_cast::<IUpdate>().unwrap());
+    });
+
+    let cached = Cached::<IFrame>::new(frame.clone());
+    let cached_try_cast = time(iterations, || {
+        black_box(cached.try_cast::<IDraw>().unwrap());
+        black_box(cached.try_cast::<IUpdate>().unwrap());
+    });
+
+    println!("{:<24} {:>10.1?} / iteration", "Rc::try_cast", try_cast);
+    println!("{:<24} {:>10.1?} / iteration", "Cached::try_cast", cached_try_cast);
+}
+
+fn time(iterations: u32, mut f: impl FnMut()) -> Duration {
+    f(); // warm up (and fill any caches)
+    let start = Instant::now();
+    for _ in 0 .. iterations { f() }
+    start.elapsed() / iterations
+}
@@ -25,8 +25,8 @@ fn main() {
         ("windows-10",      "10.0"),
     ];
 
-    print!(r#"cargo::rustc-check-cfg=cfg(windows, values("#);
-    for (_, ver) in feature_ver.iter().copied() { print!("{:?}, ", ver); }
+    print!(r#"cargo::rustc-check-cfg=cfg(windows, values(none(), "#); // none(): plain `cfg(windows)` for the target OS
+    for (_, ver) in feature_ver.iter() { print!("{:?}, ", ver); }
     println!("))");
 
     for (feature, ver) in feature_ver.iter().copied() {
@@ -56,7 +56,7 @@ fn main() {
     ];
 
     print!(r#"cargo::rustc-check-cfg=cfg(partition, values("#);
-    for (_, partition) in feature_partition.iter().copied() { print!("{:?}, ", partition); }
+    for (_, partition) in feature_partition.iter() { print!("{:?}, ", partition); }
     println!(r#"))"#);
 
     for (feature, partition) in feature_partition.iter().copied() {
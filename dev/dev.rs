@@ -1,3 +1,5 @@
+#![cfg(windows)]
+
 use winapi::um::winuser::*;
 
 use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
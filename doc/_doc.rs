@@ -1,5 +1,6 @@
 //! [Crate Features](features),
-//! [Known Soundness Holes &amp; Undefined Behavior Bait](soundness)
+//! [Known Soundness Holes &amp; Undefined Behavior Bait](soundness),
+//! [Changelog](changelog)
 
 use crate::*;
 use core::ops::Deref;
@@ -11,6 +12,7 @@ macro_rules! docs {
 }
 
 docs! {
+    changelog,
     features,
     soundness,
 }
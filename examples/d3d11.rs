@@ -1,15 +1,17 @@
-use mcom::errors::MethodHResult;
+#[cfg(windows)] use mcom::errors::MethodHResult;
 
-use winapi::shared::winerror::REGDB_E_IIDNOTREG;
-use winapi::um::d3dcommon::*;
-use winapi::um::d3d11::*;
+#[cfg(windows)] use winapi::shared::winerror::REGDB_E_IIDNOTREG;
+#[cfg(windows)] use winapi::um::d3dcommon::*;
+#[cfg(windows)] use winapi::um::d3d11::*;
 
-use core::convert::TryFrom;
-use core::ptr::{null, null_mut};
+#[cfg(windows)] use core::convert::TryFrom;
+#[cfg(windows)] use core::ptr::{null, null_mut};
 
 
 
-fn main() {
+#[cfg(not(windows))] fn main() { eprintln!("this example requires Windows"); }
+
+#[cfg(windows)] fn main() {
     mcom::init::sta().unwrap();
 
     let mut device = null_mut();
@@ -1,11 +1,13 @@
-use winapi::shared::d3d9::*;
-use winapi::shared::winerror::REGDB_E_IIDNOTREG;
+#[cfg(windows)] use winapi::shared::d3d9::*;
+#[cfg(windows)] use winapi::shared::winerror::REGDB_E_IIDNOTREG;
 
-use core::convert::TryFrom;
+#[cfg(windows)] use core::convert::TryFrom;
 
 
 
-fn main() {
+#[cfg(not(windows))] fn main() { eprintln!("this example requires Windows"); }
+
+#[cfg(windows)] fn main() {
     mcom::init::sta().unwrap();
     let d3d9 = unsafe { mcom::Rc::from_raw(Direct3DCreate9(D3D_SDK_VERSION)) };
 
@@ -8,7 +8,9 @@
 //! | --------- | ----------------- | -------------- | ------------- | ------- | --------- | ----------- |
 //! | [`Rc`]    | ❌&nbsp;no       | <span style="opacity: 25%">N/A</span> | <span style="opacity: 25%">N/A</span> | <span style="opacity: 25%">2000+</span> | <span style="opacity: 25%">any</span> | Your basic, super vanilla, apartment &amp; thread-local COM pointer.
 //! | [`Git`]   | ✔️&nbsp;yes      | ✔️&nbsp;yes    | ❌&nbsp;no    | <span style="opacity: 25%">2000+</span>    | <span style="opacity: 25%">any</span>    | [IGlobalInterfaceTable](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iglobalinterfacetable)-based COM pointer.
-//! | [`Agile`] | ✔️&nbsp;yes      | ✔️&nbsp;yes    | ✔️&nbsp;yes   | ⚠️ **8.1+**                                | ❌ ~~games~~ | [IAgileReference](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iagilereference)-based COM pointer.
+//! | [`Agile`] | ✔️&nbsp;yes      | ✔️&nbsp;yes    | ✔️&nbsp;8.1+  | <span style="opacity: 25%">2000+</span>    | <span style="opacity: 25%">any</span>    | [IAgileReference](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iagilereference)-based COM pointer.
+//! | [`Child`] | ❌&nbsp;no       | <span style="opacity: 25%">N/A</span> | <span style="opacity: 25%">N/A</span> | <span style="opacity: 25%">2000+</span> | <span style="opacity: 25%">any</span> | A borrowed, non-refcounted pointer to an interface owned by another COM object, even one not deriving from [IUnknown].
+//! | [`Cached`]| ❌&nbsp;no       | <span style="opacity: 25%">N/A</span> | <span style="opacity: 25%">N/A</span> | <span style="opacity: 25%">2000+</span> | <span style="opacity: 25%">any</span> | An [`Rc`] that caches the interfaces it has been cast to, for hot paths that repeatedly query the same object.  Requires the `alloc` feature.
 //!
 //! COM interfaces have complicated thread safety guarantees - when they have thread safety guarantees at all.
 //! While some of those guarantees can be expressed through the type system (e.g. many/most WinRT interfaces are guaranteed agile),
@@ -22,9 +24,18 @@
 //! and WinRT's rich metadata calls for WinRT-specific crates that can tackle that (e.g. [winrt]), that leaves this
 //! crate to tackle the messier runtime-enforced thread safety of more vanilla COM.
 //!
-//! All these smart pointers assume the COM interface implements [IUnknown].
+//! [Rc], [errors] and the [abi] types are platform neutral, and can be used with Rust-implemented COM objects on any host.
+//! APIs that require the Windows COM runtime ([Git], [Agile], `init`, `CoCreateInstance`) are only available on Windows,
+//! although the `emulator` feature provides pure-Rust stand-ins for `init`'s apartment bookkeeping and [Git]'s
+//! `IGlobalInterfaceTable` on any host.  Before Windows 8.1, or outside the app/system partitions, [Agile] falls back
+//! on a pure-Rust `IAgileReference` built on top of [Git].  That fallback holds delay-marshaled references in their
+//! creating apartment, and registers eager ones in the [Git] without marshaling them itself - so they're only as eager
+//! as [Git] is.
+//!
+//! All these smart pointers require the COM interface to implement [IUnknown], as declared by [ComInterface].
 //! It's worth noting that some "COM" interfaces like [ID3D12FunctionReflection] do not implement [IUnknown], and cannot
-//! be held in any of these COM smart pointers as a result.
+//! be held in any of these COM smart pointers as a result.  Instead, they can be borrowed from the object that owns
+//! them as a [`Child`], which does no reference counting.
 //!
 //! | Legend    | Description |
 //! | --------- | ----------- |
@@ -52,21 +63,59 @@ extern crate alloc; // XXX: this is currently required by errors::MethodHResult:
 
 #[cfg(doc)] #[path = "../doc/_doc.rs"] pub mod Documentation;
 
+pub mod abi;
+
 // smart pointers
 
-#[cfg(all(windows = "8.1", any(partition = "app", partition = "system")))] mod agile;
-#[cfg(all(windows = "8.1", any(partition = "app", partition = "system")))] pub use agile::Agile;
+#[cfg(any(feature = "emulator", all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games"))))] pub mod agile;
+#[cfg(any(feature = "emulator", all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games"))))] pub use agile::Agile;
+
+#[cfg(any(feature = "emulator", all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games"))))] pub mod git;
+#[cfg(any(feature = "emulator", all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games"))))] pub use git::Git;
+
+mod any;
+pub use any::AnyRc;
+
+pub mod array;
+pub use array::ComArray;
+
+#[cfg(feature = "alloc")] mod cached;
+#[cfg(feature = "alloc")] pub use cached::Cached;
+
+mod child;
+pub use child::Child;
+
+mod identity;
+pub use identity::Identity;
+
+pub mod ladder;
+pub use ladder::InterfaceLadder;
+
+mod out;
+pub use out::OutPtr;
+
+mod rc;
+pub use rc::Rc;
+
+mod reference;
+pub use reference::Ref;
+pub mod refcount;
 
-#[cfg(all(windows = "2000", any(partition = "app", partition = "system", partition = "games")))] mod git;
-#[cfg(all(windows = "2000", any(partition = "app", partition = "system", partition = "games")))] pub use git::Git;
+mod weak;
+pub use weak::Weak;
 
-#[cfg(all(windows = "2000", any(partition = "app", partition = "system", partition = "games")))] mod rc;
-#[cfg(all(windows = "2000", any(partition = "app", partition = "system", partition = "games")))] pub use rc::Rc;
+pub mod object;
+pub use object::ComObject;
 
 // misc
 
-#[cfg(all(windows = "2000", any(partition = "app", partition = "system", partition = "games")))] pub mod errors;
-#[cfg(all(windows = "2000", any(partition = "app", partition = "system", partition = "games")))] pub mod init;
+#[cfg(feature = "leak-check")] pub mod debug;
+#[cfg(feature = "validate")] mod validate;
+#[cfg(feature = "emulator")] pub mod emulator;
+pub mod errors;
+pub mod names;
+#[cfg(any(feature = "emulator", all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games"))))] pub mod init;
 
-#[cfg(all(windows = "2000", any(partition = "app", partition = "system", partition = "games")))] mod interface;
-#[cfg(all(windows = "2000", any(partition = "app", partition = "system", partition = "games")))] pub use interface::*;
+mod interface;
+pub use interface::*;
+#[cfg(windows)] mod winapi_interfaces;
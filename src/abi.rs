@@ -0,0 +1,207 @@
+//! Platform-neutral COM ABI types: [GUID], [HRESULT], [IUnknown], [Interface], [IWeakReference], etc.
+//!
+//! On Windows, these are re-exports of [winapi]'s definitions, so mcom's smart pointers keep working with
+//! `winapi`-declared interfaces.  Elsewhere, mcom ships its own `#[repr(C)]` definitions with identical layouts, so
+//! [Rc](crate::Rc) and friends can be used with Rust-implemented COM objects on any host.
+//!
+//! [winapi]:   https://docs.rs/winapi/0.3/
+
+#![allow(missing_docs)] // mirrors winapi's (undocumented) definitions
+#![allow(clippy::missing_safety_doc)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+
+#[cfg(windows)] pub use winapi::ctypes::c_void;
+#[cfg(windows)] pub use winapi::shared::guiddef::{GUID, IID, CLSID, REFGUID, REFIID, REFCLSID, IsEqualGUID, IsEqualIID, IsEqualCLSID};
+#[cfg(windows)] pub use winapi::shared::minwindef::{DWORD, ULONG};
+#[cfg(windows)] pub use winapi::shared::winerror::{SUCCEEDED, FAILED};
+#[cfg(windows)] pub use winapi::shared::winerror::{S_OK, S_FALSE, E_FAIL, E_INVALIDARG, E_NOINTERFACE, E_NOTIMPL, E_OUTOFMEMORY, E_POINTER, E_UNEXPECTED};
+#[cfg(windows)] pub use winapi::shared::winerror::{CO_E_NOTINITIALIZED, CO_E_NOT_SUPPORTED, REGDB_E_IIDNOTREG, RPC_E_CHANGED_MODE, RPC_E_WRONG_THREAD};
+#[cfg(windows)] pub use winapi::um::combaseapi::{AgileReferenceOptions, AGILEREFERENCE_DEFAULT, AGILEREFERENCE_DELAYEDMARSHAL};
+#[cfg(windows)] pub use winapi::um::combaseapi::{CO_MTA_USAGE_COOKIE, CO_MTA_USAGE_COOKIE__};
+#[cfg(windows)] pub use winapi::um::objbase::{COINIT, COINIT_APARTMENTTHREADED, COINIT_MULTITHREADED, COINIT_DISABLE_OLE1DDE, COINIT_SPEED_OVER_MEMORY};
+#[cfg(windows)] pub use winapi::um::unknwnbase::{IUnknown, IUnknownVtbl, LPUNKNOWN};
+#[cfg(windows)] pub use winapi::um::winnt::HRESULT;
+#[cfg(windows)] pub use winapi::Interface;
+
+#[cfg(not(windows))] pub use portable::*;
+
+
+
+#[cfg(not(windows))] mod portable {
+    pub use core::ffi::c_void;
+
+    pub type DWORD      = u32;
+    pub type ULONG      = u32;
+    pub type HRESULT    = i32;
+
+
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/guiddef/ns-guiddef-guid)\]
+    /// A globally unique identifier, laid out identically to `winapi::shared::guiddef::GUID`.
+    #[derive(Clone, Copy)]
+    #[repr(C)] pub struct GUID {
+        pub Data1: u32,
+        pub Data2: u16,
+        pub Data3: u16,
+        pub Data4: [u8; 8],
+    }
+
+    pub type IID        = GUID;
+    pub type CLSID      = GUID;
+    pub type REFGUID    = *const GUID;
+    pub type REFIID     = *const IID;
+    pub type REFCLSID   = *const IID;
+
+    pub fn IsEqualGUID(g1: &GUID, g2: &GUID) -> bool {
+        g1.Data1 == g2.Data1 && g1.Data2 == g2.Data2 && g1.Data3 == g2.Data3 && g1.Data4 == g2.Data4
+    }
+
+    pub use IsEqualGUID as IsEqualIID;
+    pub use IsEqualGUID as IsEqualCLSID;
+
+
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/winerror/nf-winerror-succeeded)\]
+    pub fn SUCCEEDED(hr: HRESULT) -> bool { hr >= 0 }
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/winerror/nf-winerror-failed)\]
+    pub fn FAILED(hr: HRESULT) -> bool { hr < 0 }
+
+    pub const S_OK          : HRESULT = 0;
+    pub const S_FALSE       : HRESULT = 1;
+    pub const E_FAIL        : HRESULT = 0x80004005_u32 as _;
+    pub const E_INVALIDARG  : HRESULT = 0x80070057_u32 as _;
+    pub const E_NOINTERFACE : HRESULT = 0x80004002_u32 as _;
+    pub const E_NOTIMPL     : HRESULT = 0x80004001_u32 as _;
+    pub const E_OUTOFMEMORY : HRESULT = 0x8007000E_u32 as _;
+    pub const E_POINTER     : HRESULT = 0x80004003_u32 as _;
+    pub const E_UNEXPECTED  : HRESULT = 0x8000FFFF_u32 as _;
+    pub const CO_E_NOTINITIALIZED   : HRESULT = 0x800401F0_u32 as _;
+    pub const CO_E_NOT_SUPPORTED    : HRESULT = 0x80004021_u32 as _;
+    pub const REGDB_E_IIDNOTREG     : HRESULT = 0x80040155_u32 as _;
+    pub const RPC_E_CHANGED_MODE    : HRESULT = 0x80010106_u32 as _;
+    pub const RPC_E_WRONG_THREAD    : HRESULT = 0x8001010E_u32 as _;
+
+
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/objbase/ne-objbase-coinit)\]
+    pub type COINIT = u32;
+    pub const COINIT_APARTMENTTHREADED  : COINIT = 0x2;
+    pub const COINIT_MULTITHREADED      : COINIT = 0x0;
+    pub const COINIT_DISABLE_OLE1DDE    : COINIT = 0x4;
+    pub const COINIT_SPEED_OVER_MEMORY  : COINIT = 0x8;
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/ne-combaseapi-agilereferenceoptions)\]
+    pub type AgileReferenceOptions = u32;
+    pub const AGILEREFERENCE_DEFAULT        : AgileReferenceOptions = 0;
+    pub const AGILEREFERENCE_DELAYEDMARSHAL : AgileReferenceOptions = 1;
+
+    pub enum CO_MTA_USAGE_COOKIE__ {}
+    pub type CO_MTA_USAGE_COOKIE = *mut CO_MTA_USAGE_COOKIE__;
+
+
+
+    /// A COM interface with an interface identifier, mirroring `winapi::Interface`.
+    pub trait Interface {
+        /// Returns the IID of the interface
+        fn uuidof() -> GUID;
+    }
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown)\]
+    /// The [IUnknown] vtable, laid out identically to `winapi::um::unknwnbase::IUnknownVtbl`.
+    #[repr(C)] pub struct IUnknownVtbl {
+        pub QueryInterface: unsafe extern "system" fn(This: *mut IUnknown, riid: REFIID, ppvObject: *mut *mut c_void) -> HRESULT,
+        pub AddRef:         unsafe extern "system" fn(This: *mut IUnknown) -> ULONG,
+        pub Release:        unsafe extern "system" fn(This: *mut IUnknown) -> ULONG,
+    }
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown)\]
+    /// The root COM interface, laid out identically to `winapi::um::unknwnbase::IUnknown`.
+    #[repr(C)] pub struct IUnknown {
+        pub lpVtbl: *const IUnknownVtbl,
+    }
+
+    pub type LPUNKNOWN = *mut IUnknown;
+
+    impl IUnknown {
+        #[inline] pub unsafe fn QueryInterface(&self, riid: REFIID, ppvObject: *mut *mut c_void) -> HRESULT {
+            ((*self.lpVtbl).QueryInterface)(self as *const _ as *mut _, riid, ppvObject)
+        }
+
+        #[inline] pub unsafe fn AddRef(&self) -> ULONG {
+            ((*self.lpVtbl).AddRef)(self as *const _ as *mut _)
+        }
+
+        #[inline] pub unsafe fn Release(&self) -> ULONG {
+            ((*self.lpVtbl).Release)(self as *const _ as *mut _)
+        }
+    }
+
+    impl Interface for IUnknown {
+        #[inline] fn uuidof() -> GUID {
+            GUID { Data1: 0x00000000, Data2: 0x0000, Data3: 0x0000, Data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46] }
+        }
+    }
+
+    crate::com_interface! {
+        @root unsafe impl ComInterface for IUnknown { type Parent = Self; }
+    }
+}
+
+
+
+// Not part of winapi 0.3 (or lacking the glue to implement them in Rust), so declared here on all platforms
+crate::interface! {
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/objidlbase/nn-objidlbase-iagileobject)\]
+    /// Marker interface for objects that are free-threaded, and can be used from any apartment without marshaling.
+    #[uuid(0x94ea2b94, 0xe9cc, 0x49e0, 0xc0, 0xff, 0xee, 0x64, 0xca, 0x8f, 0x5b, 0x90)]
+    pub interface IAgileObject(IAgileObjectVtbl, IAgileObjectImpl): IUnknown(IUnknownVtbl) {}
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iagilereference)\]
+    /// An apartment-neutral reference to an object, which can be resolved to an interface usable from the current apartment.
+    #[uuid(0xc03f6a43, 0x65a4, 0x9818, 0x98, 0x7e, 0xe0, 0xb8, 0x10, 0xd2, 0xa6, 0xf2)]
+    pub interface IAgileReference(IAgileReferenceVtbl, IAgileReferenceImpl): IUnknown(IUnknownVtbl) {
+        unsafe fn Resolve(riid: REFIID, ppvObjectReference: *mut *mut c_void) -> HRESULT;
+    }
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-inomarshal)\]
+    /// Marker interface for objects that must never be marshaled to another apartment.
+    #[uuid(0xecc8691b, 0xc1db, 0x4dc0, 0x85, 0x5e, 0x65, 0xf6, 0xc5, 0x51, 0xaf, 0x49)]
+    pub interface INoMarshal(INoMarshalVtbl, INoMarshalImpl): IUnknown(IUnknownVtbl) {}
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iglobalinterfacetable)\]
+    /// A process-wide table of interface pointers, which can be retrieved from other apartments by cookie.
+    #[uuid(0x00000146, 0x0000, 0x0000, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46)]
+    pub interface IGlobalInterfaceTable(IGlobalInterfaceTableVtbl, IGlobalInterfaceTableImpl): IUnknown(IUnknownVtbl) {
+        unsafe fn RegisterInterfaceInGlobal(pUnk: *mut IUnknown, riid: REFIID, pdwCookie: *mut DWORD) -> HRESULT;
+        unsafe fn RevokeInterfaceFromGlobal(dwCookie: DWORD) -> HRESULT;
+        unsafe fn GetInterfaceFromGlobal(dwCookie: DWORD, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT;
+    }
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/weakreference/nn-weakreference-iweakreference)\]
+    /// A weak reference to an object, which can be resolved to a strong reference while the object is alive.
+    #[uuid(0x00000037, 0x0000, 0x0000, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46)]
+    pub interface IWeakReference(IWeakReferenceVtbl, IWeakReferenceImpl): IUnknown(IUnknownVtbl) {
+        /// Writes a strong reference to interface `riid` of the object to `objectReference`, or null if the object has been destroyed.
+        unsafe fn Resolve(riid: REFIID, objectReference: *mut *mut c_void) -> HRESULT;
+    }
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/weakreference/nn-weakreference-iweakreferencesource)\]
+    /// Implemented by objects that can hand out [IWeakReference]s to themselves.
+    #[uuid(0x00000038, 0x0000, 0x0000, 0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46)]
+    pub interface IWeakReferenceSource(IWeakReferenceSourceVtbl, IWeakReferenceSourceImpl): IUnknown(IUnknownVtbl) {
+        fn GetWeakReference() -> Option<crate::Rc<IWeakReference>>;
+    }
+}
+
+
+
+#[test] fn layout() {
+    use core::mem::*;
+
+    assert_eq!(16, size_of::<GUID>());
+    assert_eq!( 4, align_of::<GUID>());
+    assert_eq!(size_of::<*const c_void>(),      size_of::<IUnknown>());
+    assert_eq!(size_of::<*const c_void>() * 3,  size_of::<IUnknownVtbl>());
+}
@@ -1,13 +1,22 @@
-use crate::{AsIUnknown, Rc};
+//! [Agile] and a pure-Rust [get_agile_reference] fallback for `RoGetAgileReference`.
+//!
+//! `RoGetAgileReference` requires Windows 8.1 and the app or system partitions.  Elsewhere - including non-Windows
+//! hosts with the `emulator` feature - [Agile] uses [get_agile_reference] instead, built on top of [Git](crate::Git)'s
+//! default [Backend].
+
+use crate::{AsIUnknown, ComObject, Rc};
+use crate::abi::*;
 use crate::errors::MethodHResult;
+use crate::git::{self, Backend};
 
-use winapi::Interface;
-use winapi::um::combaseapi::{AGILEREFERENCE_DEFAULT, AGILEREFERENCE_DELAYEDMARSHAL, AgileReferenceOptions, RoGetAgileReference};
-use winapi::um::objidlbase::IAgileReference;
+#[cfg(all(windows, windows = "8.1", any(partition = "app", partition = "system")))]
+use winapi::um::combaseapi::RoGetAgileReference;
 
 use core::convert::TryFrom;
 use core::fmt::{self, Debug, Formatter};
 use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::num::NonZeroU32;
 use core::ptr::null_mut;
 
 
@@ -18,11 +27,15 @@ use core::ptr::null_mut;
 /// [IAgileObject]:             https://learn.microsoft.com/en-us/windows/win32/api/objidlbase/nn-objidlbase-iagileobject
 pub struct Agile<I: Interface + AsIUnknown> {
     agile:      Rc<IAgileReference>,
+    options:    ReferenceOptions,
     phantom:    PhantomData<*const I>,
 }
 
 impl<I: Interface + AsIUnknown> Agile<I> {
     /// Eagerly marshal a COM interface for use in another apartment.  Will fail if this is not possible.
+    ///
+    /// With the pure-Rust [get_agile_reference] fallback, this only fails as eagerly as the [Git](crate::Git) backend's
+    /// `RegisterInterfaceInGlobal` does:  Windows' marshals on registration, the `emulator`'s never checks.
     pub fn try_from_eager(unk: impl AsRef<Rc<I>>) -> Result<Self, MethodHResult> {
         Self::ro_get_agile_reference(ReferenceOptions::DEFAULT, unk)
     }
@@ -51,24 +64,24 @@ impl<I: Interface + AsIUnknown> Agile<I> {
     ///
     /// [INoMarshal]:               https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-inomarshal
     fn ro_get_agile_reference(ro: impl Into<ReferenceOptions>, unk: impl AsRef<Rc<I>>) -> Result<Self, MethodHResult> {
-        let ro = ro.into().0;
+        let options = ro.into();
+        let ro = options.0;
         let unk = unk.as_ref();
         let unk = unk.as_iunknown_ptr();
         let mut agile = null_mut();
-        let hr = unsafe { RoGetAgileReference(ro, &I::uuidof(), unk, &mut agile) };
+        #[cfg(all(windows, windows = "8.1", any(partition = "app", partition = "system")))]
+        let hr = unsafe { RoGetAgileReference(ro, &I::uuidof(), unk, (&mut agile as *mut *mut IAgileReference).cast()) };
+        #[cfg(not(all(windows, windows = "8.1", any(partition = "app", partition = "system"))))]
+        let hr = unsafe { get_agile_reference(ro, &I::uuidof(), unk, &mut agile) };
         MethodHResult::check("RoGetAgileReference", hr)?;
         let agile = unsafe { Rc::from_raw_opt(agile) }.ok_or(MethodHResult::unchecked("RoGetAgileReference", hr))?;
-        Ok(Self { agile, phantom: PhantomData })
+        Ok(Self { agile, options, phantom: PhantomData })
     }
 
     /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-iagilereference-resolve(refiid_void))\]
     /// Get a COM pointer to `I` that is safe to use from the current thread's COM apartment
     pub fn resolve(&self) -> Result<Rc<I>, MethodHResult> {
-        let mut pv = null_mut();
-        let hr = unsafe { self.agile.Resolve(&I::uuidof(), &mut pv) };
-        MethodHResult::check("IAgileReference::Resolve", hr)?;
-        let rc = unsafe { Rc::from_raw(pv.cast()) };
-        Ok(rc)
+        unsafe { Rc::from_out_iid("IAgileReference::Resolve", |iid, ppv| self.agile.Resolve(iid, ppv)) }
     }
 }
 
@@ -89,7 +102,7 @@ unsafe impl<I: Interface + AsIUnknown> Send for Agile<I> {}
 unsafe impl<I: Interface + AsIUnknown> Sync for Agile<I> {}
 
 impl<I: Interface + AsIUnknown> Clone for Agile<I> {
-    fn clone(&self) -> Self { Self { agile: self.agile.clone(), phantom: PhantomData } }
+    fn clone(&self) -> Self { Self { agile: self.agile.clone(), options: self.options, phantom: PhantomData } }
 }
 
 impl<I: Interface + AsIUnknown> TryFrom<Rc<I>> for Agile<I> {
@@ -112,6 +125,14 @@ impl<I: Interface + AsIUnknown> TryFrom<&Agile<I>> for Rc<I> {
     fn try_from(value: &Agile<I>) -> Result<Self, Self::Error> { value.resolve() }
 }
 
+/// `Agile<IFoo>(ReferenceOptions::DEFAULT, 0x...)`:  the [name](crate::names) of the interface, the options it was
+/// created with, and the [IAgileReference] pointer.
+///
+/// [IAgileReference]:          https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iagilereference
+impl<I: Interface + AsIUnknown> Debug for Agile<I> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { write!(f, "Agile<{}>({:?}, {:?})", crate::names::Name(&I::uuidof()), self.options, self.agile.as_ptr()) }
+}
+
 impl<I: Interface + AsIUnknown> AsRef<Agile<I>> for Agile<I> {
     fn as_ref(&self) -> &Self { self }
 }
@@ -119,11 +140,17 @@ impl<I: Interface + AsIUnknown> AsRef<Agile<I>> for Agile<I> {
 
 
 /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/ne-combaseapi-agilereferenceoptions)\]
+/// Options for creating an [Agile] reference.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ReferenceOptions(u32);
 
 impl ReferenceOptions {
+    /// Eagerly marshal the interface when the reference is created.
+    /// (The pure-Rust [get_agile_reference] fallback registers it in the [Git](crate::Git) instead, see [Agile::try_from_eager].)
     pub const DEFAULT           : ReferenceOptions = ReferenceOptions(AGILEREFERENCE_DEFAULT);
+
+    /// Delay marshaling the interface until it's resolved from another apartment.
+    /// (The pure-Rust [get_agile_reference] fallback never marshals it, and only resolves it from the creating apartment.)
     pub const DELAYED_MARSHAL   : ReferenceOptions = ReferenceOptions(AGILEREFERENCE_DELAYEDMARSHAL);
 }
 
@@ -148,3 +175,108 @@ impl From<ReferenceOptions> for AgileReferenceOptions {
 impl From<()> for ReferenceOptions {
     fn from(_: ()) -> Self { ReferenceOptions::DEFAULT }
 }
+
+
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-rogetagilereference)\]
+/// A pure-Rust `RoGetAgileReference`, usable on any Windows version - or any host with the `emulator` feature.
+///
+/// | Wrapped object                | Behavior |
+/// | ----------------------------- | -------- |
+/// | Implements [INoMarshal]       | Fails with `CO_E_NOT_SUPPORTED`.
+/// | Implements [IAgileObject]     | Held directly, and resolved from any apartment.
+/// | [ReferenceOptions::DEFAULT]   | Registered with [git::default_backend]'s [IGlobalInterfaceTable], failing now only if that does:  Windows' marshals on registration, the `emulator`'s doesn't check marshalability.
+/// | [ReferenceOptions::DELAYED_MARSHAL] | Held directly, and resolved only from the creating apartment (`RPC_E_WRONG_THREAD` elsewhere).
+///
+/// On Windows, "apartment" conservatively means "thread" for delay-marshaled references.
+/// If the last reference to one of those is released from another apartment, the wrapped interface is leaked rather
+/// than released from the wrong apartment.
+///
+/// ### Safety
+///
+/// * `unk` must be null (returning `E_INVALIDARG`) or a valid COM interface pointer.
+/// * `riid` must be null (returning `E_INVALIDARG`) or valid for reads.
+/// * `ppv` must be null (returning `E_POINTER`) or valid for writes.
+///
+/// [IGlobalInterfaceTable]:        https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iglobalinterfacetable
+pub unsafe fn get_agile_reference(options: AgileReferenceOptions, riid: REFIID, unk: *mut IUnknown, ppv: *mut *mut IAgileReference) -> HRESULT {
+    if ppv.is_null() { return E_POINTER }
+    *ppv = null_mut();
+    let (Some(unk), Some(riid)) = (unk.as_ref(), riid.as_ref()) else { return E_INVALIDARG };
+    if options != AGILEREFERENCE_DEFAULT && options != AGILEREFERENCE_DELAYEDMARSHAL { return E_INVALIDARG }
+
+    let mut ptr = null_mut();
+    let hr = unk.QueryInterface(riid, &mut ptr);
+    if !SUCCEEDED(hr) { return hr }
+    let Some(unk) = Rc::<IUnknown>::from_raw_opt(ptr.cast()) else { return E_NOINTERFACE };
+
+    let held = if unk.try_cast::<INoMarshal>().is_some() {
+        return CO_E_NOT_SUPPORTED
+    } else if unk.try_cast::<IAgileObject>().is_some() {
+        Held::Agile(unk)
+    } else if options == AGILEREFERENCE_DEFAULT {
+        let backend = git::default_backend();
+        let mut cookie = 0;
+        let hr = backend.global_interface_table().RegisterInterfaceInGlobal(unk.as_ptr(), riid, &mut cookie);
+        if !SUCCEEDED(hr) { return hr }
+        let Some(cookie) = NonZeroU32::new(cookie) else { return E_UNEXPECTED };
+        Held::Git { backend, cookie }
+    } else {
+        let Some(apartment) = current_apartment() else { return CO_E_NOTINITIALIZED };
+        Held::Lazy { unk: ManuallyDrop::new(unk), apartment }
+    };
+
+    *ppv = ComObject::new(AgileReference(held)).into_rc::<IAgileReference>().into_raw();
+    S_OK
+}
+
+struct AgileReference(Held);
+
+enum Held {
+    Agile(Rc<IUnknown>),
+    Git { backend: &'static dyn Backend, cookie: NonZeroU32 },
+    Lazy { unk: ManuallyDrop<Rc<IUnknown>>, apartment: ApartmentId },
+}
+
+// SAFETY: agile objects are usable from any thread, GIT cookies are just integers, and lazily held interfaces are
+// only used or released from their creating apartment.
+unsafe impl Send for AgileReference {}
+unsafe impl Sync for AgileReference {}
+
+crate::implement!(AgileReference: IAgileReference, IAgileObject);
+
+impl IAgileObjectImpl for AgileReference {}
+
+impl IAgileReferenceImpl for AgileReference {
+    unsafe fn Resolve(&self, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        if ppv.is_null() { return E_POINTER }
+        *ppv = null_mut();
+        match &self.0 {
+            Held::Agile(unk) => unk.QueryInterface(riid, ppv),
+            Held::Git { backend, cookie } => backend.global_interface_table().GetInterfaceFromGlobal(cookie.get(), riid, ppv),
+            Held::Lazy { unk, apartment } if current_apartment() == Some(*apartment) => unk.QueryInterface(riid, ppv),
+            Held::Lazy { .. } => RPC_E_WRONG_THREAD,
+        }
+    }
+}
+
+impl Drop for AgileReference {
+    fn drop(&mut self) {
+        match &mut self.0 {
+            Held::Agile(_) => {},
+            Held::Git { backend, cookie } => {
+                let hr = unsafe { backend.global_interface_table().RevokeInterfaceFromGlobal(cookie.get()) };
+                debug_assert!(SUCCEEDED(hr), "IGlobalInterfaceTable::RevokeInterfaceFromGlobal failed with HRESULT == 0x{:08x}", hr);
+            },
+            Held::Lazy { unk, apartment } => if current_apartment() == Some(*apartment) {
+                unsafe { ManuallyDrop::drop(unk) }
+            },
+        }
+    }
+}
+
+#[cfg(not(windows))] type ApartmentId = crate::emulator::Apartment;
+#[cfg(not(windows))] fn current_apartment() -> Option<ApartmentId> { crate::emulator::current_apartment() }
+
+#[cfg(windows)] type ApartmentId = u32;
+#[cfg(windows)] fn current_apartment() -> Option<ApartmentId> { Some(unsafe { winapi::um::processthreadsapi::GetCurrentThreadId() }) }
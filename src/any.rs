@@ -0,0 +1,91 @@
+use crate::{ComInterface, Identity, Rc};
+use crate::abi::{IID, IUnknown, IsEqualIID};
+use crate::errors::MethodHResult;
+
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+
+
+
+/// A type-erased [Rc]:  an interface pointer, and the IID of the interface it points to.
+///
+/// Equality, ordering, and hashing are based on COM identity, like [Rc]'s - regardless of IIDs.
+///
+/// ```
+/// # use mcom::abi::*;
+/// # use mcom::{AnyRc, Rc};
+/// fn register(registry: &mut Vec<AnyRc>, rc: Rc<IAgileObject>) { registry.push(rc.into()) }
+/// fn agile_objects(registry: &[AnyRc]) -> impl Iterator<Item = &Rc<IAgileObject>> { registry.iter().filter_map(AnyRc::downcast_ref) }
+/// ```
+#[derive(Clone)] pub struct AnyRc {
+    iid:    IID,
+    rc:     Rc<IUnknown>,
+}
+
+impl AnyRc {
+    /// The IID of the interface pointed to.
+    pub fn iid(&self) -> &IID { &self.iid }
+
+    /// The interface pointer, as an [IUnknown].
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    pub fn as_unknown(&self) -> &Rc<IUnknown> { &self.rc }
+
+    /// The COM identity of the object.
+    pub fn identity(&self) -> Identity { self.rc.identity() }
+
+    /// `true` if this points to an `I`.
+    pub fn is<I: ComInterface>(&self) -> bool { IsEqualIID(&self.iid, &I::uuidof()) }
+
+    /// Borrow as an <code>[Rc]&lt;I&gt;</code> if this points to an `I`.  Never calls [QueryInterface].
+    ///
+    /// [QueryInterface]:   https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void)
+    pub fn downcast_ref<I: ComInterface>(&self) -> Option<&Rc<I>> {
+        if !self.is::<I>() { return None }
+        Some(unsafe { &*(&self.rc as *const Rc<IUnknown>).cast::<Rc<I>>() })
+    }
+
+    /// Get an <code>[Rc]&lt;I&gt;</code>:  via [AddRef] if this points to an `I`, otherwise via [QueryInterface] (see [Rc::cast].)
+    ///
+    /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+    /// [QueryInterface]:   https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void)
+    #[track_caller]
+    pub fn downcast<I: ComInterface>(&self) -> Result<Rc<I>, MethodHResult> {
+        match self.downcast_ref::<I>() {
+            Some(rc)    => Ok(rc.clone()),
+            None        => self.rc.cast(),
+        }
+    }
+}
+
+impl<I: ComInterface> From<Rc<I>> for AnyRc {
+    fn from(rc: Rc<I>) -> Self { Self { iid: I::uuidof(), rc: rc.into_unknown() } }
+}
+
+impl<I: ComInterface> From<&Rc<I>> for AnyRc {
+    fn from(rc: &Rc<I>) -> Self { rc.clone().into() }
+}
+
+/// `AnyRc<IFoo>(0x...)`:  the [name](crate::names) of the interface, and the interface pointer.
+impl Debug for AnyRc {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { write!(f, "AnyRc<{}>({:?})", crate::names::Name(&self.iid), self.rc.as_ptr()) }
+}
+
+impl PartialEq for AnyRc {
+    fn eq(&self, other: &Self) -> bool { self.rc == other.rc }
+}
+
+impl Eq for AnyRc {}
+
+impl PartialOrd for AnyRc {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for AnyRc {
+    fn cmp(&self, other: &Self) -> Ordering { self.rc.cmp(&other.rc) }
+}
+
+impl Hash for AnyRc {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.rc.hash(state) }
+}
@@ -0,0 +1,248 @@
+//! [ComArray] and the [Allocator]s owning it's storage.
+//!
+//! | Allocator     | Availability          | Description |
+//! | ------------- | --------------------- | ----------- |
+//! | [CoTaskMem]   | Windows               | [CoTaskMemAlloc] / [CoTaskMemFree], as used by COM APIs returning arrays.  The default on Windows.
+//! | [RustHeap]    | Any host (`alloc`)    | Rust's global allocator.  The default elsewhere.
+//!
+//! [CoTaskMemAlloc]:   https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-cotaskmemalloc
+//! [CoTaskMemFree]:    https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-cotaskmemfree
+
+use crate::{AsIUnknown, Rc};
+use crate::abi::c_void;
+
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
+
+use core::fmt::{self, Debug, Formatter};
+#[cfg(feature = "alloc")] use core::iter::FromIterator;
+use core::mem::{ManuallyDrop, align_of, size_of};
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+
+
+
+/// An owned array of interface pointers, such as those returned by enumerators.
+///
+/// Each interface pointer is [Release]d, and then the array itself is freed with it's [Allocator], when dropped.
+///
+/// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+pub struct ComArray<I: AsIUnknown> {
+    ptr:        Option<NonNull<Option<Rc<I>>>>, // n.b. may be non-null even if `len` is 0, and then still needs freeing
+    len:        usize,
+    allocator:  &'static dyn Allocator,
+}
+
+impl<I: AsIUnknown> ComArray<I> {
+    /// Take ownership of an array of `len` interface pointers, allocated by the [default_allocator].
+    ///
+    /// ### Safety
+    ///
+    /// * `ptr` may be null if `len` is 0.  Otherwise:
+    /// * `ptr` must have been allocated by the [default_allocator], and point to `len` interface pointers.
+    /// * Each interface pointer may be null.  Otherwise it must be a "valid" [IUnknown]-derived COM interface pointer,
+    ///   accessible from the current COM apartment, whose reference is owned by the caller.
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[cfg(any(windows, feature = "alloc"))]
+    #[track_caller]
+    pub unsafe fn from_raw(ptr: *mut *mut I, len: usize) -> Self {
+        Self::from_raw_in(default_allocator(), ptr, len)
+    }
+
+    /// Take ownership of an array of `len` interface pointers, allocated by `allocator`.
+    ///
+    /// ### Safety
+    ///
+    /// * `ptr` may be null if `len` is 0.  Otherwise:
+    /// * `ptr` must have been allocated by `allocator`, and point to `len` interface pointers.
+    /// * Each interface pointer may be null.  Otherwise it must be a "valid" [IUnknown]-derived COM interface pointer,
+    ///   accessible from the current COM apartment, whose reference is owned by the caller.
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[track_caller]
+    pub unsafe fn from_raw_in(allocator: &'static dyn Allocator, ptr: *mut *mut I, len: usize) -> Self {
+        assert!(!ptr.is_null() || len == 0, "ComArray::from_raw_in: null array with non-zero length");
+        for i in 0 .. len {
+            let slot = ptr.add(i);
+            slot.cast::<Option<Rc<I>>>().write(Rc::from_raw_opt(*slot));
+        }
+        Self { ptr: NonNull::new(ptr.cast()), len, allocator }
+    }
+
+    /// Copy `vec` into a new array allocated by `allocator`.  Requires the `alloc` feature.
+    ///
+    /// Panics if allocation fails.
+    #[cfg(feature = "alloc")]
+    pub fn from_vec_in(allocator: &'static dyn Allocator, vec: Vec<Option<Rc<I>>>) -> Self {
+        let len = vec.len();
+        if len == 0 { return Self { ptr: None, len, allocator } }
+        let bytes = len.checked_mul(size_of::<*mut I>()).expect("ComArray::from_vec_in: array too large");
+        let ptr = NonNull::new(allocator.alloc(bytes)).expect("ComArray::from_vec_in: allocation failed").cast::<Option<Rc<I>>>();
+        for (i, rc) in vec.into_iter().enumerate() { unsafe { ptr.as_ptr().add(i).write(rc) } }
+        Self { ptr: Some(ptr), len, allocator }
+    }
+
+    /// Give up ownership of the array and it's interface pointers, for returning to a caller that will free them with this array's [Allocator].
+    ///
+    /// Returns a null pointer for empty arrays that were created without any storage, e.g. by [from_vec_in](Self::from_vec_in).
+    #[track_caller]
+    pub fn into_raw(self) -> (*mut *mut I, usize) {
+        let this = ManuallyDrop::new(self);
+        #[cfg(feature = "leak-check")] for rc in this.iter().flatten() { crate::debug::escaped(rc.as_ptr() as usize) }
+        (this.ptr.map_or(ptr::null_mut(), |ptr| ptr.as_ptr().cast()), this.len)
+    }
+
+    /// The [Allocator] that will free this array.
+    pub fn allocator(&self) -> &'static dyn Allocator { self.allocator }
+
+    fn data(&self) -> *mut Option<Rc<I>> { self.ptr.unwrap_or(NonNull::dangling()).as_ptr() }
+
+    /// Free the array itself (but not it's elements.)
+    unsafe fn free(&self) { if let Some(ptr) = self.ptr { self.allocator.free(ptr.as_ptr().cast()) } }
+}
+
+impl<I: AsIUnknown> Deref for ComArray<I> {
+    type Target = [Option<Rc<I>>];
+    fn deref(&self) -> &Self::Target { unsafe { core::slice::from_raw_parts(self.data(), self.len) } }
+}
+
+impl<I: AsIUnknown> DerefMut for ComArray<I> {
+    fn deref_mut(&mut self) -> &mut Self::Target { unsafe { core::slice::from_raw_parts_mut(self.data(), self.len) } }
+}
+
+impl<I: AsIUnknown> Debug for ComArray<I> where Rc<I> : Debug {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { f.debug_list().entries(self.iter()).finish() }
+}
+
+impl<I: AsIUnknown> Drop for ComArray<I> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(&mut **self) };
+        unsafe { self.free() };
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: AsIUnknown> FromIterator<Option<Rc<I>>> for ComArray<I> {
+    fn from_iter<It: IntoIterator<Item = Option<Rc<I>>>>(iter: It) -> Self { Self::from_vec_in(default_allocator(), iter.into_iter().collect()) }
+}
+
+impl<I: AsIUnknown> IntoIterator for ComArray<I> {
+    type Item = Option<Rc<I>>;
+    type IntoIter = IntoIter<I>;
+    fn into_iter(self) -> IntoIter<I> { IntoIter { next: 0, array: ManuallyDrop::new(self) } }
+}
+
+impl<'a, I: AsIUnknown> IntoIterator for &'a ComArray<I> {
+    type Item = &'a Option<Rc<I>>;
+    type IntoIter = core::slice::Iter<'a, Option<Rc<I>>>;
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+impl<'a, I: AsIUnknown> IntoIterator for &'a mut ComArray<I> {
+    type Item = &'a mut Option<Rc<I>>;
+    type IntoIter = core::slice::IterMut<'a, Option<Rc<I>>>;
+    fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+}
+
+
+
+/// A by-value iterator over the interface pointers of a [ComArray].
+pub struct IntoIter<I: AsIUnknown> {
+    array:  ManuallyDrop<ComArray<I>>,
+    next:   usize,
+}
+
+impl<I: AsIUnknown> Iterator for IntoIter<I> {
+    type Item = Option<Rc<I>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.array.len { return None }
+        let rc = unsafe { self.array.data().add(self.next).read() };
+        self.next += 1;
+        Some(rc)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.array.len - self.next;
+        (n, Some(n))
+    }
+}
+
+impl<I: AsIUnknown> ExactSizeIterator for IntoIter<I> {}
+
+impl<I: AsIUnknown> Drop for IntoIter<I> {
+    fn drop(&mut self) {
+        for _ in &mut *self {}
+        unsafe { self.array.free() };
+    }
+}
+
+
+
+/// A source of memory for [ComArray]s.
+///
+/// ### Safety
+///
+/// * [alloc](Self::alloc) must return null, or memory suitably aligned for pointers, valid for `bytes` bytes until [free](Self::free)d.
+/// * [free](Self::free) must accept anything [alloc](Self::alloc) returned.
+pub unsafe trait Allocator : Send + Sync + 'static {
+    /// Allocate `bytes` (non-zero) bytes of pointer-aligned memory, or return null on failure.
+    fn alloc(&self, bytes: usize) -> *mut c_void;
+
+    /// Free memory previously returned by [alloc](Self::alloc).
+    ///
+    /// ### Safety
+    ///
+    /// * `ptr` must have been returned by [alloc](Self::alloc) of this same allocator, and not already freed.
+    unsafe fn free(&self, ptr: *mut c_void);
+}
+
+/// The [Allocator] used by [ComArray::from_raw] and [FromIterator]:  [CoTaskMem] on Windows, otherwise [RustHeap] (requires the `alloc` feature.)
+#[cfg(any(windows, feature = "alloc"))]
+pub fn default_allocator() -> &'static dyn Allocator {
+    #[cfg(windows)] { &CoTaskMem }
+    #[cfg(not(windows))] { &RustHeap }
+}
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-cotaskmemalloc)\]
+/// [CoTaskMemAlloc] / [CoTaskMemFree], as used by COM APIs returning arrays.
+///
+/// [CoTaskMemAlloc]:   https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-cotaskmemalloc
+/// [CoTaskMemFree]:    https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-cotaskmemfree
+#[cfg(windows)]
+#[derive(Clone, Copy, Debug, Default)] pub struct CoTaskMem;
+
+#[cfg(windows)]
+unsafe impl Allocator for CoTaskMem {
+    fn alloc(&self, bytes: usize) -> *mut c_void { unsafe { winapi::um::combaseapi::CoTaskMemAlloc(bytes) } }
+    unsafe fn free(&self, ptr: *mut c_void) { winapi::um::combaseapi::CoTaskMemFree(ptr) }
+}
+
+/// Rust's global allocator, for [ComArray]s that never cross an FFI boundary (or for testing on any host.)  Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Default)] pub struct RustHeap;
+
+#[cfg(feature = "alloc")]
+unsafe impl Allocator for RustHeap {
+    fn alloc(&self, bytes: usize) -> *mut c_void {
+        let Some(layout) = Self::layout(bytes) else { return ptr::null_mut() };
+        let header = unsafe { alloc::alloc::alloc(layout) }.cast::<usize>();
+        if header.is_null() { return ptr::null_mut() }
+        unsafe { header.write(bytes) };
+        unsafe { header.add(1) }.cast()
+    }
+
+    unsafe fn free(&self, ptr: *mut c_void) {
+        let header = ptr.cast::<usize>().sub(1);
+        alloc::alloc::dealloc(header.cast(), Self::layout(header.read()).unwrap());
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl RustHeap {
+    /// `bytes` prefixed by a `usize` header recording `bytes`, so [free](Allocator::free) can recover the layout.
+    fn layout(bytes: usize) -> Option<core::alloc::Layout> {
+        const _ : () = assert!(align_of::<usize>() >= align_of::<*mut c_void>());
+        core::alloc::Layout::from_size_align(bytes.checked_add(size_of::<usize>())?, align_of::<usize>()).ok()
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{ComInterface, Rc};
+use crate::abi::{IID, IUnknown, IsEqualIID};
+use crate::errors::MethodHResult;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use core::cell::{Cell, UnsafeCell};
+use core::fmt::{self, Debug, Formatter};
+use core::ops::Deref;
+use core::ptr::NonNull;
+
+
+
+/// An [Rc] with a cache of interfaces it has already been [cast](Self::cast) to, for hot paths that repeatedly query the same object.  Requires the `alloc` feature.
+///
+/// The first [cast](Self::cast) to a given interface calls [QueryInterface] as usual.  Later casts to the same interface
+/// borrow the cached <code>&amp;[Rc]&lt;I2&gt;</code> without any [QueryInterface], [AddRef], or [Release] calls.
+/// The first `N` interfaces are cached inline, the rest in individually boxed entries.  Failed casts aren't cached.
+///
+/// ```
+/// # use mcom::abi::*;
+/// # use mcom::{Cached, Rc};
+/// fn per_frame(unk: &Cached<IUnknown>) {
+///     if let Some(agile) = unk.try_cast::<IAgileObject>() { // only the first call per Cached will QueryInterface
+///         let _ : &Rc<IAgileObject> = agile;
+///     }
+/// }
+/// ```
+///
+/// Cached interfaces stay borrowed until the [Cached] is [clear](Self::clear)ed or dropped:
+///
+/// ```compile_fail
+/// # use mcom::abi::*;
+/// # use mcom::Cached;
+/// fn dangle(mut unk: Cached<IUnknown>) {
+///     let agile = unk.cast::<IAgileObject>().unwrap();
+///     unk.clear();
+///     let _ = agile;
+/// }
+/// ```
+///
+/// [QueryInterface]:   https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void)
+/// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+/// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+pub struct Cached<I: ComInterface, const N: usize = 4> {
+    rc:     Rc<I>,
+    // Entries are only ever appended while shared, never modified or removed, so borrows of them stay valid until `&mut self`.
+    inline: [UnsafeCell<Option<Entry>>; N],
+    len:    Cell<usize>,
+    spill:  UnsafeCell<Spill>,
+}
+
+struct Entry {
+    iid:    IID,
+    rc:     Rc<IUnknown>, // n.b. actually the `iid` interface pointer
+}
+
+/// Entries past the first `N`.  n.b. not `Vec<Box<Entry>>`:  moving a `Box` would invalidate borrows of it's contents.
+#[derive(Default)] struct Spill(Vec<NonNull<Entry>>);
+
+impl Spill {
+    fn clear(&mut self) { for entry in self.0.drain(..) { drop(unsafe { Box::from_raw(entry.as_ptr()) }) } }
+}
+
+impl Drop for Spill {
+    fn drop(&mut self) { self.clear() }
+}
+
+impl<I: ComInterface, const N: usize> Cached<I, N> {
+    /// Wrap `rc` with an empty cache.
+    pub fn new(rc: Rc<I>) -> Self {
+        Self { rc, inline: core::array::from_fn(|_| UnsafeCell::new(None)), len: Cell::new(0), spill: UnsafeCell::new(Spill::default()) }
+    }
+
+    /// The wrapped [Rc].
+    pub fn as_rc(&self) -> &Rc<I> { &self.rc }
+
+    /// Unwrap the [Rc], [Release]ing all cached interfaces.
+    ///
+    /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    pub fn into_rc(self) -> Rc<I> { self.rc }
+
+    /// The number of interfaces cached.
+    pub fn len(&self) -> usize { self.len.get() }
+
+    /// `true` if no interfaces have been cached yet.
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// [Release] all cached interfaces.
+    ///
+    /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    pub fn clear(&mut self) {
+        for slot in self.inline.iter_mut() { *slot.get_mut() = None; }
+        self.spill.get_mut().clear();
+        self.len.set(0);
+    }
+
+    /// Like [Rc::try_cast], but returns a cached interface if available.
+    ///
+    /// Discards the reason for any failure - see [Cached::cast] to get it.
+    #[track_caller]
+    pub fn try_cast<I2: ComInterface>(&self) -> Option<&Rc<I2>> {
+        self.cast().ok()
+    }
+
+    /// Like [Rc::cast], but returns a cached interface if available, and caches the result of successful queries.
+    ///
+    /// Casting to `I` itself borrows the wrapped [Rc] without querying or caching anything.
+    #[track_caller]
+    pub fn cast<I2: ComInterface>(&self) -> Result<&Rc<I2>, MethodHResult> {
+        let iid = I2::uuidof();
+        if IsEqualIID(&iid, &I::uuidof()) { return Ok(unsafe { &*(&self.rc as *const Rc<I>).cast() }) }
+        if let Some(rc) = self.find(&iid) { return Ok(unsafe { &*(rc as *const Rc<IUnknown>).cast() }) }
+        let rc = self.rc.cast::<I2>()?.into_unknown();
+        let rc = self.push(Entry { iid, rc });
+        Ok(unsafe { &*(rc as *const Rc<IUnknown>).cast() })
+    }
+
+    fn find(&self, iid: &IID) -> Option<&Rc<IUnknown>> {
+        let len = self.len.get();
+        let inline = self.inline.iter().take(len).map(|slot| unsafe { &*slot.get() }.as_ref().unwrap());
+        let spill = unsafe { &*self.spill.get() }.0.iter().take(len.saturating_sub(N)).map(|entry| unsafe { entry.as_ref() });
+        inline.chain(spill).find(|entry| IsEqualIID(&entry.iid, iid)).map(|entry| &entry.rc)
+    }
+
+    fn push(&self, entry: Entry) -> &Rc<IUnknown> {
+        let len = self.len.get();
+        let entry = if let Some(slot) = self.inline.get(len) {
+            // SAFETY: slots at or past `len` are never borrowed
+            let slot = unsafe { &mut *slot.get() };
+            &*slot.insert(entry)
+        } else {
+            // SAFETY:  the Vec itself is never borrowed outside of `find` and `push`, only the entries it points to
+            let entry = NonNull::from(Box::leak(Box::new(entry)));
+            unsafe { &mut *self.spill.get() }.0.push(entry);
+            unsafe { &*entry.as_ptr() }
+        };
+        self.len.set(len + 1);
+        &entry.rc
+    }
+}
+
+impl<I: ComInterface, const N: usize> Deref for Cached<I, N> {
+    type Target = Rc<I>;
+    fn deref(&self) -> &Rc<I> { &self.rc }
+}
+
+impl<I: ComInterface, const N: usize> From<Rc<I>> for Cached<I, N> {
+    fn from(rc: Rc<I>) -> Self { Self::new(rc) }
+}
+
+impl<I: ComInterface, const N: usize> Clone for Cached<I, N> {
+    /// Clones the wrapped [Rc] with an empty cache.
+    fn clone(&self) -> Self { Self::new(self.rc.clone()) }
+}
+
+/// `Cached(Rc<IFoo>(0x...), cached: 2)`
+impl<I: ComInterface, const N: usize> Debug for Cached<I, N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { write!(f, "Cached({:?}, cached: {})", self.rc, self.len()) }
+}
@@ -0,0 +1,147 @@
+use crate::{ComInterface, RawInterface, Rc};
+
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr::NonNull;
+
+
+
+/// A borrowed, non-refcounted interface pointer, owned by a COM object that outlives `'parent`.
+///
+/// Some "COM" interfaces like [ID3D12FunctionReflection] don't derive from [IUnknown], and as such can't be held in an [Rc].
+/// Instead, they're owned by another COM object ([ID3D12LibraryReflection] in this case), and only remain valid for as long as it does.
+/// [Child] borrows the owner's [Rc] to enforce that at compile time, and otherwise acts like a <code>&amp;'parent I</code>.
+///
+/// ```
+/// # use mcom::abi::*;
+/// # use mcom::{Child, Rc};
+/// mcom::interface! {
+///     #[uuid(0x5e1f3a70, 0x2b4c, 0x4d8e, 0x9f, 0x01, 0x6a, 0x7b, 0x8c, 0x9d, 0xae, 0xbf)]
+///     pub interface IVariable(IVariableVtbl) {
+///         fn GetValue() -> u32;
+///     }
+///
+///     #[uuid(0x5e1f3a70, 0x2b4c, 0x4d8e, 0x9f, 0x01, 0x6a, 0x7b, 0x8c, 0x9d, 0xae, 0xc0)]
+///     pub interface IReflection(IReflectionVtbl): IUnknown(IUnknownVtbl) {
+///         unsafe fn GetVariable(index: u32) -> *mut IVariable;
+///     }
+/// }
+///
+/// fn first_value(reflection: &Rc<IReflection>) -> Option<u32> {
+///     let variable = unsafe { Child::from_raw_opt(reflection, reflection.GetVariable(0)) }?;
+///     variable.GetValue().ok()
+/// }
+/// ```
+///
+/// A [Child] can't outlive the [Rc] it was borrowed from:
+///
+/// ```compile_fail
+/// # use mcom::abi::*;
+/// # use mcom::{Child, Rc};
+/// # mcom::interface! {
+/// #     #[uuid(0x5e1f3a70, 0x2b4c, 0x4d8e, 0x9f, 0x01, 0x6a, 0x7b, 0x8c, 0x9d, 0xae, 0xbf)]
+/// #     pub interface IVariable(IVariableVtbl) { fn GetValue() -> u32; }
+/// #     #[uuid(0x5e1f3a70, 0x2b4c, 0x4d8e, 0x9f, 0x01, 0x6a, 0x7b, 0x8c, 0x9d, 0xae, 0xc0)]
+/// #     pub interface IReflection(IReflectionVtbl): IUnknown(IUnknownVtbl) { unsafe fn GetVariable(index: u32) -> *mut IVariable; }
+/// # }
+/// fn dangling(reflection: Rc<IReflection>) -> u32 {
+///     let variable = unsafe { Child::from_raw(&reflection, reflection.GetVariable(0)) };
+///     drop(reflection);
+///     variable.GetValue().unwrap()
+/// }
+/// ```
+///
+/// [IUnknown]:                    https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+/// [ID3D12FunctionReflection]:     https://learn.microsoft.com/en-us/windows/win32/api/d3d12shader/nn-d3d12shader-id3d12functionreflection
+/// [ID3D12LibraryReflection]:      https://learn.microsoft.com/en-us/windows/win32/api/d3d12shader/nn-d3d12shader-id3d12libraryreflection
+#[repr(transparent)] pub struct Child<'parent, I: RawInterface> {
+    ptr:    NonNull<I>,
+    parent: PhantomData<&'parent I>,
+}
+
+impl<'parent, I: RawInterface> Child<'parent, I> {
+    /// Borrow a raw interface pointer owned by `parent`.  Nothing is [AddRef]ed or [Release]d.
+    ///
+    /// ### Safety
+    ///
+    /// * `ptr` may be null, in which case `None` will be returned.  Otherwise:
+    /// * `ptr` must be a "valid" interface pointer, accessible from the current COM apartment.
+    /// * `ptr` must remain valid for as long as `parent`'s COM object remains alive.
+    ///
+    /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+    /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    pub unsafe fn from_raw_opt<P: ComInterface>(parent: &'parent Rc<P>, ptr: *mut I) -> Option<Self> {
+        let _ = parent;
+        Some(Self { ptr: NonNull::new(ptr)?, parent: PhantomData })
+    }
+
+    /// Borrow a raw interface pointer owned by `parent`.  Nothing is [AddRef]ed or [Release]d.
+    ///
+    /// ### Safety
+    ///
+    /// * `ptr` may be null, but this will result in a panic.  Otherwise:
+    /// * `ptr` must be a "valid" interface pointer, accessible from the current COM apartment.
+    /// * `ptr` must remain valid for as long as `parent`'s COM object remains alive.
+    ///
+    /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+    /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    #[track_caller]
+    pub unsafe fn from_raw<P: ComInterface>(parent: &'parent Rc<P>, ptr: *mut I) -> Self {
+        Self::from_raw_opt(parent, ptr).unwrap()
+    }
+
+    /// Borrow a raw interface pointer owned by the same COM object as `sibling`, such as an
+    /// [ID3D12ShaderReflectionVariable] returned by an [ID3D12ShaderReflectionConstantBuffer].
+    ///
+    /// ### Safety
+    ///
+    /// * `ptr` may be null, in which case `None` will be returned.  Otherwise:
+    /// * `ptr` must be a "valid" interface pointer, accessible from the current COM apartment.
+    /// * `ptr` must remain valid for as long as `sibling`'s owning COM object remains alive.
+    ///
+    /// [ID3D12ShaderReflectionVariable]:       https://learn.microsoft.com/en-us/windows/win32/api/d3d12shader/nn-d3d12shader-id3d12shaderreflectionvariable
+    /// [ID3D12ShaderReflectionConstantBuffer]: https://learn.microsoft.com/en-us/windows/win32/api/d3d12shader/nn-d3d12shader-id3d12shaderreflectionconstantbuffer
+    pub unsafe fn from_raw_sibling_opt<S: RawInterface>(sibling: Child<'parent, S>, ptr: *mut I) -> Option<Self> {
+        let _ = sibling;
+        Some(Self { ptr: NonNull::new(ptr)?, parent: PhantomData })
+    }
+
+    /// Borrow a raw interface pointer owned by the same COM object as `sibling`.
+    ///
+    /// ### Safety
+    ///
+    /// * `ptr` may be null, but this will result in a panic.  Otherwise:
+    /// * `ptr` must be a "valid" interface pointer, accessible from the current COM apartment.
+    /// * `ptr` must remain valid for as long as `sibling`'s owning COM object remains alive.
+    #[track_caller]
+    pub unsafe fn from_raw_sibling<S: RawInterface>(sibling: Child<'parent, S>, ptr: *mut I) -> Self {
+        Self::from_raw_sibling_opt(sibling, ptr).unwrap()
+    }
+
+    /// Retrieve the raw interface pointer.
+    pub fn as_ptr(&self) -> *mut I { self.ptr.as_ptr() }
+}
+
+impl<I: RawInterface> Clone for Child<'_, I> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<I: RawInterface> Copy for Child<'_, I> {}
+
+/// `Child<IFoo>(0x...)`:  the Rust type name of the interface (sans module path), and the interface pointer.
+impl<I: RawInterface> Debug for Child<'_, I> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = core::any::type_name::<I>();
+        write!(f, "Child<{}>({:?})", name.rsplit("::").next().unwrap_or(name), self.ptr)
+    }
+}
+
+impl<I: RawInterface> Deref for Child<'_, I> {
+    type Target = I;
+    fn deref(&self) -> &I { unsafe { self.ptr.as_ref() } }
+}
+
+impl<I: RawInterface> AsRef<I> for Child<'_, I> {
+    fn as_ref(&self) -> &I { self }
+}
@@ -0,0 +1,184 @@
+//! Debugging aids for tracking down leaked COM references.  Requires the `leak-check` feature.
+//!
+//! With `leak-check` enabled, every live [Rc] is recorded along with the location that created it
+//! (via `#[track_caller]`), the thread it was created on, the identity of the object it refers to, and - if enabled
+//! via `RUST_BACKTRACE` / `RUST_LIB_BACKTRACE` - a [Backtrace].
+//!
+//! *   [Rc::into_raw] and [Rc::leak] don't forget a reference, they mark it as [escaped](LiveReference::escaped).
+//! *   [Rc::from_raw] and friends re-adopt an escaped reference to the same pointer if there is one.
+//! *   [Rc]s are indistinguishable from each other beyond their pointer, so dropping one forgets the most recently
+//!     created reference to the same pointer - creation sites of duplicate references may be swapped around.
+//!
+//! ```
+//! # #[cfg(feature = "leak-check")] {
+//! # use mcom::abi::*;
+//! # mcom::interface! {
+//! #     #[uuid(0x5b1e7f0c, 0x2d4a, 0x4c93, 0x8e, 0x61, 0x0a, 0x9f, 0x3b, 0x72, 0xd5, 0x14)]
+//! #     pub interface IFoo(IFooVtbl, IFooImpl): IUnknown(IUnknownVtbl) {}
+//! # }
+//! # struct Foo;
+//! # mcom::implement!(Foo: IFoo);
+//! # impl IFooImpl for Foo {}
+//! mcom::debug::assert_no_leaks(|| {
+//!     let foo : mcom::Rc<IFoo> = mcom::ComObject::new(Foo).into_rc();
+//!     let raw = foo.clone().into_raw();
+//!     drop(unsafe { mcom::Rc::from_raw(raw) }); // without this, `assert_no_leaks` would panic
+//! });
+//! # }
+//! ```
+
+use crate::Rc;
+use crate::abi::*;
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::panic::Location;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+use std::vec::Vec;
+
+use core::ptr::null_mut;
+
+
+
+/// A live [Rc], as recorded by the `leak-check` feature.
+#[derive(Clone)]
+pub struct LiveReference {
+    serial:     u64,
+    pointer:    usize,
+    identity:   usize,
+    location:   &'static Location<'static>,
+    escaped:    Option<&'static Location<'static>>,
+    thread:     ThreadId,
+    backtrace:  Option<Arc<Backtrace>>,
+}
+
+impl LiveReference {
+    /// The interface pointer held by the [Rc].
+    pub fn pointer(&self) -> *mut IUnknown { self.pointer as *mut IUnknown }
+
+    /// The [IUnknown] identity of the object the [Rc] refers to, or null if `QueryInterface(IUnknown)` failed.
+    pub fn identity(&self) -> *mut IUnknown { self.identity as *mut IUnknown }
+
+    /// Where the [Rc] was created (or [cloned](Clone::clone), [cast](Rc::try_cast), etc.)
+    pub fn location(&self) -> &'static Location<'static> { self.location }
+
+    /// Where the [Rc] was converted into a raw pointer via [Rc::into_raw] or [Rc::leak], if it was.
+    pub fn escaped(&self) -> Option<&'static Location<'static>> { self.escaped }
+
+    /// The thread the [Rc] was created on.
+    pub fn thread(&self) -> ThreadId { self.thread }
+
+    /// A [Backtrace] of the [Rc]'s creation, if backtraces were enabled via `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`.
+    pub fn backtrace(&self) -> Option<&Backtrace> { self.backtrace.as_deref() }
+}
+
+impl Debug for LiveReference {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("LiveReference")
+            .field("pointer",   &self.pointer())
+            .field("identity",  &self.identity())
+            .field("location",  &self.location)
+            .field("escaped",   &self.escaped)
+            .field("thread",    &self.thread)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Display for LiveReference {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?} (object {:?}) created at {}", self.pointer(), self.identity(), self.location)?;
+        if let Some(escaped) = self.escaped { write!(f, ", escaped via into_raw/leak at {}", escaped)?; }
+        if let Some(bt) = self.backtrace() { write!(f, "\n{}", bt)?; }
+        Ok(())
+    }
+}
+
+
+
+/// Every live [Rc] on every thread, in creation order.
+pub fn live_references() -> Vec<LiveReference> {
+    let mut live = LIVE.lock().unwrap_or_else(|p| p.into_inner()).entries.values().flatten().cloned().collect::<Vec<_>>();
+    live.sort_by_key(|r| r.serial);
+    live
+}
+
+/// Run `f`, then panic if any [Rc]s created by `f` on the current thread are still live, listing where they were created.
+///
+/// References created on other threads are ignored, so this can be used by tests running in parallel.
+#[track_caller]
+pub fn assert_no_leaks(f: impl FnOnce()) {
+    let start = LIVE.lock().unwrap_or_else(|p| p.into_inner()).serial;
+    let thread = thread::current().id();
+    f();
+
+    let leaked = live_references().into_iter().filter(|r| r.serial >= start && r.thread == thread).collect::<Vec<_>>();
+    if leaked.is_empty() { return }
+
+    let mut objects = leaked.iter().map(|r| r.identity).collect::<Vec<_>>();
+    objects.sort_unstable();
+    objects.dedup();
+
+    let mut message = std::format!("{} COM reference(s) to {} object(s) outlived `assert_no_leaks` scope:", leaked.len(), objects.len());
+    for r in leaked.iter() { message += &std::format!("\n    {}", r); }
+    panic!("{}", message);
+}
+
+
+
+/// Record a new [Rc].  If `adopt`, the [Rc] was created from a raw pointer, and might be re-adopting an escaped reference.
+#[track_caller] pub(crate) fn created<I: crate::AsIUnknown>(rc: &Rc<I>, adopt: bool) {
+    let location = Location::caller();
+    let pointer = rc.as_ptr() as usize;
+
+    if adopt {
+        let mut live = LIVE.lock().unwrap_or_else(|p| p.into_inner());
+        if let Some(escaped) = live.entries.get_mut(&pointer).and_then(|refs| refs.iter_mut().rev().find(|r| r.escaped.is_some())) {
+            escaped.escaped = None;
+            return;
+        }
+    } // n.b. don't hold the lock while calling into the object
+
+    let identity = unsafe {
+        let unk = rc.as_iunknown();
+        let mut identity = null_mut();
+        if SUCCEEDED(unk.QueryInterface(&IUnknown::uuidof(), &mut identity)) && !identity.is_null() {
+            (*identity.cast::<IUnknown>()).Release();
+        }
+        identity as usize
+    };
+    let backtrace = Backtrace::capture();
+    let backtrace = if backtrace.status() == BacktraceStatus::Captured { Some(Arc::new(backtrace)) } else { None };
+
+    let mut live = LIVE.lock().unwrap_or_else(|p| p.into_inner());
+    let serial = live.serial;
+    live.serial += 1;
+    live.entries.entry(pointer).or_default().push(LiveReference { serial, pointer, identity, location, escaped: None, thread: thread::current().id(), backtrace });
+}
+
+/// Mark an [Rc] as having been converted into a raw pointer.
+#[track_caller] pub(crate) fn escaped(pointer: usize) {
+    let location = Location::caller();
+    let mut live = LIVE.lock().unwrap_or_else(|p| p.into_inner());
+    if let Some(r) = live.entries.get_mut(&pointer).and_then(|refs| refs.iter_mut().rev().find(|r| r.escaped.is_none())) {
+        r.escaped = Some(location);
+    }
+}
+
+/// Forget an [Rc] that's about to be released.
+pub(crate) fn dropped(pointer: usize) {
+    let mut live = LIVE.lock().unwrap_or_else(|p| p.into_inner());
+    let Some(refs) = live.entries.get_mut(&pointer) else { return }; // e.g. transmuted into existence
+    if let Some(i) = refs.iter().rposition(|r| r.escaped.is_none()) { refs.remove(i); }
+    if refs.is_empty() { live.entries.remove(&pointer); }
+}
+
+
+
+struct Live {
+    entries:    BTreeMap<usize, Vec<LiveReference>>,
+    serial:     u64,
+}
+
+static LIVE : Mutex<Live> = Mutex::new(Live { entries: BTreeMap::new(), serial: 0 });
@@ -0,0 +1,257 @@
+//! An in-process, pure-Rust emulation of the COM runtime's apartment bookkeeping.
+//!
+//! Enabled by the `emulator` feature, which routes [init](crate::init) through this module on non-Windows hosts, so code
+//! relying on apartment semantics - `RPC_E_CHANGED_MODE`, `S_FALSE` re-initialization, MTA lifetime - can be tested on
+//! any host.  On Windows, [init](crate::init) always uses the OS, and this module is only used when called explicitly.
+//!
+//! Only the per-thread / per-process bookkeeping is emulated:  no messages are pumped, no DLLs are loaded or unloaded.
+//!
+//! | State                             | Emulated behavior |
+//! | --------------------------------- | ----------------- |
+//! | Thread not in an apartment        | [co_initialize_ex] enters a new STA or the MTA and returns `S_OK`.
+//! | Thread in the requested apartment | [co_initialize_ex] returns `S_FALSE`, and must still be balanced by [co_uninitialize].
+//! | Thread in the other apartment     | [co_initialize_ex] returns `RPC_E_CHANGED_MODE`, and must not be balanced.
+//! | MTA exists                        | Threads not in an apartment are implicitly in the MTA, but may still enter an STA.
+//!
+//! The MTA exists while any thread explicitly initialized it, or any [co_increment_mta_usage] cookie is outstanding.
+//! Threads exiting while still in the MTA are removed from it.
+//!
+//! [global_interface_table] provides an emulated [IGlobalInterfaceTable], used by [Git](crate::Git) by default when
+//! this feature is enabled.
+
+use crate::{ComObject, Rc};
+use crate::abi::*;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Mutex, OnceLock};
+
+use core::cell::Cell;
+use core::num::NonZeroU64;
+use core::ptr::null_mut;
+use core::sync::atomic::{AtomicU64, Ordering::*};
+
+
+
+/// A COM apartment, as tracked by the emulator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Apartment {
+    /// A single-threaded apartment.  Each STA created by [co_initialize_ex] gets a unique ID.
+    Sta(NonZeroU64),
+
+    /// The process-wide multi-threaded apartment.
+    Mta,
+}
+
+/// The apartment the current thread belongs to - including implicitly belonging to the MTA - if any.
+pub fn current_apartment() -> Option<Apartment> {
+    THREAD.with(|t| t.apartment.get()).or_else(|| if mta_exists() { Some(Apartment::Mta) } else { None })
+}
+
+/// `true` if the current thread only implicitly belongs to the MTA, by way of the MTA existing.
+pub fn is_implicit_mta() -> bool {
+    THREAD.with(|t| t.apartment.get()).is_none() && mta_exists()
+}
+
+/// The number of outstanding successful [co_initialize_ex] calls (including `S_FALSE`) on the current thread.
+pub fn init_count() -> u32 { THREAD.with(|t| t.inits.get()) }
+
+/// The number of outstanding [co_increment_mta_usage] cookies.
+pub fn mta_usage() -> usize { MTA.lock().unwrap().cookies.len() }
+
+/// `true` if any thread explicitly belongs to the MTA, or any [co_increment_mta_usage] cookies are outstanding.
+pub fn mta_exists() -> bool { MTA.lock().unwrap().exists() }
+
+
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-coinitializeex)\]
+/// Emulated `CoInitializeEx`.
+///
+/// ### Returns
+///
+/// * `S_OK` - the thread entered a new STA or the MTA.
+/// * `S_FALSE` - the thread was already in the requested kind of apartment.
+/// * `RPC_E_CHANGED_MODE` - the thread was already in the other kind of apartment.
+/// * `E_INVALIDARG` - `reserved` was non-null, or `coinit` contained unknown flags.
+///
+/// ### Safety
+///
+/// * Same requirements as `CoInitializeEx`.  No pointers are dereferenced.
+pub unsafe fn co_initialize_ex(reserved: *mut c_void, coinit: COINIT) -> HRESULT {
+    const KNOWN : COINIT = COINIT_APARTMENTTHREADED | COINIT_MULTITHREADED | COINIT_DISABLE_OLE1DDE | COINIT_SPEED_OVER_MEMORY;
+    if !reserved.is_null() || (coinit & !KNOWN) != 0 { return E_INVALIDARG }
+    let sta = (coinit & COINIT_APARTMENTTHREADED) != 0;
+
+    THREAD.with(|t| match t.apartment.get() {
+        Some(Apartment::Sta(_)) if !sta => RPC_E_CHANGED_MODE,
+        Some(Apartment::Mta)    if  sta => RPC_E_CHANGED_MODE,
+        Some(_) => {
+            t.inits.set(t.inits.get() + 1);
+            S_FALSE
+        },
+        None => {
+            let apartment = if sta {
+                Apartment::Sta(NonZeroU64::new(NEXT_STA.fetch_add(1, Relaxed)).unwrap())
+            } else {
+                MTA.lock().unwrap().threads += 1;
+                Apartment::Mta
+            };
+            t.apartment.set(Some(apartment));
+            t.inits.set(1);
+            S_OK
+        },
+    })
+}
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-couninitialize)\]
+/// Emulated `CoUninitialize`.  The thread leaves it's apartment when the last [co_initialize_ex] call is balanced.
+/// Does nothing if the thread isn't initialized.
+///
+/// ### Safety
+///
+/// * Same requirements as `CoUninitialize`.
+pub unsafe fn co_uninitialize() {
+    THREAD.with(|t| match t.inits.get() {
+        0 => {},
+        1 => {
+            t.inits.set(0);
+            t.leave();
+        },
+        n => t.inits.set(n - 1),
+    })
+}
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-coincrementmtausage)\]
+/// Emulated `CoIncrementMTAUsage`.  Keeps the MTA alive until the returned cookie is passed to [co_decrement_mta_usage].
+///
+/// ### Safety
+///
+/// * `cookie` must be null (returning `E_POINTER`) or valid for writes.
+pub unsafe fn co_increment_mta_usage(cookie: *mut CO_MTA_USAGE_COOKIE) -> HRESULT {
+    if cookie.is_null() { return E_POINTER }
+    let mut mta = MTA.lock().unwrap();
+    let id = mta.next_cookie;
+    mta.next_cookie += 1;
+    mta.cookies.insert(id);
+    *cookie = id as CO_MTA_USAGE_COOKIE;
+    S_OK
+}
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-codecrementmtausage)\]
+/// Emulated `CoDecrementMTAUsage`.  Returns `E_INVALIDARG` for unknown or already decremented cookies.
+///
+/// ### Safety
+///
+/// * Same requirements as `CoDecrementMTAUsage`.  No pointers are dereferenced.
+pub unsafe fn co_decrement_mta_usage(cookie: CO_MTA_USAGE_COOKIE) -> HRESULT {
+    if MTA.lock().unwrap().cookies.remove(&(cookie as usize)) { S_OK } else { E_INVALIDARG }
+}
+
+
+
+struct Thread {
+    apartment:  Cell<Option<Apartment>>, // explicit membership only
+    inits:      Cell<u32>,
+}
+
+impl Thread {
+    fn leave(&self) {
+        if self.apartment.take() == Some(Apartment::Mta) {
+            if let Ok(mut mta) = MTA.lock() { mta.threads -= 1; }
+        }
+    }
+}
+
+impl Drop for Thread {
+    fn drop(&mut self) { self.leave() }
+}
+
+struct Mta {
+    threads:        usize,
+    cookies:        BTreeSet<usize>,
+    next_cookie:    usize,
+}
+
+impl Mta {
+    fn exists(&self) -> bool { self.threads > 0 || !self.cookies.is_empty() }
+}
+
+std::thread_local! {
+    static THREAD : Thread = const { Thread { apartment: Cell::new(None), inits: Cell::new(0) } };
+}
+
+static MTA : Mutex<Mta> = Mutex::new(Mta { threads: 0, cookies: BTreeSet::new(), next_cookie: 1 });
+static NEXT_STA : AtomicU64 = AtomicU64::new(1);
+
+
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iglobalinterfacetable)\]
+/// The process-wide emulated [IGlobalInterfaceTable].
+///
+/// *   Cookies are non-zero, and the [Apartment] each interface was registered from is recorded.
+/// *   Interfaces are returned as-is within the same apartment, or to any apartment if the object implements [IAgileObject].
+/// *   Otherwise, `GetInterfaceFromGlobal` fails with `RPC_E_WRONG_THREAD`, as no proxies are generated.
+/// *   Registering or retrieving interfaces from threads outside of any apartment fails with `CO_E_NOTINITIALIZED`.
+pub fn global_interface_table() -> Rc<IGlobalInterfaceTable> {
+    static GIT : OnceLock<ComObject<GlobalInterfaceTable>> = OnceLock::new();
+    GIT.get_or_init(|| ComObject::new(GlobalInterfaceTable(Mutex::new(GitState { entries: BTreeMap::new(), next_cookie: 1 })))).to_rc()
+}
+
+struct GlobalInterfaceTable(Mutex<GitState>);
+
+struct GitState {
+    entries:        BTreeMap<DWORD, GitEntry>,
+    next_cookie:    DWORD,
+}
+
+struct GitEntry {
+    unk:        Rc<IUnknown>, // the registered `riid` interface, as an IUnknown
+    apartment:  Apartment,
+    agile:      bool,
+}
+
+// SAFETY: entries are only used from their registered apartment, unless agile
+unsafe impl Send for GitEntry {}
+
+crate::implement!(GlobalInterfaceTable: IGlobalInterfaceTable, IAgileObject);
+
+impl IAgileObjectImpl for GlobalInterfaceTable {}
+
+impl IGlobalInterfaceTableImpl for GlobalInterfaceTable {
+    unsafe fn RegisterInterfaceInGlobal(&self, unk: *mut IUnknown, riid: REFIID, cookie: *mut DWORD) -> HRESULT {
+        if cookie.is_null() { return E_POINTER }
+        *cookie = 0;
+        let (Some(unk), Some(riid)) = (unk.as_ref(), riid.as_ref()) else { return E_INVALIDARG };
+        let Some(apartment) = current_apartment() else { return CO_E_NOTINITIALIZED };
+
+        let mut ptr = null_mut();
+        let hr = unk.QueryInterface(riid, &mut ptr);
+        if !SUCCEEDED(hr) { return hr }
+        let Some(unk) = Rc::<IUnknown>::from_raw_opt(ptr.cast()) else { return E_NOINTERFACE };
+        let agile = unk.try_cast::<IAgileObject>().is_some();
+
+        let mut git = self.0.lock().unwrap();
+        let mut id = git.next_cookie;
+        while git.entries.contains_key(&id) { id = id.checked_add(1).unwrap_or(1) } // "The value of an invalid cookie is 0."
+        git.next_cookie = id.checked_add(1).unwrap_or(1);
+        git.entries.insert(id, GitEntry { unk, apartment, agile });
+        *cookie = id;
+        S_OK
+    }
+
+    unsafe fn RevokeInterfaceFromGlobal(&self, cookie: DWORD) -> HRESULT {
+        let entry = self.0.lock().unwrap().entries.remove(&cookie); // n.b. released after unlocking
+        if entry.is_some() { S_OK } else { E_INVALIDARG }
+    }
+
+    unsafe fn GetInterfaceFromGlobal(&self, cookie: DWORD, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        if ppv.is_null() { return E_INVALIDARG }
+        *ppv = null_mut();
+        let Some(apartment) = current_apartment() else { return CO_E_NOTINITIALIZED };
+        let unk = match self.0.lock().unwrap().entries.get(&cookie) {
+            None                                                    => return E_INVALIDARG,
+            Some(e) if e.agile || e.apartment == apartment          => e.unk.clone(),
+            Some(_)                                                 => return RPC_E_WRONG_THREAD,
+        };
+        unk.QueryInterface(riid, ppv) // n.b. called after unlocking
+    }
+}
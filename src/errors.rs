@@ -1,6 +1,6 @@
-//! [MethodHResult]
+//! [MethodHResult] and [ProtocolViolation]
 
-use winapi::shared::winerror::{HRESULT, SUCCEEDED};
+use crate::abi::{HRESULT, SUCCEEDED, E_POINTER};
 
 use core::fmt::{self, Debug, Display, Formatter};
 
@@ -9,8 +9,9 @@ use core::fmt::{self, Debug, Display, Formatter};
 /// An error about some specific method returning an [HRESULT](https://www.hresult.info/)
 #[derive(Clone)]
 pub struct MethodHResult {
-    pub(crate) method:  &'static str,
-    pub(crate) hr:      HRESULT,
+    pub(crate) method:      &'static str,
+    pub(crate) hr:          HRESULT,
+    pub(crate) violation:   Option<ProtocolViolation>,
 }
 
 impl MethodHResult {
@@ -24,8 +25,20 @@ impl MethodHResult {
     }
 
     /// Returns the [HRESULT] of the error
+    ///
+    /// For [ProtocolViolation::SucceededWithoutInterface], this is the method's own success code.
+    /// Converting into an [HRESULT] with [From] maps that to `E_POINTER` instead, so `?` can't turn it into success.
     pub fn hresult(&self) -> HRESULT { self.hr }
 
+    /// Returns how the method violated COM's rules, if it did, instead of merely failing
+    pub fn protocol_violation(&self) -> Option<ProtocolViolation> { self.violation }
+
+    /// Returns `true` if the method violated COM's rules, instead of merely failing
+    pub fn is_protocol_violation(&self) -> bool { self.violation.is_some() }
+
+    /// Returns the method that failed, e.g. `"IUnknown::QueryInterface"`
+    pub fn method(&self) -> &'static str { self.method }
+
     /// Returns a link in the format of e.g. "<https://www.hresult.info/Search?q=0x80000005>"
     #[deprecated = "This function will be removed in 0.2.0"]
     // #[cfg(feature = "alloc")] // XXX: this method predates the feature, so this feature gate cannot be introduced without a breaking change
@@ -33,14 +46,44 @@ impl MethodHResult {
 }
 
 impl MethodHResult {
-    pub(crate) fn unchecked(method: &'static str, hr: HRESULT) -> Self { Self { method, hr } }
+    pub(crate) fn unchecked(method: &'static str, hr: HRESULT) -> Self { Self { method, hr, violation: None } }
+    pub(crate) fn violation(method: &'static str, hr: HRESULT, violation: ProtocolViolation) -> Self { Self { method, hr, violation: Some(violation) } }
     pub(crate) fn to_u32(&self) -> u32 { self.hr as _ }
+    fn failure(&self) -> HRESULT { if SUCCEEDED(self.hr) { E_POINTER } else { self.hr } }
 }
 
-impl Debug   for MethodHResult { fn fmt(&self, fmt: &mut Formatter) -> fmt::Result { write!(fmt, "MethodHResult({:?}, 0x{:08x})", self.method, self.to_u32()) } }
-impl Display for MethodHResult { fn fmt(&self, fmt: &mut Formatter) -> fmt::Result { write!(fmt, "{} failed with HRESULT == 0x{:08x}", self.method, self.to_u32()) } }
+impl Debug for MethodHResult {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self.violation {
+            None            => write!(fmt, "MethodHResult({:?}, 0x{:08x})", self.method, self.to_u32()),
+            Some(violation) => write!(fmt, "MethodHResult({:?}, 0x{:08x}, {:?})", self.method, self.to_u32(), violation),
+        }
+    }
+}
+
+impl Display for MethodHResult {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self.violation {
+            None                                                => write!(fmt, "{} failed with HRESULT == 0x{:08x}", self.method, self.to_u32()),
+            Some(ProtocolViolation::SucceededWithoutInterface)  => write!(fmt, "{} succeeded without an interface (HRESULT == 0x{:08x})", self.method, self.to_u32()),
+            Some(ProtocolViolation::FailedWithInterface)        => write!(fmt, "{} failed with an interface (HRESULT == 0x{:08x})", self.method, self.to_u32()),
+        }
+    }
+}
 
 impl core::error::Error for MethodHResult {}
 
-impl From<MethodHResult> for HRESULT { fn from(value: MethodHResult) -> Self { value.hresult() } }
-#[cfg(feature = "winresult-types-0-1")] impl From<MethodHResult> for winresult_types_0_1::HResult { fn from(value: MethodHResult) -> Self { winresult_types_0_1::HResult::from(value.hr) } }
+impl From<MethodHResult> for HRESULT { fn from(value: MethodHResult) -> Self { value.failure() } }
+#[cfg(all(windows, feature = "winresult-types-0-1"))] impl From<MethodHResult> for winresult_types_0_1::HResult { fn from(value: MethodHResult) -> Self { winresult_types_0_1::HResult::from(value.failure()) } }
+
+
+
+/// How a method violated COM's rules, as opposed to merely failing.  See [MethodHResult::protocol_violation].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProtocolViolation {
+    /// The method returned a success code, but no interface pointer.
+    SucceededWithoutInterface,
+
+    /// The method returned a failure code, but also a (non-null, possibly garbage) interface pointer.
+    FailedWithInterface,
+}
@@ -1,15 +1,20 @@
-use crate as mcom;
+//! [Git] and the [Backend]s providing it's [IGlobalInterfaceTable].
+//!
+//! | Backend       | Availability          | Description |
+//! | ------------- | --------------------- | ----------- |
+//! | [Os]          | Windows 2000+         | `CLSID_StdGlobalInterfaceTable`, created once per thread.
+//! | [Emulator]    | `feature = "emulator"`| [crate::emulator::global_interface_table], usable on any host.  The default where [Os] is unavailable.
+//!
+//! [IGlobalInterfaceTable]:        https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iglobalinterfacetable
+
 use crate::*;
+use crate::abi::{IGlobalInterfaceTable, Interface, SUCCEEDED};
 use crate::errors::MethodHResult;
 
-use winapi::Interface;
-use winapi::shared::winerror::SUCCEEDED;
-use winapi::um::cguid::CLSID_StdGlobalInterfaceTable;
-use winapi::um::objidlbase::IGlobalInterfaceTable;
-
 use alloc::sync::Arc;
 
 use core::convert::TryFrom;
+use core::fmt::{self, Debug, Formatter};
 use core::num::NonZeroU32;
 use core::marker::PhantomData;
 use core::ptr::null_mut;
@@ -24,7 +29,14 @@ pub struct Git<I: Interface + AsIUnknown>(Arc<Cookie<I>>);
 impl<I: Interface + AsIUnknown> Git<I> {
     /// Lazily marshal a COM interface for use in another thread.  May fail when converted back into an [Rc] if in another COM apartment.
     pub fn try_from_lazy(unk: impl AsRef<Rc<I>>) -> Result<Self, MethodHResult> {
-        Cookie::new(unk.as_ref()).map(|c| Self(Arc::new(c)))
+        Self::try_from_lazy_in(default_backend(), unk)
+    }
+
+    /// Lazily marshal a COM interface for use in another thread, using a specific [Backend]'s [IGlobalInterfaceTable].
+    ///
+    /// [IGlobalInterfaceTable]:        https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iglobalinterfacetable
+    pub fn try_from_lazy_in(backend: &'static dyn Backend, unk: impl AsRef<Rc<I>>) -> Result<Self, MethodHResult> {
+        Cookie::new(backend, unk.as_ref()).map(|c| Self(Arc::new(c)))
     }
 }
 
@@ -62,13 +74,21 @@ impl<I: Interface + AsIUnknown> TryFrom<&Git<I>> for Rc<I> {
     fn try_from(src: &Git<I>) -> Result<Self, Self::Error> { src.0.get() }
 }
 
+/// `Git<IFoo>(cookie: 1)`:  the [name](crate::names) of the interface, and the [IGlobalInterfaceTable] cookie.
+///
+/// [IGlobalInterfaceTable]:        https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iglobalinterfacetable
+impl<I: Interface + AsIUnknown> Debug for Git<I> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { write!(f, "Git<{}>(cookie: {})", crate::names::Name(&I::uuidof()), self.0.cookie) }
+}
+
 impl<I: Interface + AsIUnknown> AsRef<Git<I>> for Git<I> {
     fn as_ref(&self) -> &Self { self }
 }
 
 
 
-#[repr(transparent)] struct Cookie<I: Interface + AsIUnknown> {
+struct Cookie<I: Interface + AsIUnknown> {
+    backend:    &'static dyn Backend,
     /// "The value of an invalid cookie is 0."
     /// https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-iglobalinterfacetable-registerinterfaceinglobal
     cookie:     NonZeroU32,
@@ -76,20 +96,20 @@ impl<I: Interface + AsIUnknown> AsRef<Git<I>> for Git<I> {
 }
 
 impl<I: Interface + AsIUnknown> Cookie<I> {
-    fn new(rc: &Rc<I>) -> Result<Self, MethodHResult> {
+    fn new(backend: &'static dyn Backend, rc: &Rc<I>) -> Result<Self, MethodHResult> {
         let unk = rc.as_iunknown_ptr();
         let iid = I::uuidof();
         let mut cookie = 0;
-        let hr = with_git(|git| unsafe { git.RegisterInterfaceInGlobal(unk, &iid, &mut cookie) });
+        let hr = unsafe { backend.global_interface_table().RegisterInterfaceInGlobal(unk, &iid, &mut cookie) };
         MethodHResult::check("IGlobalInterfaceTable::RegisterInterfaceInGlobal", hr)?;
-        NonZeroU32::new(cookie).ok_or(MethodHResult::unchecked("IGlobalInterfaceTable::RegisterInterfaceInGlobal", hr)).map(|cookie| Self { cookie, phantom: PhantomData })
+        NonZeroU32::new(cookie).ok_or(MethodHResult::unchecked("IGlobalInterfaceTable::RegisterInterfaceInGlobal", hr)).map(|cookie| Self { backend, cookie, phantom: PhantomData })
     }
 
     fn get(&self) -> Result<Rc<I>, MethodHResult> {
         let cookie : u32 = self.cookie.into();
         let iid = I::uuidof();
         let mut int = null_mut();
-        let hr = with_git(|git| unsafe { git.GetInterfaceFromGlobal(cookie, &iid, &mut int) });
+        let hr = unsafe { self.backend.global_interface_table().GetInterfaceFromGlobal(cookie, &iid, &mut int) };
         MethodHResult::check("IGlobalInterfaceTable::GetInterfaceFromGlobal", hr)?;
         unsafe { Rc::from_raw_opt(int.cast()) }.ok_or(MethodHResult::unchecked("IGlobalInterfaceTable::GetInterfaceFromGlobal", hr))
     }
@@ -98,21 +118,63 @@ impl<I: Interface + AsIUnknown> Cookie<I> {
 impl<I: Interface + AsIUnknown> Drop for Cookie<I> {
     fn drop(&mut self) {
         let cookie : u32 = self.cookie.into();
-        let hr = with_git(|git| unsafe { git.RevokeInterfaceFromGlobal(cookie) });
+        let hr = unsafe { self.backend.global_interface_table().RevokeInterfaceFromGlobal(cookie) };
         assert!(SUCCEEDED(hr), "IGlobalInterfaceTable::RevokeInterfaceFromGlobal failed with HRESULT == 0x{:08x}", hr);
     }
 }
 
 
 
-#[cfg(feature = "std")] fn with_git<R>(f: impl FnOnce(&IGlobalInterfaceTable) -> R) -> R {
-    std::thread_local! { static GIT : mcom::Rc<IGlobalInterfaceTable> = create_thread_git(); }
+/// A source of [IGlobalInterfaceTable]s for [Git] to register interfaces with.
+///
+/// [IGlobalInterfaceTable]:        https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iglobalinterfacetable
+pub trait Backend : Send + Sync + 'static {
+    /// The [IGlobalInterfaceTable] to use on the current thread.
+    /// Must refer to the same table on every thread, or cookies registered on one thread won't resolve on another.
+    ///
+    /// [IGlobalInterfaceTable]:        https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iglobalinterfacetable
+    fn global_interface_table(&self) -> Rc<IGlobalInterfaceTable>;
+}
+
+/// The [Backend] used by [Git::try_from_lazy] and [TryFrom]:  [Os] where available, otherwise [Emulator].
+///
+/// Enabling `feature = "emulator"` doesn't change this on Windows - use [Emulator] explicitly instead.
+pub fn default_backend() -> &'static dyn Backend {
+    #[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))] { &Os }
+    #[cfg(not(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games"))))] { &Emulator }
+}
+
+/// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/objidl/nn-objidl-iglobalinterfacetable)\]
+/// The OS provided `CLSID_StdGlobalInterfaceTable`, created once per thread.
+#[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))]
+#[derive(Clone, Copy, Debug, Default)] pub struct Os;
+
+#[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))]
+impl Backend for Os {
+    fn global_interface_table(&self) -> Rc<IGlobalInterfaceTable> { with_git(|git| git.clone()) }
+}
+
+/// The pure-Rust [crate::emulator::global_interface_table], which tracks [apartments](crate::emulator::Apartment) instead of marshaling.
+#[cfg(feature = "emulator")]
+#[derive(Clone, Copy, Debug, Default)] pub struct Emulator;
+
+#[cfg(feature = "emulator")]
+impl Backend for Emulator {
+    fn global_interface_table(&self) -> Rc<IGlobalInterfaceTable> { crate::emulator::global_interface_table() }
+}
+
+
+
+#[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))]
+#[cfg(feature = "std")] fn with_git<R>(f: impl FnOnce(&Rc<IGlobalInterfaceTable>) -> R) -> R {
+    std::thread_local! { static GIT : Rc<IGlobalInterfaceTable> = create_thread_git(); }
     GIT.with(|git| f(git))
 }
 
 // XXX: `Git` was introduced before `feature = "std"`.  Gating behind the feature would be a breaking change.
 // As such, roll our own poorly tested thread local storage... but only if we don't have `feature = "std"`.
-#[cfg(not(feature = "std"))] fn with_git<R>(f: impl FnOnce(&IGlobalInterfaceTable) -> R) -> R {
+#[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))]
+#[cfg(not(feature = "std"))] fn with_git<R>(f: impl FnOnce(&Rc<IGlobalInterfaceTable>) -> R) -> R {
     use core::sync::atomic::{AtomicU32, Ordering::*};
     use winapi::um::processthreadsapi::*;
 
@@ -148,9 +210,11 @@ impl<I: Interface + AsIUnknown> Drop for Cookie<I> {
         }
     };
 
-    f(unsafe { &*git })
+    f(unsafe { Rc::borrow_ptr(&git) })
 }
 
-fn create_thread_git() -> mcom::Rc<IGlobalInterfaceTable> {
-    unsafe { mcom::Rc::co_create(CLSID_StdGlobalInterfaceTable, None) }.unwrap()
+#[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))]
+fn create_thread_git() -> Rc<IGlobalInterfaceTable> {
+    use winapi::um::cguid::CLSID_StdGlobalInterfaceTable;
+    unsafe { Rc::co_create(CLSID_StdGlobalInterfaceTable, None) }.unwrap()
 }
@@ -0,0 +1,82 @@
+use crate::{AsIUnknown, Rc};
+use crate::abi::*;
+
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ptr::null_mut;
+
+
+
+/// The [IUnknown] identity of a COM object:  a strong reference to it's canonical [IUnknown] interface.
+///
+/// COM only guarantees that querying any of an object's interfaces for [IUnknown] returns the same pointer - different
+/// interfaces of the same object (or even the same interface, for tear-offs) may have different pointers.  As such,
+/// this is the only sound way to tell if two interface pointers refer to the same object.
+///
+/// Since [Identity] holds a strong reference, it can be cached or used as a `HashMap` / `BTreeMap` key without the
+/// object's address being reused by another object.  [Eq], [Ord], and [Hash] compare the canonical pointers.
+///
+/// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+#[derive(Clone)]
+pub struct Identity(Rc<IUnknown>);
+
+impl Identity {
+    /// Query `rc` for it's canonical [IUnknown].
+    ///
+    /// Objects that (incorrectly) fail to `QueryInterface` themselves for [IUnknown] are identified by `rc`'s own pointer.
+    /// Like [Rc::cast], anything a failing `QueryInterface` writes is leaked rather than released, as it may be garbage.
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    pub fn of<I: AsIUnknown>(rc: &Rc<I>) -> Self {
+        let unk = rc.as_iunknown();
+        let mut ptr = null_mut();
+        let hr = unsafe { unk.QueryInterface(&IUnknown::uuidof(), &mut ptr) };
+        match if SUCCEEDED(hr) { unsafe { Rc::from_raw_opt(ptr.cast()) } } else { None } {
+            Some(canonical) => Self(canonical),
+            None            => Self(unsafe { Rc::borrow_ref(&unk) }.clone()),
+        }
+    }
+
+    /// The canonical [IUnknown] pointer.
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    pub fn as_ptr(&self) -> *mut IUnknown { self.0.as_ptr() }
+
+    /// The canonical [IUnknown] interface.
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    pub fn as_unknown(&self) -> &Rc<IUnknown> { &self.0 }
+
+    /// Convert into the canonical [IUnknown] interface.
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    pub fn into_unknown(self) -> Rc<IUnknown> { self.0 }
+}
+
+impl<I: AsIUnknown> From<&Rc<I>> for Identity {
+    fn from(rc: &Rc<I>) -> Self { Self::of(rc) }
+}
+
+impl Debug for Identity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { write!(f, "Identity({:?})", self.as_ptr()) }
+}
+
+impl PartialEq for Identity { fn eq(&self, other: &Self) -> bool { self.as_ptr() == other.as_ptr() } }
+impl Eq for Identity {}
+impl PartialOrd for Identity { fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) } }
+impl Ord for Identity { fn cmp(&self, other: &Self) -> Ordering { self.as_ptr().cmp(&other.as_ptr()) } }
+impl Hash for Identity { fn hash<H: Hasher>(&self, state: &mut H) { self.as_ptr().hash(state) } }
+
+
+
+/// The canonical [IUnknown] pointer of `unk`'s object, without keeping a reference to it.
+/// Only meaningful while the caller keeps the object alive.  Falls back on `unk` like [Identity::of].
+pub(crate) fn canonical(unk: &IUnknown) -> *mut IUnknown {
+    let mut ptr = null_mut();
+    let hr = unsafe { unk.QueryInterface(&IUnknown::uuidof(), &mut ptr) };
+    let ptr : *mut IUnknown = ptr.cast();
+    if !SUCCEEDED(hr) || ptr.is_null() { return unk as *const IUnknown as *mut IUnknown }
+    unsafe { (*ptr).Release() };
+    ptr
+}
@@ -1,13 +1,20 @@
 //! Wrappers around [CoInitializeEx] etc. for initializing COM.
 //!
+//! On non-Windows hosts, these route through [crate::emulator] instead (requires the `emulator` feature.)
+//!
 //! [CoInitializeEx]:   https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-coinitializeex
 
+use crate::abi::{c_void, S_OK, S_FALSE, CO_MTA_USAGE_COOKIE};
+use crate::abi::{COINIT, COINIT_APARTMENTTHREADED, COINIT_MULTITHREADED, COINIT_DISABLE_OLE1DDE, COINIT_SPEED_OVER_MEMORY};
 use crate::errors::MethodHResult;
 
-use winapi::ctypes::c_void;
-use winapi::shared::winerror::{S_OK, S_FALSE};
-use winapi::um::combaseapi::{CoInitializeEx, CoUninitialize, CoIncrementMTAUsage, CoDecrementMTAUsage, CO_MTA_USAGE_COOKIE};
-use winapi::um::objbase::{COINIT, COINIT_APARTMENTTHREADED, COINIT_MULTITHREADED, COINIT_DISABLE_OLE1DDE, COINIT_SPEED_OVER_MEMORY};
+#[cfg(windows)] use winapi::um::combaseapi::{CoInitializeEx, CoUninitialize, CoIncrementMTAUsage, CoDecrementMTAUsage};
+#[cfg(not(windows))] use crate::emulator::{
+    co_initialize_ex        as CoInitializeEx,
+    co_uninitialize         as CoUninitialize,
+    co_increment_mta_usage  as CoIncrementMTAUsage,
+    co_decrement_mta_usage  as CoDecrementMTAUsage,
+};
 
 use core::fmt::{self, Debug, Formatter};
 use core::marker::PhantomData;
@@ -1,8 +1,154 @@
-use winapi::um::unknwnbase::IUnknown;
-use winapi::Interface;
+use crate::abi::{IID, IUnknown, Interface};
 
 
 
+/// A "COM" interface:  a `#[repr(C)]` pointer to a vtable, which may or may not derive from [IUnknown].
+///
+/// Interfaces that don't derive from [IUnknown] - such as [ID3D12FunctionReflection] - aren't reference counted, and
+/// can't be held in an [Rc](crate::Rc).  They're owned by some other COM object instead, and can be borrowed for that
+/// object's lifetime as a [Child](crate::Child).
+///
+/// Implemented by [interface!](macro@crate::interface) (including for interfaces declared without a parent), by
+/// [com_interface!](crate::com_interface) alongside [ComInterface], and for winapi's interfaces behind `winapi-*` features.
+///
+/// ### Safety
+///
+/// *   `Self` must be a `#[repr(C)]` struct containing only a pointer to a vtable (and zero-sized fields.)
+///
+/// [IUnknown]:                     https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+/// [ID3D12FunctionReflection]:     https://learn.microsoft.com/en-us/windows/win32/api/d3d12shader/nn-d3d12shader-id3d12functionreflection
+pub unsafe trait RawInterface : Sized {}
+
+/// A private field of [interface!](macro@crate::interface) structs, keeping safe code from constructing them with
+/// arbitrary vtable pointers - which the generated safe method wrappers would then dereference.
+#[doc(hidden)] #[derive(Clone, Copy)] pub struct Unconstructible(());
+
+impl Unconstructible {
+    /// ### Safety
+    ///
+    /// * The interface struct this is a field of must point to a valid vtable, for as long as it's used as an interface.
+    #[doc(hidden)] pub const unsafe fn new() -> Self { Self(()) }
+}
+
+
+
+/// A COM interface deriving from [IUnknown], as required by mcom's smart pointers.
+///
+/// Rather than implementing this trait directly, declare interfaces with [interface!](macro@crate::interface), or vouch for
+/// interfaces declared elsewhere (e.g. with winapi's `RIDL!`) with [com_interface!](crate::com_interface).  mcom implements it for winapi's
+/// [IUnknown]-derived interfaces, one `winapi-*` feature per winapi module - see [Crate Features](crate::Documentation::features).
+///
+/// Interfaces that don't derive from [IUnknown] - such as [ID3D12FunctionReflection] - don't implement this trait, and
+/// can't be held in an [Rc](crate::Rc) or friends (see [RawInterface] and [Child](crate::Child) instead.)
+/// Neither can types that merely implement [Interface]:
+///
+/// ```compile_fail
+/// struct NotAnInterface(u32);
+/// impl mcom::abi::Interface for NotAnInterface {
+///     fn uuidof() -> mcom::abi::GUID { <mcom::abi::IUnknown as mcom::abi::Interface>::uuidof() }
+/// }
+/// fn unsound(rc: &mcom::Rc<mcom::abi::IUnknown>) -> Option<mcom::Rc<NotAnInterface>> { rc.try_cast() }
+/// ```
+///
+/// Implementing this trait directly also requires implementing a `#[doc(hidden)]` supertrait, which those macros
+/// implement alongside it.  That supertrait isn't truly sealed - it can't be, as the macros expand in other crates - but
+/// implementing it by hand is unsupported, and not covered by semver.  The `unsafe` on this trait is what actually
+/// guards soundness:
+///
+/// ```compile_fail
+/// # use mcom::abi::*;
+/// #[repr(C)] struct IFoo { lpVtbl: *const IUnknownVtbl }
+/// impl Interface for IFoo { fn uuidof() -> GUID { IUnknown::uuidof() } }
+/// unsafe impl mcom::ComInterface for IFoo { type Parent = IUnknown; }
+/// ```
+///
+/// ### Safety
+///
+/// *   `Self` must be a [RawInterface]:  a `#[repr(C)]` struct containing only a pointer to a vtable (and zero-sized fields.)
+/// *   [Interface::uuidof] must return the IID of `Self`.
+/// *   `Self`'s vtable must begin with [Parent](Self::Parent)'s vtable.
+/// *   [Parent](Self::Parent) must itself lead back to [IUnknown], which is it's own [Parent](Self::Parent).
+///
+/// [IUnknown]:                     https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+/// [ID3D12FunctionReflection]:     https://learn.microsoft.com/en-us/windows/win32/api/d3d12shader/nn-d3d12shader-id3d12functionreflection
+pub unsafe trait ComInterface : Interface + RawInterface + sealed::Sealed {
+    /// The interface `Self` directly derives from.
+    type Parent : ComInterface;
+}
+
+/// `Self` derives from `A`, directly or indirectly:  an <code>[Rc](crate::Rc)&lt;Self&gt;</code> is also a valid
+/// <code>[Rc](crate::Rc)&lt;A&gt;</code>, and can be [upcast](crate::Rc::upcast) without calling [QueryInterface].
+///
+/// Implemented automatically for every [ComInterface] and each of it's strict ancestors (so never for `A = Self`),
+/// based on the chain of [Parent](ComInterface::Parent)s.
+///
+/// ```
+/// # use mcom::abi::*;
+/// # use mcom::Inherits;
+/// mcom::interface! {
+///     #[uuid(0x2f4e6a81, 0x5b3c, 0x4d7e, 0x8a, 0x1f, 0x6c, 0x0d, 0x9e, 0x2b, 0x47, 0x13)]
+///     pub interface IFoo(IFooVtbl): IUnknown(IUnknownVtbl) {}
+///
+///     #[uuid(0x2f4e6a82, 0x5b3c, 0x4d7e, 0x8a, 0x1f, 0x6c, 0x0d, 0x9e, 0x2b, 0x47, 0x13)]
+///     pub interface IFoo2(IFoo2Vtbl): IFoo(IFooVtbl) {}
+/// }
+///
+/// fn inherits<D: Inherits<B>, B: mcom::ComInterface>() {}
+/// inherits::<IFoo2, IFoo>();
+/// inherits::<IFoo2, IUnknown>();
+/// inherits::<IFoo, IUnknown>();
+/// ```
+///
+/// ```compile_fail
+/// # use mcom::abi::*;
+/// # fn inherits<D: mcom::Inherits<B>, B: mcom::ComInterface>() {}
+/// inherits::<IUnknown, IUnknown>();
+/// ```
+///
+/// ### Safety
+///
+/// *   `Self`'s vtable must begin with `A`'s vtable.  Guaranteed by [ComInterface]'s safety requirements.
+///
+/// [QueryInterface]:   https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void)
+pub unsafe trait Inherits<A: ComInterface> : ComInterface {}
+
+unsafe impl<I: ComInterface, A: ComInterface> Inherits<A> for I where
+    I::Depth                                                : sealed::Sub<A::Depth>,
+    <I::Depth as sealed::Sub<A::Depth>>::Output             : sealed::Pred,
+    I : sealed::Ancestor<<<I::Depth as sealed::Sub<A::Depth>>::Output as sealed::Pred>::Output, Type = A>,
+{}
+
+#[doc(hidden)] pub mod sealed {
+    use core::marker::PhantomData;
+
+    /// Implemented by [interface!](macro@crate::interface) and [com_interface!](crate::com_interface) alongside [ComInterface](crate::ComInterface).
+    /// Public only so those macros can implement it:  implementing it by hand is unsupported, and not covered by semver.
+    pub trait Sealed {
+        /// The number of [Parent](crate::ComInterface::Parent)s between `Self` and `IUnknown`, counted with [Z] and [S].
+        type Depth;
+    }
+
+    /// Zero
+    pub struct Z;
+
+    /// `N + 1`
+    pub struct S<N>(PhantomData<N>);
+
+    /// `Self - N`, for `Self >= N`
+    pub trait Sub<N> { type Output; }
+    impl<N> Sub<Z> for N { type Output = N; }
+    impl<N: Sub<M>, M> Sub<S<M>> for S<N> { type Output = N::Output; }
+
+    /// `Self - 1`, for `Self >= 1`
+    pub trait Pred { type Output; }
+    impl<N> Pred for S<N> { type Output = N; }
+
+    /// The ancestor `N + 1` [Parent](crate::ComInterface::Parent)s up from `Self`.
+    pub trait Ancestor<N> { type Type; }
+    impl<I: crate::ComInterface> Ancestor<Z> for I { type Type = I::Parent; }
+    impl<I: crate::ComInterface, N> Ancestor<S<N>> for I where I::Parent : Ancestor<N> { type Type = <I::Parent as Ancestor<N>>::Type; }
+}
+
 #[doc(hidden)]
 // XXX: Ehh... this type may not remain exposed by mcom / might be subsumed into winapi.
 // See https://github.com/retep998/winapi-rs/pull/961 for some of the details.
@@ -12,10 +158,376 @@ pub unsafe trait AsIUnknown {
     fn as_iunknown_ptr(&self) -> *mut IUnknown {
         self.as_iunknown() as *const IUnknown as *mut IUnknown
     }
+
+    /// The IID of `Self`, if known (used by the `validate` feature.)
+    fn iid() -> Option<IID> where Self : Sized { None }
 }
 
-unsafe impl<I: Interface> AsIUnknown for I {
+unsafe impl<I: ComInterface> AsIUnknown for I {
     fn as_iunknown(&self) -> &IUnknown {
         unsafe { &*(self as *const Self as *const IUnknown) }
     }
+
+    fn iid() -> Option<IID> { Some(I::uuidof()) }
+}
+
+
+
+/// Implement [ComInterface] (and [RawInterface]) for interfaces declared outside of [interface!](macro@crate::interface), such as with winapi's `RIDL!`.
+///
+/// ```
+/// # use mcom::abi::*;
+/// # #[repr(C)] pub struct IFooVtbl { pub parent: IUnknownVtbl }
+/// #[repr(C)] pub struct IFoo { pub lpVtbl: *const IFooVtbl }
+/// impl Interface for IFoo {
+///     fn uuidof() -> GUID { GUID { Data1: 0x8d0e7a42, Data2: 0x1c3f, Data3: 0x4b25, Data4: [0x9e, 0x60, 0x2a, 0x7b, 0xd4, 0x11, 0x58, 0xc3] } }
+/// }
+///
+/// mcom::com_interface! {
+///     // SAFETY: IFoo is a #[repr(C)] vtable pointer, and IFooVtbl begins with IUnknownVtbl
+///     unsafe impl ComInterface for IFoo { type Parent = IUnknown; }
+/// }
+///
+/// fn takes_rc(_: mcom::Rc<IFoo>) {}
+/// ```
+///
+/// See [ComInterface]'s safety section for what `unsafe` is vouching for.
+#[macro_export]
+macro_rules! com_interface {
+    ( @root unsafe impl ComInterface for $interface:ty { type Parent = Self; } ) => {
+        impl $crate::sealed::Sealed for $interface { type Depth = $crate::sealed::Z; }
+        unsafe impl $crate::RawInterface for $interface {}
+        unsafe impl $crate::ComInterface for $interface { type Parent = $interface; }
+    };
+
+    ( $( unsafe impl ComInterface for $interface:ty { type Parent = $parent:ty; } )* ) => {$(
+        impl $crate::sealed::Sealed for $interface { type Depth = $crate::sealed::S<<$parent as $crate::sealed::Sealed>::Depth>; }
+        unsafe impl $crate::RawInterface for $interface {}
+        unsafe impl $crate::ComInterface for $interface { type Parent = $parent; }
+    )*};
+}
+
+
+
+/// Declare a COM interface (and its vtable) in Rust, similar to winapi's `RIDL!`.
+///
+/// ```
+/// use mcom::abi::{HRESULT, IUnknown, IUnknownVtbl};
+///
+/// mcom::interface! {
+///     /// A simple counter.
+///     #[uuid(0x3c8e2a3b, 0x3f6b, 0x4a1e, 0x9a, 0x5e, 0x0d, 0x7b, 0x34, 0x52, 0x21, 0x01)]
+///     pub interface ICounter(ICounterVtbl): IUnknown(IUnknownVtbl) {
+///         /// `fn` without a return type:  the vtable entry returns [HRESULT], the wrapper returns `Result<(), MethodHResult>`.
+///         fn Add(amount: u32);
+///
+///         /// `fn` with a return type:  the vtable entry takes a trailing `*mut u32` out parameter and returns [HRESULT],
+///         /// the wrapper returns `Result<u32, MethodHResult>`.
+///         fn Get() -> u32;
+///
+///         /// `unsafe fn`:  the vtable entry and the (unsafe) wrapper have exactly the declared signature.
+///         unsafe fn GetRaw(value: *mut u32) -> HRESULT;
+///     }
+/// }
+/// ```
+///
+/// This generates:
+/// *   A `#[repr(C)] pub struct ICounterVtbl { pub parent: IUnknownVtbl, pub Add: ..., ... }`
+/// *   A `#[repr(C)] pub struct ICounter { pub lpVtbl: *const ICounterVtbl, /* private */ }`, and an
+///     `unsafe fn ICounter::from_vtbl(lpVtbl)` for embedding `ICounter` in hand-rolled COM objects
+/// *   <code>impl [Interface](crate::abi::Interface) for ICounter</code>
+/// *   <code>unsafe impl [ComInterface] for ICounter { type Parent = IUnknown; }</code>, so `ICounter` can be held in an [Rc](crate::Rc) and [up](crate::Rc::up)cast
+///     (which also makes `ICounter` <code>[Inherits]&lt;IUnknown&gt;</code>, as well as every other ancestor, for [upcast](crate::Rc::upcast)ing in one step)
+/// *   <code>impl [Deref](core::ops::Deref)&lt;Target = IUnknown&gt; for ICounter</code>, so `IUnknown`'s methods can be called on `ICounter`
+/// *   Method wrappers on `ICounter`
+///
+/// Naming an `Impl` trait, as in `interface ICounter(ICounterVtbl, ICounterImpl)`, additionally generates:
+/// *   A `pub trait ICounterImpl` with a method for each interface method, for Rust types to implement
+///     (`fn Add(&self, amount: u32) -> Result<(), HRESULT>`, `fn Get(&self) -> Result<u32, HRESULT>`, `unsafe fn GetRaw(&self, value: *mut u32) -> HRESULT`)
+/// *   The glue allowing [implement!](crate::implement) to expose `ICounter` from a [ComObject](crate::ComObject).
+///     The parent interface must also have an `Impl` trait (or be [IUnknown](crate::abi::IUnknown).)
+///
+/// Omitting the parent, as in `interface IVariable(IVariableVtbl) { ... }`, declares a vtable-only interface that doesn't
+/// derive from [IUnknown](crate::abi::IUnknown), like [ID3D12FunctionReflection].  The vtable has no `parent` field,
+/// and <code>unsafe impl [RawInterface] for IVariable</code> is generated instead of [ComInterface] and [Deref](core::ops::Deref).
+/// Such interfaces can't name an `Impl` trait, and are typically borrowed from their owner as a [Child](crate::Child).
+///
+/// Doc comments must come before `#[uuid(...)]`, and any other attributes after it.
+///
+/// The generated structs can't be constructed by safe code, as the safe method wrappers trust `lpVtbl`:
+///
+/// ```compile_fail
+/// mod ifaces {
+///     # use mcom::abi::{IUnknown, IUnknownVtbl};
+///     mcom::interface! {
+///         #[uuid(0x3c8e2a3b, 0x3f6b, 0x4a1e, 0x9a, 0x5e, 0x0d, 0x7b, 0x34, 0x52, 0x21, 0x01)]
+///         pub interface ICounter(ICounterVtbl): IUnknown(IUnknownVtbl) { fn Add(amount: u32); }
+///     }
+/// }
+/// let _ = ifaces::ICounter { lpVtbl: core::ptr::null() }.Add(1);
+/// ```
+///
+/// Methods declared without `unsafe` get safe wrappers, so only do that when any value of the parameters is sound to pass.
+/// Return types of such methods must implement [Default], which is passed as the initial value of the out parameter.
+/// <code>[Option]&lt;[Rc](crate::Rc)&lt;I&gt;&gt;</code> is a good choice for interface out parameters.
+/// <code>[Option]&lt;[Ref](crate::Ref)&lt;'_, I&gt;&gt;</code> is a good choice for interface (in) parameters.
+///
+/// [HRESULT]:                      crate::abi::HRESULT
+/// [ID3D12FunctionReflection]:     https://learn.microsoft.com/en-us/windows/win32/api/d3d12shader/nn-d3d12shader-id3d12functionreflection
+#[macro_export]
+macro_rules! interface {
+    () => {};
+
+    (
+        $(#[doc = $doc:expr])*
+        #[uuid($l:expr, $w1:expr, $w2:expr, $b1:expr, $b2:expr, $b3:expr, $b4:expr, $b5:expr, $b6:expr, $b7:expr, $b8:expr)]
+        $(#[$attr:meta])*
+        $vis:vis interface $interface:ident ($vtbl:ident $(, $impl:ident)?) : $parent:ident ($pvtbl:ident) {
+            $($methods:tt)*
+        }
+        $($rest:tt)*
+    ) => {
+        $crate::interface! {
+            @munch [$(#[doc = $doc])* $(#[$attr])*] [$vis] [$interface $vtbl [$($impl)?] [$parent $pvtbl]] [$l, $w1, $w2, [$b1, $b2, $b3, $b4, $b5, $b6, $b7, $b8]]
+            [] [] []
+            $($methods)*
+        }
+        $crate::interface! { $($rest)* }
+    };
+
+    (
+        $(#[doc = $doc:expr])*
+        #[uuid($l:expr, $w1:expr, $w2:expr, $b1:expr, $b2:expr, $b3:expr, $b4:expr, $b5:expr, $b6:expr, $b7:expr, $b8:expr)]
+        $(#[$attr:meta])*
+        $vis:vis interface $interface:ident ($vtbl:ident) {
+            $($methods:tt)*
+        }
+        $($rest:tt)*
+    ) => {
+        $crate::interface! {
+            @munch [$(#[doc = $doc])* $(#[$attr])*] [$vis] [$interface $vtbl [] []] [$l, $w1, $w2, [$b1, $b2, $b3, $b4, $b5, $b6, $b7, $b8]]
+            [] [] []
+            $($methods)*
+        }
+        $crate::interface! { $($rest)* }
+    };
+
+    // Parse methods one at a time, accumulating [vtable fields] [wrapper methods] [normalized methods]
+
+    (
+        @munch $attrs:tt $vis:tt $names:tt $uuid:tt $fields:tt $wrappers:tt $methods:tt
+        $(#[$mattr:meta])* unsafe fn $method:ident ($($p:ident : $t:ty),* $(,)?);
+        $($rest:tt)*
+    ) => {
+        $crate::interface! {
+            @munch $attrs $vis $names $uuid $fields $wrappers $methods
+            $(#[$mattr])* unsafe fn $method ($($p: $t),*) -> ();
+            $($rest)*
+        }
+    };
+
+    (
+        @munch $attrs:tt $vis:tt [$interface:ident $($names:tt)*] $uuid:tt
+        [$($fields:tt)*] [$($wrappers:tt)*] [$($methods:tt)*]
+        $(#[$mattr:meta])* unsafe fn $method:ident ($($p:ident : $t:ty),* $(,)?) -> $ret:ty;
+        $($rest:tt)*
+    ) => {
+        $crate::interface! {
+            @munch $attrs $vis [$interface $($names)*] $uuid
+            [$($fields)*
+                $(#[$mattr])* pub $method: unsafe extern "system" fn (This: *mut $interface, $($p: $t),*) -> $ret,
+            ]
+            [$($wrappers)*
+                $(#[$mattr])* #[inline] pub unsafe fn $method(&self, $($p: $t),*) -> $ret {
+                    ((*self.lpVtbl).$method)(self as *const Self as *mut Self, $($p),*)
+                }
+            ]
+            [$($methods)* { raw [$(#[$mattr])*] $method ($($p: $t),*) ($ret) }]
+            $($rest)*
+        }
+    };
+
+    (
+        @munch $attrs:tt $vis:tt [$interface:ident $($names:tt)*] $uuid:tt
+        [$($fields:tt)*] [$($wrappers:tt)*] [$($methods:tt)*]
+        $(#[$mattr:meta])* fn $method:ident ($($p:ident : $t:ty),* $(,)?);
+        $($rest:tt)*
+    ) => {
+        $crate::interface! {
+            @munch $attrs $vis [$interface $($names)*] $uuid
+            [$($fields)*
+                $(#[$mattr])* pub $method: unsafe extern "system" fn (This: *mut $interface, $($p: $t),*) -> $crate::abi::HRESULT,
+            ]
+            [$($wrappers)*
+                $(#[$mattr])* #[inline] pub fn $method(&self, $($p: $t),*) -> ::core::result::Result<(), $crate::errors::MethodHResult> {
+                    let hr = unsafe { ((*self.lpVtbl).$method)(self as *const Self as *mut Self, $($p),*) };
+                    $crate::errors::MethodHResult::check(::core::concat!(::core::stringify!($interface), "::", ::core::stringify!($method)), hr)
+                }
+            ]
+            [$($methods)* { hr [$(#[$mattr])*] $method ($($p: $t),*) (()) }]
+            $($rest)*
+        }
+    };
+
+    (
+        @munch $attrs:tt $vis:tt [$interface:ident $($names:tt)*] $uuid:tt
+        [$($fields:tt)*] [$($wrappers:tt)*] [$($methods:tt)*]
+        $(#[$mattr:meta])* fn $method:ident ($($p:ident : $t:ty),* $(,)?) -> $ret:ty;
+        $($rest:tt)*
+    ) => {
+        $crate::interface! {
+            @munch $attrs $vis [$interface $($names)*] $uuid
+            [$($fields)*
+                $(#[$mattr])* pub $method: unsafe extern "system" fn (This: *mut $interface, $($p: $t,)* out: *mut $ret) -> $crate::abi::HRESULT,
+            ]
+            [$($wrappers)*
+                $(#[$mattr])* #[inline] #[track_caller] pub fn $method(&self, $($p: $t),*) -> ::core::result::Result<$ret, $crate::errors::MethodHResult> {
+                    let mut out = <$ret as ::core::default::Default>::default();
+                    let hr = unsafe { ((*self.lpVtbl).$method)(self as *const Self as *mut Self, $($p,)* &mut out) };
+                    $crate::errors::MethodHResult::check(::core::concat!(::core::stringify!($interface), "::", ::core::stringify!($method)), hr)?;
+                    ::core::result::Result::Ok($crate::__out_param!(read out))
+                }
+            ]
+            [$($methods)* { out [$(#[$mattr])*] $method ($($p: $t),*) ($ret) }]
+            $($rest)*
+        }
+    };
+
+    // All methods parsed, emit the interface
+
+    (
+        @munch $attrs:tt $vis:tt [$interface:ident $vtbl:ident $impl:tt [$parent:ident $pvtbl:ident]] $uuid:tt
+        [$($fields:tt)*] $wrappers:tt $methods:tt
+    ) => {
+        $crate::interface! {
+            @emit $attrs $vis [$interface $vtbl] $uuid
+            [
+                /// The vtable of the parent interface
+                pub parent: $pvtbl,
+                $($fields)*
+            ]
+            $wrappers
+        }
+
+        $crate::com_interface! { unsafe impl ComInterface for $interface { type Parent = $parent; } }
+
+        impl ::core::ops::Deref for $interface {
+            type Target = $parent;
+            #[inline] fn deref(&self) -> &$parent {
+                unsafe { &*(self as *const $interface as *const $parent) }
+            }
+        }
+
+        $crate::interface! { @impl $vis [$interface $vtbl $impl $parent $pvtbl] $methods }
+    };
+
+    (
+        @munch $attrs:tt $vis:tt [$interface:ident $vtbl:ident [] []] $uuid:tt
+        $fields:tt $wrappers:tt $methods:tt
+    ) => {
+        $crate::interface! { @emit $attrs $vis [$interface $vtbl] $uuid $fields $wrappers }
+
+        unsafe impl $crate::RawInterface for $interface {}
+    };
+
+    (
+        @emit [$($attrs:tt)*] [$vis:vis] [$interface:ident $vtbl:ident] [$l:expr, $w1:expr, $w2:expr, $b:expr]
+        [$($fields:tt)*] [$($wrappers:tt)*]
+    ) => {
+        #[doc = ::core::concat!("The vtable of [`", ::core::stringify!($interface), "`]")]
+        #[allow(non_snake_case)]
+        #[allow(clippy::unused_unit)]
+        #[repr(C)] $vis struct $vtbl {
+            $($fields)*
+        }
+
+        $($attrs)*
+        #[repr(C)] $vis struct $interface {
+            /// The vtable of this interface
+            pub lpVtbl: *const $vtbl,
+            unconstructible: $crate::Unconstructible,
+        }
+
+        #[allow(non_snake_case)]
+        #[allow(clippy::missing_safety_doc)]
+        #[allow(clippy::unused_unit)]
+        impl $interface {
+            /// An interface pointing to `lpVtbl`, for embedding in a hand-rolled COM object.
+            ///
+            /// ### Safety
+            ///
+            /// * `lpVtbl` must point to a valid vtable for the object `Self` is embedded in, for as long as `Self` is used as an interface.
+            #[inline] pub const unsafe fn from_vtbl(lpVtbl: *const $vtbl) -> Self {
+                Self { lpVtbl, unconstructible: $crate::Unconstructible::new() }
+            }
+
+            $($wrappers)*
+        }
+
+        impl $crate::abi::Interface for $interface {
+            #[inline] fn uuidof() -> $crate::abi::GUID {
+                $crate::abi::GUID { Data1: $l, Data2: $w1, Data3: $w2, Data4: $b }
+            }
+        }
+    };
+
+    // Allow Rust types to implement the interface
+
+    ( @impl [$vis:vis] [$interface:ident $vtbl:ident [] $parent:ident $pvtbl:ident] $methods:tt ) => {};
+    ( @impl [$vis:vis] [$interface:ident $vtbl:ident [$impl:ident] $parent:ident $pvtbl:ident] [$({ $kind:ident [$($mattr:tt)*] $method:ident ($($p:ident : $t:ty),*) ($ret:ty) })*] ) => {
+        #[doc = ::core::concat!("Implement [`", ::core::stringify!($interface), "`] for a Rust type wrapped in a [ComObject](", ::core::stringify!($crate), "::ComObject).")]
+        #[allow(non_snake_case)]
+        #[allow(clippy::missing_safety_doc)]
+        #[allow(clippy::unused_unit)]
+        $vis trait $impl {
+            $($crate::interface! { @impl_fn $kind [$($mattr)*] $method ($($p: $t),*) ($ret) })*
+        }
+
+        unsafe impl $crate::object::Implementable for $interface {
+            type Vtbl = $vtbl;
+            fn is_iid_or_ancestor(iid: &$crate::abi::IID) -> bool {
+                $crate::abi::IsEqualIID(iid, &<Self as $crate::abi::Interface>::uuidof()) || <$parent as $crate::object::Implementable>::is_iid_or_ancestor(iid)
+            }
+        }
+
+        unsafe impl<S: $crate::object::Unknown> $crate::object::Vtable<S> for $vtbl where S::Class: $impl, $pvtbl: $crate::object::Vtable<S> {
+            const VTBL : Self = $vtbl {
+                parent: <$pvtbl as $crate::object::Vtable<S>>::VTBL,
+                $($method: $crate::interface! { @thunk $kind $interface $impl $method ($($p: $t),*) ($ret) },)*
+            };
+        }
+    };
+
+    ( @impl_fn raw [$($mattr:tt)*] $method:ident ($($p:ident : $t:ty),*) ($ret:ty) ) => { $($mattr)* unsafe fn $method(&self, $($p: $t),*) -> $ret; };
+    ( @impl_fn hr  [$($mattr:tt)*] $method:ident ($($p:ident : $t:ty),*) ($ret:ty) ) => { $($mattr)* fn $method(&self, $($p: $t),*) -> ::core::result::Result<(), $crate::abi::HRESULT>; };
+    ( @impl_fn out [$($mattr:tt)*] $method:ident ($($p:ident : $t:ty),*) ($ret:ty) ) => { $($mattr)* fn $method(&self, $($p: $t),*) -> ::core::result::Result<$ret, $crate::abi::HRESULT>; };
+
+    ( @thunk raw $interface:ident $impl:ident $method:ident ($($p:ident : $t:ty),*) ($ret:ty) ) => {{
+        #[allow(non_snake_case)] unsafe extern "system" fn $method<S: $crate::object::Unknown>(This: *mut $interface, $($p: $t),*) -> $ret where S::Class: $impl {
+            <S::Class as $impl>::$method(S::class(This.cast()), $($p),*)
+        }
+        $method::<S>
+    }};
+    ( @thunk hr $interface:ident $impl:ident $method:ident ($($p:ident : $t:ty),*) ($ret:ty) ) => {{
+        #[allow(non_snake_case)] unsafe extern "system" fn $method<S: $crate::object::Unknown>(This: *mut $interface, $($p: $t),*) -> $crate::abi::HRESULT where S::Class: $impl {
+            match <S::Class as $impl>::$method(S::class(This.cast()), $($p),*) {
+                ::core::result::Result::Ok(()) => $crate::abi::S_OK,
+                ::core::result::Result::Err(hr) => hr,
+            }
+        }
+        $method::<S>
+    }};
+    ( @thunk out $interface:ident $impl:ident $method:ident ($($p:ident : $t:ty),*) ($ret:ty) ) => {{
+        #[allow(non_snake_case)] unsafe extern "system" fn $method<S: $crate::object::Unknown>(This: *mut $interface, $($p: $t,)* out: *mut $ret) -> $crate::abi::HRESULT where S::Class: $impl {
+            if out.is_null() { return $crate::abi::E_POINTER }
+            match <S::Class as $impl>::$method(S::class(This.cast()), $($p),*) {
+                ::core::result::Result::Ok(value) => {
+                    $crate::__out_param!(write out value);
+                    $crate::abi::S_OK
+                },
+                ::core::result::Result::Err(hr) => { out.write(::core::default::Default::default()); hr },
+            }
+        }
+        $method::<S>
+    }};
 }
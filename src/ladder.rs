@@ -0,0 +1,113 @@
+//! [InterfaceLadder] and the `Rung*` enums returned by [Rc::cast_first].
+//!
+//! ```
+//! # use mcom::abi::*;
+//! # use mcom::Rc;
+//! # use mcom::ladder::Rung3;
+//! # mcom::interface! {
+//! #     #[uuid(0x4a6c8e01, 0x2b3d, 0x4f5e, 0x8a, 0x9b, 0x0c, 0x1d, 0x2e, 0x3f, 0x40, 0x51)]
+//! #     pub interface IFoo(IFooVtbl): IUnknown(IUnknownVtbl) {}
+//! #     #[uuid(0x4a6c8e02, 0x2b3d, 0x4f5e, 0x8a, 0x9b, 0x0c, 0x1d, 0x2e, 0x3f, 0x40, 0x51)]
+//! #     pub interface IFoo2(IFoo2Vtbl): IFoo(IFooVtbl) {}
+//! #     #[uuid(0x4a6c8e03, 0x2b3d, 0x4f5e, 0x8a, 0x9b, 0x0c, 0x1d, 0x2e, 0x3f, 0x40, 0x51)]
+//! #     pub interface IFoo3(IFoo3Vtbl): IFoo2(IFoo2Vtbl) {}
+//! # }
+//! fn newest(foo: &Rc<IFoo>) -> Result<&'static str, mcom::errors::MethodHResult> {
+//!     Ok(match foo.cast_first::<(IFoo3, IFoo2, IFoo)>()? {
+//!         Rung3::A(_foo3) => "IFoo3",
+//!         Rung3::B(_foo2) => "IFoo2",
+//!         Rung3::C(_foo)  => "IFoo",
+//!     })
+//! }
+//! ```
+
+use crate::{AsIUnknown, ComInterface, Rc};
+use crate::abi::{IUnknown, E_NOINTERFACE};
+use crate::errors::MethodHResult;
+
+use core::fmt::{self, Debug, Formatter};
+
+
+
+/// A tuple of up to 8 interfaces, in order of preference, for [Rc::cast_first] to [QueryInterface] for.
+///
+/// [QueryInterface]:   https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void)
+pub trait InterfaceLadder {
+    /// The `Rung*` enum identifying which interface was found.
+    type Rung;
+
+    /// See [Rc::cast_first].
+    #[track_caller]
+    fn cast_first<I: AsIUnknown>(rc: &Rc<I>) -> Result<Self::Rung, MethodHResult>;
+}
+
+/// `Ok(None)` if `rc` doesn't implement `I2`.  Protocol violations are errors, even if they claim `E_NOINTERFACE`.
+#[track_caller]
+fn probe<I: AsIUnknown, I2: ComInterface>(rc: &Rc<I>) -> Result<Option<Rc<I2>>, MethodHResult> {
+    match rc.cast() {
+        Ok(rc)                                                              => Ok(Some(rc)),
+        Err(err) if err.hr == E_NOINTERFACE && !err.is_protocol_violation() => Ok(None),
+        Err(err)                                                            => Err(err),
+    }
+}
+
+macro_rules! ladders {
+    ( $( $(#[$attr:meta])* $rung:ident { $( $i:ident = $index:literal ),+ } )* ) => {$(
+        $(#[$attr])*
+        pub enum $rung<$($i: ComInterface),+> {
+            $(
+                #[doc = ::core::concat!("Interface #", ::core::stringify!($index), " of the [InterfaceLadder] was found.")]
+                $i(Rc<$i>),
+            )+
+        }
+
+        impl<$($i: ComInterface),+> $rung<$($i),+> {
+            /// The index of the interface that was found, within the [InterfaceLadder] - 0 being the most preferred.
+            pub fn index(&self) -> usize { match self { $( Self::$i(_) => $index, )+ } }
+
+            /// The interface that was found, as an [IUnknown].
+            ///
+            /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+            pub fn as_unknown(&self) -> &Rc<IUnknown> { match self { $( Self::$i(rc) => rc.as_unknown(), )+ } }
+        }
+
+        impl<$($i: ComInterface),+> Clone for $rung<$($i),+> {
+            fn clone(&self) -> Self { match self { $( Self::$i(rc) => Self::$i(rc.clone()), )+ } }
+        }
+
+        impl<$($i: ComInterface),+> Debug for $rung<$($i),+> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                match self { $( Self::$i(rc) => write!(f, "{}::{}({:?})", ::core::stringify!($rung), ::core::stringify!($i), rc), )+ }
+            }
+        }
+
+        impl<$($i: ComInterface),+> InterfaceLadder for ($($i,)+) {
+            type Rung = $rung<$($i),+>;
+
+            #[track_caller]
+            fn cast_first<I: AsIUnknown>(rc: &Rc<I>) -> Result<Self::Rung, MethodHResult> {
+                $( if let Some(rc) = probe::<I, $i>(rc)? { return Ok($rung::$i(rc)) } )+
+                Err(MethodHResult::unchecked("IUnknown::QueryInterface", E_NOINTERFACE))
+            }
+        }
+    )*};
+}
+
+ladders! {
+    /// The result of [Rc::cast_first] with a 1-interface [InterfaceLadder].
+    Rung1 { A = 0 }
+    /// The result of [Rc::cast_first] with a 2-interface [InterfaceLadder].
+    Rung2 { A = 0, B = 1 }
+    /// The result of [Rc::cast_first] with a 3-interface [InterfaceLadder].
+    Rung3 { A = 0, B = 1, C = 2 }
+    /// The result of [Rc::cast_first] with a 4-interface [InterfaceLadder].
+    Rung4 { A = 0, B = 1, C = 2, D = 3 }
+    /// The result of [Rc::cast_first] with a 5-interface [InterfaceLadder].
+    Rung5 { A = 0, B = 1, C = 2, D = 3, E = 4 }
+    /// The result of [Rc::cast_first] with a 6-interface [InterfaceLadder].
+    Rung6 { A = 0, B = 1, C = 2, D = 3, E = 4, F = 5 }
+    /// The result of [Rc::cast_first] with a 7-interface [InterfaceLadder].
+    Rung7 { A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6 }
+    /// The result of [Rc::cast_first] with an 8-interface [InterfaceLadder].
+    Rung8 { A = 0, B = 1, C = 2, D = 3, E = 4, F = 5, G = 6, H = 7 }
+}
@@ -0,0 +1,104 @@
+//! Human readable names for interface IDs, as shown by the [Debug] output of [Rc](crate::Rc) and friends.
+//!
+//! The interfaces declared in [abi](crate::abi) are named out of the box.  Anything else prints as a registry-format
+//! IID (`{00000000-0000-0000-c000-000000000046}`) until it's [register]ed (requires the `alloc` feature):
+//!
+//! ```
+//! use mcom::abi::*;
+//! # #[cfg(feature = "alloc")] {
+//! # mcom::interface! {
+//! #     #[uuid(0x1f2e3d4c, 0x5b6a, 0x4798, 0x8a, 0x9b, 0xac, 0xbd, 0xce, 0xdf, 0xe0, 0xf1)]
+//! #     pub interface IFoo(IFooVtbl, IFooImpl): IUnknown(IUnknownVtbl) {}
+//! # }
+//! assert_eq!(None, mcom::names::lookup(&IFoo::uuidof()));
+//! mcom::names::register_interface::<IFoo>();
+//! assert_eq!(Some("IFoo"), mcom::names::lookup(&IFoo::uuidof()));
+//! assert_eq!("IFoo", mcom::names::Name(&IFoo::uuidof()).to_string());
+//! # }
+//! ```
+
+use crate::abi::*;
+
+#[cfg(feature = "alloc")] use alloc::boxed::Box;
+
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ptr::{null, null_mut};
+use core::sync::atomic::{AtomicPtr, Ordering::*};
+
+
+
+/// Formats as the name of an interface if [lookup] knows it, or the registry-format IID otherwise.
+#[derive(Clone, Copy)]
+pub struct Name<'a>(pub &'a IID);
+
+impl Display for Name<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if let Some(name) = lookup(self.0) { return f.write_str(name) }
+        let IID { Data1, Data2, Data3, Data4: [b0, b1, b2, b3, b4, b5, b6, b7] } = *self.0;
+        write!(f, "{{{:08x}-{:04x}-{:04x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}}}", Data1, Data2, Data3, b0, b1, b2, b3, b4, b5, b6, b7)
+    }
+}
+
+impl Debug for Name<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { Display::fmt(self, f) }
+}
+
+/// The name of interface `iid`, if it's one of mcom's [abi](crate::abi) interfaces or has been [register]ed.
+///
+/// Later registrations take precedence over earlier ones, and over mcom's own names.
+pub fn lookup(iid: &IID) -> Option<&'static str> {
+    let mut entry = REGISTERED.load(Acquire) as *const Entry;
+    while let Some(e) = unsafe { entry.as_ref() } {
+        if IsEqualIID(&e.iid, iid) { return Some(e.name) }
+        entry = e.next;
+    }
+    builtin(iid)
+}
+
+/// Name interface `iid` `name`.
+///
+/// Registrations are never freed, so this is meant to be called once per interface (e.g. at startup), not per [Rc](crate::Rc).
+/// Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub fn register(iid: IID, name: &'static str) {
+    let entry = Box::leak(Box::new(Entry { iid, name, next: null() }));
+    let mut head = REGISTERED.load(Acquire);
+    loop {
+        entry.next = head;
+        match REGISTERED.compare_exchange_weak(head, entry, AcqRel, Acquire) {
+            Ok(_)       => return,
+            Err(new)    => head = new,
+        }
+    }
+}
+
+/// Name interface `I` after it's Rust type (sans module path.)  Requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub fn register_interface<I: Interface>() {
+    let name = core::any::type_name::<I>();
+    register(I::uuidof(), name.rsplit("::").next().unwrap_or(name));
+}
+
+
+
+struct Entry {
+    iid:    IID,
+    name:   &'static str,
+    next:   *const Entry,
+}
+
+static REGISTERED : AtomicPtr<Entry> = AtomicPtr::new(null_mut());
+
+fn builtin(iid: &IID) -> Option<&'static str> {
+    fn is<I: Interface>(iid: &IID) -> bool { IsEqualIID(iid, &I::uuidof()) }
+
+    if      is::<IUnknown               >(iid) { Some("IUnknown") }
+    else if is::<IAgileObject           >(iid) { Some("IAgileObject") }
+    else if is::<IAgileReference        >(iid) { Some("IAgileReference") }
+    else if is::<INoMarshal             >(iid) { Some("INoMarshal") }
+    else if is::<IGlobalInterfaceTable  >(iid) { Some("IGlobalInterfaceTable") }
+    else if is::<IWeakReference         >(iid) { Some("IWeakReference") }
+    else if is::<IWeakReferenceSource   >(iid) { Some("IWeakReferenceSource") }
+    else { None }
+}
+
@@ -0,0 +1,746 @@
+//! [ComObject]: implement COM objects in Rust
+//!
+//! ```
+//! use mcom::abi::{HRESULT, IUnknown, IUnknownVtbl};
+//! use std::cell::Cell;
+//!
+//! mcom::interface! {
+//!     #[uuid(0x3c8e2a3b, 0x3f6b, 0x4a1e, 0x9a, 0x5e, 0x0d, 0x7b, 0x34, 0x52, 0x21, 0x01)]
+//!     pub interface ICounter(ICounterVtbl, ICounterImpl): IUnknown(IUnknownVtbl) {
+//!         fn Add(amount: u32);
+//!         fn Get() -> u32;
+//!     }
+//! }
+//!
+//! #[derive(Default)] struct Counter(Cell<u32>);
+//! mcom::implement!(Counter: ICounter);
+//!
+//! impl ICounterImpl for Counter {
+//!     fn Add(&self, amount: u32) -> Result<(), HRESULT> { self.0.set(self.0.get() + amount); Ok(()) }
+//!     fn Get(&self) -> Result<u32, HRESULT> { Ok(self.0.get()) }
+//! }
+//!
+//! let counter : mcom::Rc<ICounter> = mcom::ComObject::new(Counter::default()).into_rc();
+//! counter.Add(2).unwrap();
+//! counter.Add(3).unwrap();
+//! assert_eq!(5, counter.Get().unwrap());
+//! ```
+
+use crate::{ComInterface, Rc};
+use crate::abi::*;
+
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+use core::ptr::{NonNull, null_mut};
+use core::sync::atomic::{AtomicPtr, AtomicU32, Ordering::*, fence};
+
+use alloc::boxed::Box;
+
+
+
+/// A strong reference to a heap allocated, Rust-implemented COM object wrapping a `T`.
+///
+/// Each interface `T` is declared to implement via [implement!](crate::implement) gets its own vtable pointer within the
+/// object, and [QueryInterface] follows the usual [IUnknown] identity rules:  querying any interface of the object
+/// for [IUnknown] always returns the same pointer.  Reference counts are atomic, and `T` is dropped when the last
+/// reference (either [ComObject] or [Rc]) is released.
+///
+/// ### Aggregation
+///
+/// Objects created with [ComObject::new_aggregated] follow the COM [aggregation] rules:  slot 0 is the
+/// non-delegating inner [IUnknown] (which is what the [ComObject] itself refers to), while every other interface
+/// delegates `QueryInterface`, `AddRef`, and `Release` to the controlling outer unknown.
+///
+/// ### Tear-offs
+///
+/// Rarely used interfaces can be listed with `tearoff` in [implement!](crate::implement) to avoid costing a vtable
+/// pointer in every instance.  Querying for them allocates a small [TearOff] object with it's own reference count,
+/// holding a strong reference to the main object (and to the outer unknown, if aggregated.)  [IUnknown] identity is preserved, although every query returns a
+/// new tear-off.
+///
+/// ### Weak references
+///
+/// Objects that aren't aggregated implement [IWeakReferenceSource] automatically, for use with
+/// [Rc::downgrade](crate::Rc::downgrade).  A separate weak count keeps the allocation (but not `T`) alive.
+///
+/// Conversely, a Rust outer object can aggregate another object by holding an [Aggregated] field, listed with
+/// `aggregate` in [implement!](crate::implement).
+///
+/// [aggregation]:      https://learn.microsoft.com/en-us/windows/win32/com/aggregation
+/// [QueryInterface]:   https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void)
+/// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+pub struct ComObject<T: Class>(NonNull<Block<T>>);
+
+impl<T: Class> ComObject<T> {
+    /// Move `value` into a new heap allocated COM object.
+    pub fn new(value: T) -> Self {
+        Self::new_impl(value, null_mut())
+    }
+
+    /// Move `value` into a new heap allocated COM object, aggregated by `outer`.
+    ///
+    /// The returned [ComObject] (and <code>[into_rc](Self::into_rc)::&lt;[IUnknown]&gt;()</code>) refers to the
+    /// non-delegating inner unknown, which `outer` should hold on to, and forward `QueryInterface` calls to for
+    /// whichever interfaces it wishes to expose.  All other interface pointers (including those returned by
+    /// [to_rc](Self::to_rc)) delegate their [IUnknown] methods, and thus reference counts, to `outer`.
+    ///
+    /// `outer` is not [AddRef]ed, to avoid a reference cycle.
+    ///
+    /// ### Safety
+    ///
+    /// * `outer` must outlive every reference to the object's interfaces other than [IUnknown] -
+    ///   typically by owning the inner unknown, and only releasing it while being destroyed.
+    ///
+    /// [AddRef]:   https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+    pub unsafe fn new_aggregated(value: T, outer: &Rc<IUnknown>) -> Self {
+        Self::new_impl(value, outer.as_ptr())
+    }
+
+    fn new_impl(value: T, outer: *mut IUnknown) -> Self {
+        let block = Box::new(Block { vtbls: T::VTBLS, refs: AtomicU32::new(1), weak: AtomicU32::new(1), outer, value: ManuallyDrop::new(value) });
+        Self(NonNull::from(Box::leak(block)))
+    }
+
+    /// Get a new [Rc] to interface `I` of this object.
+    #[track_caller]
+    pub fn to_rc<I: ComInterface>(&self) -> Rc<I> where T: Implements<I> {
+        unsafe { Block::add_ref(self.0.as_ptr(), T::SLOT) };
+        unsafe { Rc::from_raw(self.slot(T::SLOT).cast()) }
+    }
+
+    /// Convert this object reference into an [Rc] to interface `I` of this object.
+    ///
+    /// Panics if the object is [aggregated](Self::new_aggregated) and `I` isn't [IUnknown]:  `self` counts towards the
+    /// inner unknown, while the result would count towards `outer`, leaving nothing to keep the object alive.
+    /// Use [to_rc](Self::to_rc) while the inner unknown is held instead.
+    #[track_caller]
+    pub fn into_rc<I: ComInterface>(self) -> Rc<I> where T: Implements<I> {
+        assert!(T::SLOT == 0 || self.block().outer.is_null(), "ComObject::into_rc: aggregated objects can only be converted into their non-delegating IUnknown");
+        let ptr = self.slot(T::SLOT);
+        core::mem::forget(self);
+        unsafe { Rc::from_raw(ptr.cast()) }
+    }
+
+    unsafe fn from_block(block: *mut Block<T>) -> Self {
+        (*block).refs.fetch_add(1, Relaxed);
+        Self(NonNull::new_unchecked(block))
+    }
+
+    fn block(&self) -> &Block<T> { unsafe { self.0.as_ref() } }
+    fn slot(&self, n: usize) -> *mut c_void { unsafe { Block::slot(self.0.as_ptr(), n) } }
+}
+
+unsafe impl<T: Class + Send + Sync> Send for ComObject<T> {}
+unsafe impl<T: Class + Send + Sync> Sync for ComObject<T> {}
+
+impl<T: Class> Clone for ComObject<T> {
+    fn clone(&self) -> Self {
+        self.block().refs.fetch_add(1, Relaxed);
+        Self(self.0)
+    }
+}
+
+impl<T: Class> Deref for ComObject<T> {
+    type Target = T;
+    fn deref(&self) -> &T { &self.block().value }
+}
+
+impl<T: Class> Drop for ComObject<T> {
+    fn drop(&mut self) { unsafe { Block::release(self.0.as_ptr(), 0) }; }
+}
+
+impl<T: Class, I: ComInterface> From<ComObject<T>> for Rc<I> where T: Implements<I> {
+    #[track_caller]
+    fn from(object: ComObject<T>) -> Self { object.into_rc() }
+}
+
+
+
+/// A Rust type that can be wrapped in a [ComObject].  Implement with [implement!](crate::implement).
+///
+/// ### Safety
+///
+/// * `Vtbls` must be `[*const c_void; N]`, where `VTBLS[0]` is an [IUnknownVtbl] for [`Slot<Self, 0>`], and
+///   `VTBLS[n]` is a vtable of an interface implemented by `Self` for [`Slot<Self, n>`].
+/// * `query_slot` must return `Some(0)` for [IUnknown], and otherwise only return slots whose vtable implements `iid`.
+/// * `query_tearoff` must only return functions creating tear-offs implementing `iid`.
+/// * `query_aggregated` must only return non-delegating inner unknowns aggregated by `self`.
+pub unsafe trait Class : Sized + 'static {
+    #[doc(hidden)] type Vtbls : Copy + 'static;
+    #[doc(hidden)] const VTBLS : Self::Vtbls;
+    #[doc(hidden)] fn query_slot(iid: &IID) -> Option<usize>;
+    #[doc(hidden)] fn query_tearoff(_iid: &IID) -> Option<fn(ComObject<Self>) -> *mut c_void> { None }
+    #[doc(hidden)] fn query_aggregated(&self, _iid: &IID) -> Option<&IUnknown> { None }
+}
+
+/// `Self` is a [Class] implementing interface `I` (excluding [IUnknown] and other ancestor interfaces.)  Implement with [implement!](crate::implement).
+///
+/// ### Safety
+///
+/// * `SLOT` must be the index of the vtable for `I` within `Self::VTBLS`.
+pub unsafe trait Implements<I: ComInterface> : Class {
+    #[doc(hidden)] const SLOT : usize;
+}
+
+unsafe impl<T: Class> Implements<IUnknown> for T {
+    const SLOT : usize = 0;
+}
+
+/// An interface which can be implemented by Rust [Class]es.  Implemented by [interface!](macro@crate::interface) for interfaces with an `Impl` trait.
+///
+/// ### Safety
+///
+/// * `Vtbl` must be the vtable type of `Self`.
+/// * `is_iid_or_ancestor(iid)` must only return `true` if `Self` is, or derives from, the interface identified by `iid`.
+pub unsafe trait Implementable : ComInterface + 'static {
+    /// The vtable of this interface
+    type Vtbl : 'static;
+
+    /// Returns `true` if `iid` identifies this interface or any of the interfaces it derives from.
+    fn is_iid_or_ancestor(iid: &IID) -> bool;
+}
+
+unsafe impl Implementable for IUnknown {
+    type Vtbl = IUnknownVtbl;
+    fn is_iid_or_ancestor(iid: &IID) -> bool { IsEqualIID(iid, &IUnknown::uuidof()) }
+}
+
+/// A vtable which can be populated with thunks to the Rust implementation located through `S`.  Implemented by [interface!](macro@crate::interface).
+///
+/// ### Safety
+///
+/// * `VTBL` must be a valid vtable for interface pointers handled by `S`.
+pub unsafe trait Vtable<S: Unknown> : Sized + 'static {
+    /// A vtable of thunks to the Rust implementation
+    const VTBL : Self;
+}
+
+unsafe impl<S: Unknown> Vtable<S> for IUnknownVtbl {
+    const VTBL : Self = IUnknownVtbl { QueryInterface: S::query_interface, AddRef: S::add_ref, Release: S::release };
+}
+
+/// Locates the Rust implementation behind an interface pointer, and implements [IUnknown] for it.
+///
+/// ### Safety
+///
+/// * `class` must return a reference that remains valid while `this` does.
+/// * The [IUnknown] methods must implement the COM refcounting and identity rules.
+pub unsafe trait Unknown : 'static {
+    /// The Rust type implementing the interface's methods
+    type Class;
+
+    /// Get the Rust implementation of an interface pointer.
+    ///
+    /// ### Safety
+    ///
+    /// * `this` must be an interface pointer whose vtable was created from <code>[Vtable]&lt;Self&gt;</code>.
+    unsafe fn class<'a>(this: *mut c_void) -> &'a Self::Class;
+
+    /// [IUnknown::QueryInterface](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void))
+    ///
+    /// ### Safety
+    ///
+    /// * `this` must be a valid interface pointer handled by `Self`.
+    /// * `riid` must be null or point to a valid [IID].  `ppv` must be null or valid for writes.
+    unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT;
+
+    /// [IUnknown::AddRef](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref)
+    ///
+    /// ### Safety
+    ///
+    /// * `this` must be a valid interface pointer handled by `Self`.
+    unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG;
+
+    /// [IUnknown::Release](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release)
+    ///
+    /// ### Safety
+    ///
+    /// * `this` must be a valid interface pointer handled by `Self`, which the caller owns a reference through.
+    unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG;
+}
+
+/// The [Unknown] implementation of interface pointers to vtable `N` of a <code>[ComObject]&lt;T&gt;</code>
+pub struct Slot<T, const N: usize>(PhantomData<T>);
+
+unsafe impl<T: Class, const N: usize> Unknown for Slot<T, N> {
+    type Class = T;
+
+    unsafe fn class<'a>(this: *mut c_void) -> &'a T { &(*Block::<T>::from_slot(this, N)).value }
+
+    unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        Block::<T>::query_interface(Block::from_slot(this.cast(), N), N, riid, ppv)
+    }
+
+    unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG {
+        Block::<T>::add_ref(Block::from_slot(this.cast(), N), N)
+    }
+
+    unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG {
+        Block::<T>::release(Block::from_slot(this.cast(), N), N)
+    }
+}
+
+
+
+#[repr(C)] struct Block<T: Class> {
+    vtbls:  T::Vtbls, // must be first: interface pointers are pointers into this array
+    refs:   AtomicU32,
+    weak:   AtomicU32, // all strong refs collectively hold 1 weak ref, keeping the allocation alive
+    outer:  *mut IUnknown, // controlling unknown if aggregated (not AddRef'ed), otherwise null
+    value:  ManuallyDrop<T>, // dropped when `refs` reaches 0, deallocated when `weak` reaches 0
+}
+
+impl<T: Class> Block<T> {
+    unsafe fn from_slot(this: *mut c_void, n: usize) -> *mut Self { this.cast::<*const c_void>().sub(n).cast() }
+    unsafe fn slot(block: *mut Self, n: usize) -> *mut c_void { block.cast::<*const c_void>().add(n).cast() }
+
+    /// The controlling unknown that slot `n` delegates to, if any.  Slot 0 never delegates.
+    unsafe fn delegate(block: *mut Self, n: usize) -> Option<&'static IUnknown> {
+        if n == 0 { None } else { (*block).outer.as_ref() }
+    }
+
+    unsafe fn query_interface(block: *mut Self, n: usize, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        if let Some(outer) = Self::delegate(block, n) { return outer.QueryInterface(riid, ppv) }
+        if ppv.is_null() { return E_POINTER }
+        *ppv = null_mut();
+        let Some(riid) = riid.as_ref() else { return E_INVALIDARG };
+        if let Some(slot) = T::query_slot(riid) {
+            Self::add_ref(block, slot); // interfaces other than the inner unknown count towards the outer unknown
+            *ppv = Self::slot(block, slot);
+            S_OK
+        } else if IsEqualIID(riid, &IWeakReferenceSource::uuidof()) && (*block).outer.is_null() {
+            *ppv = WeakSource::create(ComObject::from_block(block).into_rc(), block.cast(), Self::WEAK_OPS).cast();
+            S_OK
+        } else if let Some(tearoff) = T::query_tearoff(riid) {
+            *ppv = tearoff(ComObject::from_block(block));
+            S_OK
+        } else if let Some(inner) = (*block).value.query_aggregated(riid) {
+            inner.QueryInterface(riid, ppv)
+        } else {
+            E_NOINTERFACE
+        }
+    }
+
+    unsafe fn add_ref(block: *mut Self, n: usize) -> ULONG {
+        if let Some(outer) = Self::delegate(block, n) { return outer.AddRef() }
+        (*block).refs.fetch_add(1, Relaxed) + 1
+    }
+
+    unsafe fn release(block: *mut Self, n: usize) -> ULONG {
+        if let Some(outer) = Self::delegate(block, n) { return outer.Release() }
+        let refs = (*block).refs.fetch_sub(1, Release) - 1;
+        if refs == 0 {
+            fence(Acquire);
+            ManuallyDrop::drop(&mut (*block).value);
+            Self::release_weak(block.cast());
+        }
+        refs
+    }
+
+    const WEAK_OPS : &'static WeakOps = &WeakOps { resolve: Self::resolve, add_weak: Self::add_weak, release_weak: Self::release_weak };
+
+    unsafe fn resolve(block: *mut c_void, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        let block = block.cast::<Self>();
+        if ppv.is_null() { return E_POINTER }
+        *ppv = null_mut();
+        let mut refs = (*block).refs.load(Relaxed);
+        loop {
+            if refs == 0 { return S_OK } // already destroyed
+            match (*block).refs.compare_exchange_weak(refs, refs + 1, Acquire, Relaxed) {
+                Ok(_)       => break,
+                Err(actual) => refs = actual,
+            }
+        }
+        let hr = Self::query_interface(block, 0, riid, ppv);
+        Self::release(block, 0);
+        hr
+    }
+
+    unsafe fn add_weak(block: *mut c_void) {
+        (*block.cast::<Self>()).weak.fetch_add(1, Relaxed);
+    }
+
+    unsafe fn release_weak(block: *mut c_void) {
+        let block = block.cast::<Self>();
+        if (*block).weak.fetch_sub(1, Release) == 1 {
+            fence(Acquire);
+            drop(Box::from_raw(block));
+        }
+    }
+}
+
+
+
+/// Type-erased weak reference operations on a `Block<T>`.
+struct WeakOps {
+    resolve:        unsafe fn(block: *mut c_void, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT,
+    add_weak:       unsafe fn(block: *mut c_void),
+    release_weak:   unsafe fn(block: *mut c_void),
+}
+
+/// The [IWeakReferenceSource] of a [ComObject], allocated per `QueryInterface` like a [TearOff].
+#[repr(C)] struct WeakSource {
+    vtbl:   *const IWeakReferenceSourceVtbl,
+    refs:   AtomicU32,
+    object: Rc<IUnknown>,
+    block:  *mut c_void,
+    ops:    &'static WeakOps,
+}
+
+impl WeakSource {
+    const VTBL : &'static IWeakReferenceSourceVtbl = &<IWeakReferenceSourceVtbl as Vtable<Self>>::VTBL;
+
+    fn create(object: Rc<IUnknown>, block: *mut c_void, ops: &'static WeakOps) -> *mut IWeakReferenceSource {
+        Box::into_raw(Box::new(Self { vtbl: Self::VTBL, refs: AtomicU32::new(1), object, block, ops })).cast()
+    }
+}
+
+unsafe impl Unknown for WeakSource {
+    type Class = Self;
+
+    unsafe fn class<'a>(this: *mut c_void) -> &'a Self { &*this.cast() }
+
+    unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        if ppv.is_null() { return E_POINTER }
+        match riid.as_ref() {
+            Some(iid) if IsEqualIID(iid, &IWeakReferenceSource::uuidof()) => {
+                Self::add_ref(this);
+                *ppv = this.cast();
+                S_OK
+            },
+            _ => (*this.cast::<Self>()).object.QueryInterface(riid, ppv),
+        }
+    }
+
+    unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG {
+        (*this.cast::<Self>()).refs.fetch_add(1, Relaxed) + 1
+    }
+
+    unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG {
+        let this = this.cast::<Self>();
+        let refs = (*this).refs.fetch_sub(1, Release) - 1;
+        if refs == 0 {
+            fence(Acquire);
+            drop(Box::from_raw(this));
+        }
+        refs
+    }
+}
+
+impl IWeakReferenceSourceImpl for WeakSource {
+    fn GetWeakReference(&self) -> Result<Option<Rc<IWeakReference>>, HRESULT> {
+        unsafe { (self.ops.add_weak)(self.block) };
+        Ok(Some(unsafe { Rc::from_raw(WeakRef::create(self.block, self.ops)) }))
+    }
+}
+
+/// The [IWeakReference] of a [ComObject], holding a weak count.
+#[repr(C)] struct WeakRef {
+    vtbl:   *const IWeakReferenceVtbl,
+    refs:   AtomicU32,
+    block:  *mut c_void,
+    ops:    &'static WeakOps,
+}
+
+impl WeakRef {
+    const VTBL : &'static IWeakReferenceVtbl = &<IWeakReferenceVtbl as Vtable<Self>>::VTBL;
+
+    /// ### Safety
+    ///
+    /// * The caller must have already added a weak count to `block`, which the result will take ownership of.
+    unsafe fn create(block: *mut c_void, ops: &'static WeakOps) -> *mut IWeakReference {
+        Box::into_raw(Box::new(Self { vtbl: Self::VTBL, refs: AtomicU32::new(1), block, ops })).cast()
+    }
+}
+
+unsafe impl Unknown for WeakRef {
+    type Class = Self;
+
+    unsafe fn class<'a>(this: *mut c_void) -> &'a Self { &*this.cast() }
+
+    unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        if ppv.is_null() { return E_POINTER }
+        match riid.as_ref() {
+            Some(iid) if IsEqualIID(iid, &IWeakReference::uuidof()) || IsEqualIID(iid, &IUnknown::uuidof()) => {
+                Self::add_ref(this);
+                *ppv = this.cast();
+                S_OK
+            },
+            _ => {
+                *ppv = null_mut();
+                E_NOINTERFACE
+            },
+        }
+    }
+
+    unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG {
+        (*this.cast::<Self>()).refs.fetch_add(1, Relaxed) + 1
+    }
+
+    unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG {
+        let this = this.cast::<Self>();
+        let refs = (*this).refs.fetch_sub(1, Release) - 1;
+        if refs == 0 {
+            fence(Acquire);
+            ((*this).ops.release_weak)((*this).block);
+            drop(Box::from_raw(this));
+        }
+        refs
+    }
+}
+
+impl IWeakReferenceImpl for WeakRef {
+    unsafe fn Resolve(&self, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        (self.ops.resolve)(self.block, riid, ppv)
+    }
+}
+
+
+
+/// The [Unknown] implementation of a tear-off interface `I` of a <code>[ComObject]&lt;T&gt;</code>, and the object allocated for each such interface pointer.
+///
+/// Interfaces other than `I` (and it's ancestors) are queried from the main object.  Tear-offs of aggregated objects
+/// also hold a strong reference to the controlling outer unknown, which they query instead.
+#[repr(C)] pub struct TearOff<T: Class, I: Implementable> {
+    vtbl:       *const I::Vtbl,
+    refs:       AtomicU32,
+    main:       ComObject<T>,
+    outer:      Option<Rc<IUnknown>>,
+    interface:  PhantomData<fn() -> I>,
+}
+
+impl<T: Class, I: Implementable> TearOff<T, I> where I::Vtbl : Vtable<Self> {
+    /// ### Safety
+    ///
+    /// * `vtbl` must be <code>&amp;&lt;I::Vtbl as [Vtable]&lt;Self&gt;&gt;::VTBL</code>.
+    ///   (Generic code can't promote that to a `&'static` reference itself, as `I::Vtbl` might be interior mutable.)
+    #[doc(hidden)] pub unsafe fn create(main: ComObject<T>, vtbl: &'static I::Vtbl) -> *mut c_void {
+        let outer = Rc::borrow_ptr_opt(&main.block().outer).cloned();
+        let tearoff = Box::new(Self { vtbl, refs: AtomicU32::new(1), main, outer, interface: PhantomData });
+        Box::into_raw(tearoff).cast()
+    }
+}
+
+unsafe impl<T: Class, I: Implementable> Unknown for TearOff<T, I> {
+    type Class = T;
+
+    unsafe fn class<'a>(this: *mut c_void) -> &'a T { &(*this.cast::<Self>()).main }
+
+    unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        if ppv.is_null() { return E_POINTER }
+        match riid.as_ref() {
+            Some(iid) if I::is_iid_or_ancestor(iid) && !IsEqualIID(iid, &IUnknown::uuidof()) => {
+                Self::add_ref(this);
+                *ppv = this.cast();
+                S_OK
+            },
+            _ => match (*this.cast::<Self>()).outer.as_ref() {
+                Some(outer) => outer.QueryInterface(riid, ppv),
+                None        => Block::query_interface((*this.cast::<Self>()).main.0.as_ptr(), 0, riid, ppv),
+            },
+        }
+    }
+
+    unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG {
+        (*this.cast::<Self>()).refs.fetch_add(1, Relaxed) + 1
+    }
+
+    unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG {
+        let this = this.cast::<Self>();
+        let refs = (*this).refs.fetch_sub(1, Release) - 1;
+        if refs == 0 {
+            fence(Acquire);
+            drop(Box::from_raw(this));
+        }
+        refs
+    }
+}
+
+
+
+/// The non-delegating inner unknown of an object aggregated by a [Class].
+///
+/// List the field in [implement!](crate::implement) to expose some of the inner object's interfaces
+/// through the outer object's `QueryInterface`:
+///
+/// ```text
+/// struct Outer { inner: Aggregated }
+/// mcom::implement!(Outer: IFoo; aggregate inner: IBar, IBaz);
+/// ```
+///
+/// Since the inner object must be created with the outer object's [IUnknown], the field starts out empty,
+/// and is [set](Self::set) once the outer [ComObject] exists.  The inner unknown is released when `Self` is dropped.
+pub struct Aggregated(AtomicPtr<IUnknown>);
+
+impl Aggregated {
+    /// An empty field, with no aggregated inner object (yet.)
+    pub const fn new() -> Self { Self(AtomicPtr::new(null_mut())) }
+
+    /// Set the non-delegating inner unknown of the aggregated object.  Fails if already set.
+    ///
+    /// `inner` should have been created with this object's [IUnknown] as it's outer unknown, e.g. via
+    /// [ComObject::new_aggregated] or `Rc::co_create`.
+    pub fn set(&self, inner: Rc<IUnknown>) -> Result<(), Rc<IUnknown>> {
+        match self.0.compare_exchange(null_mut(), inner.as_ptr(), AcqRel, Acquire) {
+            Ok(_) => { let _ = inner.into_raw(); Ok(()) }, // n.b. not `forget`:  re-adopted by `drop`, as far as `leak-check` is concerned
+            Err(_) => Err(inner),
+        }
+    }
+
+    /// The non-delegating inner unknown of the aggregated object, if [set](Self::set).
+    pub fn get(&self) -> Option<&IUnknown> { unsafe { self.0.load(Acquire).as_ref() } }
+}
+
+impl Default for Aggregated {
+    fn default() -> Self { Self::new() }
+}
+
+impl Drop for Aggregated {
+    fn drop(&mut self) {
+        let inner = *self.0.get_mut();
+        if !inner.is_null() { drop(unsafe { Rc::from_raw(inner) }) }
+    }
+}
+
+
+
+/// `__out_param!(write out value)` writes the result of an `interface!` out-pointer thunk, `__out_param!(read out)`
+/// reads the result of an `interface!` out-pointer wrapper.  Both are plain moves unless `leak-check` is enabled.
+#[cfg(not(feature = "leak-check"))]
+#[doc(hidden)] #[macro_export] macro_rules! __out_param {
+    ( write $out:ident $value:ident ) => { $out.write($value) };
+    ( read $out:ident ) => { $out };
+}
+
+/// `__out_param!(write out value)` writes the result of an `interface!` out-pointer thunk, `__out_param!(read out)`
+/// reads the result of an `interface!` out-pointer wrapper, via [OutParam].
+#[cfg(feature = "leak-check")]
+#[doc(hidden)] #[macro_export] macro_rules! __out_param {
+    ( write $out:ident $value:ident ) => {{
+        #[allow(unused_imports)] use $crate::object::{OutParamAny as _, OutParamRc as _};
+        (&$crate::object::OutParam(::core::cell::Cell::new(::core::option::Option::Some($value)))).write_out($out)
+    }};
+    ( read $out:ident ) => {{
+        #[allow(unused_imports)] use $crate::object::{OutParamAny as _, OutParamRc as _};
+        (&$crate::object::OutParam(::core::cell::Cell::new(::core::option::Option::Some($out)))).read_out()
+    }};
+}
+
+/// Writes the result of an `interface!` out-pointer thunk, or reads the result of an `interface!` out-pointer wrapper.
+///
+/// Implemented via autoref specialization:  [Rc]s are written with [Rc::into_raw] and read with [Rc::from_raw], so
+/// `leak-check` knows ownership was transferred.  Everything else is written and read as-is.
+#[cfg(feature = "leak-check")]
+#[doc(hidden)] pub struct OutParam<T>(pub core::cell::Cell<Option<T>>);
+
+#[cfg(feature = "leak-check")]
+#[doc(hidden)] pub trait OutParamRc<T> {
+    unsafe fn write_out(&self, out: *mut T);
+    #[track_caller] fn read_out(&self) -> T;
+}
+
+#[cfg(feature = "leak-check")]
+#[doc(hidden)] pub trait OutParamAny<T> {
+    unsafe fn write_out(&self, out: *mut T);
+    fn read_out(&self) -> T;
+}
+
+#[cfg(feature = "leak-check")]
+impl<I: crate::AsIUnknown> OutParamRc<Option<Rc<I>>> for OutParam<Option<Rc<I>>> {
+    unsafe fn write_out(&self, out: *mut Option<Rc<I>>) {
+        let raw = match self.0.take().flatten() { Some(rc) => rc.into_raw(), None => null_mut() };
+        out.cast::<*mut I>().write(raw);
+    }
+
+    #[track_caller] fn read_out(&self) -> Option<Rc<I>> {
+        let raw = self.0.take().flatten().map(ManuallyDrop::new)?.as_ptr();
+        unsafe { Rc::from_raw_opt(raw) }
+    }
+}
+
+#[cfg(feature = "leak-check")]
+impl<T> OutParamAny<T> for &OutParam<T> {
+    unsafe fn write_out(&self, out: *mut T) { out.write(self.0.take().unwrap()) }
+    fn read_out(&self) -> T { self.0.take().unwrap() }
+}
+
+
+
+/// Implement [Class] and [Implements] for a Rust type, allowing it to be wrapped in a [ComObject].
+///
+/// ```text
+/// mcom::implement!(MyType: IFoo, IBar);
+/// mcom::implement!(MyType: IFoo; tearoff IRarelyUsed);
+/// mcom::implement!(MyOuterType: IFoo; aggregate inner: IBaz, IQux);
+/// ```
+///
+/// *   `MyType` must implement the `Impl` trait of each interface listed, and those of their ancestor interfaces.
+/// *   Ancestor interfaces (including [IUnknown]) are reachable through `QueryInterface`, and should not be listed.
+/// *   `; tearoff IPersist, IDiagnostics` exposes those interfaces through [TearOff]s instead of vtable pointers in every instance.
+/// *   `; aggregate field: IBaz, IQux` forwards `QueryInterface` for exactly those IIDs to the [Aggregated] `field`.
+/// *   `MyType` must not be generic.
+/// *   Panics unwinding out of an `Impl` method will abort.
+#[macro_export]
+macro_rules! implement {
+    ( @one $interface:ty ) => { 1 };
+
+    ( @vtbls $class:ty; $n:expr; [$($vtbls:expr,)*]; ) => { [$($vtbls,)*] };
+    ( @vtbls $class:ty; $n:expr; [$($vtbls:expr,)*]; $interface:ty $(, $rest:ty)* ) => {
+        $crate::implement!(@vtbls $class; $n + 1; [$($vtbls,)*
+            &<<$interface as $crate::object::Implementable>::Vtbl as $crate::object::Vtable<$crate::object::Slot<$class, {$n}>>>::VTBL as *const _ as *const $crate::abi::c_void,
+        ]; $($rest),*)
+    };
+
+    ( @implements $class:ty; $n:expr; ) => {};
+    ( @implements $class:ty; $n:expr; $interface:ty $(, $rest:ty)* ) => {
+        unsafe impl $crate::object::Implements<$interface> for $class { const SLOT : usize = $n; }
+        $crate::implement!(@implements $class; $n + 1; $($rest),*);
+    };
+
+    ( $class:ty : $($interface:ty),+ $(; tearoff $($tearoff:ty),+)? $(; aggregate $field:ident : $($aggregated:ty),+)? $(;)? ) => {
+        unsafe impl $crate::object::Class for $class {
+            type Vtbls = [*const $crate::abi::c_void; 1 $(+ $crate::implement!(@one $interface))+];
+            const VTBLS : Self::Vtbls = $crate::implement!(@vtbls $class; 1; [
+                &<$crate::abi::IUnknownVtbl as $crate::object::Vtable<$crate::object::Slot<$class, 0>>>::VTBL as *const _ as *const $crate::abi::c_void,
+            ]; $($interface),+);
+
+            fn query_slot(iid: &$crate::abi::IID) -> ::core::option::Option<usize> {
+                if $crate::abi::IsEqualIID(iid, &<$crate::abi::IUnknown as $crate::abi::Interface>::uuidof()) { return ::core::option::Option::Some(0) }
+                let mut slot = 0;
+                $(
+                    slot += 1;
+                    if <$interface as $crate::object::Implementable>::is_iid_or_ancestor(iid) { return ::core::option::Option::Some(slot) }
+                )+
+                ::core::option::Option::None
+            }
+
+            $(
+                fn query_tearoff(iid: &$crate::abi::IID) -> ::core::option::Option<fn($crate::object::ComObject<Self>) -> *mut $crate::abi::c_void> {
+                    $(
+                        if <$tearoff as $crate::object::Implementable>::is_iid_or_ancestor(iid) {
+                            fn tearoff(main: $crate::object::ComObject<$class>) -> *mut $crate::abi::c_void {
+                                type TearOff = $crate::object::TearOff<$class, $tearoff>;
+                                const VTBL : &'static <$tearoff as $crate::object::Implementable>::Vtbl = &<<$tearoff as $crate::object::Implementable>::Vtbl as $crate::object::Vtable<TearOff>>::VTBL;
+                                unsafe { TearOff::create(main, VTBL) }
+                            }
+                            return ::core::option::Option::Some(tearoff)
+                        }
+                    )+
+                    ::core::option::Option::None
+                }
+            )?
+
+            $(
+                fn query_aggregated(&self, iid: &$crate::abi::IID) -> ::core::option::Option<&$crate::abi::IUnknown> {
+                    $(
+                        if $crate::abi::IsEqualIID(iid, &<$aggregated as $crate::abi::Interface>::uuidof()) { return self.$field.get() }
+                    )+
+                    ::core::option::Option::None
+                }
+            )?
+        }
+
+        $crate::implement!(@implements $class; 1; $($interface),+);
+    };
+}
@@ -0,0 +1,89 @@
+use crate::{AsIUnknown, Rc};
+use crate::abi::{HRESULT, IUnknown, E_POINTER, c_void};
+use crate::errors::MethodHResult;
+
+use core::fmt::{self, Debug, Formatter};
+use core::ptr::null_mut;
+
+
+
+/// An interface out parameter, for functions that return a new interface pointer through a `*mut *mut I` (or `void**`.)
+///
+/// Starts out null.  Whatever a function writes through [as_mut_ptr](Self::as_mut_ptr) or [as_mut_void](Self::as_mut_void)
+/// is owned by the [OutPtr], and is either handed off to an [Rc] by [check](Self::check), or [Release]d when dropped.
+///
+/// ```
+/// # use mcom::abi::*;
+/// # use mcom::{OutPtr, Rc};
+/// fn query_unknown(rc: &Rc<IUnknown>) -> Result<Rc<IUnknown>, mcom::errors::MethodHResult> {
+///     let mut out = OutPtr::<IUnknown>::new();
+///     let hr = unsafe { rc.QueryInterface(&IUnknown::uuidof(), out.as_mut_void()) };
+///     out.check("IUnknown::QueryInterface", hr)
+/// }
+/// ```
+///
+/// See also [Rc::from_out] and [Rc::from_out_iid], which wrap the above pattern.
+///
+/// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+#[repr(transparent)] pub struct OutPtr<I: AsIUnknown>(*mut I);
+
+impl<I: AsIUnknown> OutPtr<I> {
+    /// A null out parameter.
+    pub fn new() -> Self { Self(null_mut()) }
+
+    /// A `*mut *mut I` to pass to FFI.
+    ///
+    /// Only write null or an [IUnknown]-derived COM interface pointer (accessible from the current COM apartment) through it:
+    /// [OutPtr] takes ownership of whatever is written, and will eventually [Release] it.
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    pub fn as_mut_ptr(&mut self) -> *mut *mut I { &mut self.0 }
+
+    /// A `void**` to pass to FFI alongside `I`'s IID, such as to [QueryInterface] or [IAgileReference::Resolve].
+    ///
+    /// Only write null or an `I` interface pointer (accessible from the current COM apartment) through it:
+    /// [OutPtr] takes ownership of whatever is written, and will eventually [Release] it.
+    ///
+    /// [QueryInterface]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void)
+    /// [IAgileReference::Resolve]: https://learn.microsoft.com/en-us/windows/win32/api/objidl/nf-objidl-iagilereference-resolve(refiid_void)
+    /// [Release]:                  https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    pub fn as_mut_void(&mut self) -> *mut *mut c_void { self.as_mut_ptr().cast() }
+
+    /// `true` if nothing has been written (or null was written.)
+    pub fn is_null(&self) -> bool { self.0.is_null() }
+
+    /// Take ownership of the written interface pointer, if `hr` indicates success.
+    ///
+    /// ### Returns
+    ///
+    /// * `Ok(Rc(...))` - Success!
+    /// * `Err(MethodHResult(method, hr))` - `hr` indicates failure.  Any interface pointer written anyways is [Release]d.
+    /// * `Err(MethodHResult(method, 0x80004003))` - aka `E_POINTER` - `hr` indicates success, but no interface pointer was written.
+    ///
+    /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    #[track_caller]
+    pub fn check(mut self, method: &'static str, hr: HRESULT) -> Result<Rc<I>, MethodHResult> {
+        let ptr = core::mem::replace(&mut self.0, null_mut());
+        if let Err(err) = MethodHResult::check(method, hr) {
+            drop(unsafe { Rc::from_raw_opt(ptr.cast::<IUnknown>()) }); // n.b. not necessarily an `I`
+            return Err(err);
+        }
+        unsafe { Rc::from_raw_opt(ptr) }.ok_or(MethodHResult::unchecked(method, E_POINTER))
+    }
+}
+
+impl<I: AsIUnknown> Default for OutPtr<I> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<I: AsIUnknown> Debug for OutPtr<I> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { write!(f, "OutPtr({:?})", self.0) }
+}
+
+impl<I: AsIUnknown> Drop for OutPtr<I> {
+    fn drop(&mut self) {
+        drop(unsafe { Rc::from_raw_opt(self.0) });
+    }
+}
+
@@ -1,18 +1,26 @@
-use crate::AsIUnknown;
-use crate::errors::MethodHResult;
-
-use winapi::{Interface};
-use winapi::shared::winerror::*;
-use winapi::shared::guiddef::GUID;
-use winapi::shared::wtypesbase::{CLSCTX, CLSCTX_INPROC_SERVER};
-use winapi::um::combaseapi::{CoCreateInstance, CoCreateInstanceFromApp};
-use winapi::um::objidlbase::MULTI_QI;
-use winapi::um::unknwnbase::IUnknown;
-
-use core::convert::TryInto;
+use crate::{AsIUnknown, ComInterface, Inherits, InterfaceLadder, OutPtr};
+use crate::abi::{HRESULT, IID, Interface, SUCCEEDED, c_void};
+use crate::identity::canonical;
+
+#[cfg(windows)] use crate::abi::IUnknown;
+#[cfg(windows)] use crate::errors::MethodHResult;
+
+#[cfg(windows)] use winapi::shared::winerror::*;
+#[cfg(windows)] use winapi::shared::guiddef::GUID;
+#[cfg(windows)] use winapi::shared::wtypesbase::{CLSCTX, CLSCTX_INPROC_SERVER};
+#[cfg(windows)] use winapi::um::combaseapi::{CoCreateInstance, CoCreateInstanceFromApp};
+#[cfg(windows)] use winapi::um::objidlbase::MULTI_QI;
+
+#[cfg(windows)] use core::convert::TryInto;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
 use core::ptr::{NonNull, null_mut};
 use core::ops::Deref;
 
+#[cfg(feature = "alloc")] use alloc::vec;
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
+
 
 
 /// A \![Send]+\![Sync] basic reference counting smart pointer residing within the current COM apartment.
@@ -31,8 +39,13 @@ impl<I: AsIUnknown> Rc<I> {
     /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
     /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
     /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[track_caller]
     pub unsafe fn from_raw_opt(ptr: *mut I) -> Option<Self> {
-        Some(Self(NonNull::new(ptr)?))
+        #[cfg(feature = "validate")] crate::validate::pointer("adopted", ptr);
+        let rc = Self(NonNull::new(ptr)?);
+        #[cfg(feature = "leak-check")] crate::debug::created(&rc, true);
+        crate::refcount::adopted(&rc);
+        Some(rc)
     }
 
     /// Take ownership of a raw COM pointer.  [AddRef] will **not** be called.  [Release] **will* be called when this [Rc] is dropped.
@@ -47,6 +60,7 @@ impl<I: AsIUnknown> Rc<I> {
     /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
     /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
     /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[track_caller]
     pub unsafe fn from_raw(ptr: *mut I) -> Self {
         Self::from_raw_opt(ptr).unwrap()
     }
@@ -63,8 +77,13 @@ impl<I: AsIUnknown> Rc<I> {
     /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
     /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
     /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[track_caller]
     pub unsafe fn from_raw_unchecked(ptr: *mut I) -> Self {
-        Self(NonNull::new_unchecked(ptr))
+        #[cfg(feature = "validate")] crate::validate::pointer("adopted", ptr);
+        let rc = Self(NonNull::new_unchecked(ptr));
+        #[cfg(feature = "leak-check")] crate::debug::created(&rc, true);
+        crate::refcount::adopted(&rc);
+        rc
     }
 
     /// Borrow a raw COM pointer.  [AddRef] will **not** be called.  [Release] will not be called either, as this returns a transmuted reference.
@@ -80,7 +99,9 @@ impl<I: AsIUnknown> Rc<I> {
     /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
     #[doc(hidden)]
     #[deprecated(since = "0.1.2", note = "use `borrow_ptr_opt` instead")]
+    #[track_caller]
     pub unsafe fn borrow(ptr: &*mut I) -> &Option<Self> {
+        #[cfg(feature = "validate")] crate::validate::pointer("borrowed", *ptr);
         core::mem::transmute(ptr)
     }
 
@@ -95,7 +116,9 @@ impl<I: AsIUnknown> Rc<I> {
     /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
     /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
     /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[track_caller]
     pub unsafe fn borrow_ptr_opt(ptr: &*mut I) -> Option<&Self> {
+        #[cfg(feature = "validate")] crate::validate::pointer("borrowed", *ptr);
         let xmute : &Option<Self> = core::mem::transmute(ptr);
         xmute.as_ref()
     }
@@ -111,6 +134,7 @@ impl<I: AsIUnknown> Rc<I> {
     /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
     /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
     /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[track_caller]
     pub unsafe fn borrow_ptr(ptr: &*mut I) -> &Self {
         Self::borrow_ptr_opt(ptr).unwrap()
     }
@@ -126,7 +150,9 @@ impl<I: AsIUnknown> Rc<I> {
     /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
     /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
     /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[track_caller]
     pub unsafe fn borrow_ptr_unchecked(ptr: &*mut I) -> &Self {
+        #[cfg(feature = "validate")] crate::validate::pointer("borrowed", *ptr);
         core::mem::transmute(ptr)
     }
 
@@ -144,6 +170,51 @@ impl<I: AsIUnknown> Rc<I> {
         core::mem::transmute(r)
     }
 
+    /// Take ownership of a COM pointer returned through an out parameter by `f`.  See [OutPtr::check] for the possible errors.
+    ///
+    /// ```
+    /// # use mcom::abi::*;
+    /// # fn example(rc: &mcom::Rc<IUnknown>) -> Result<mcom::Rc<IUnknown>, mcom::errors::MethodHResult> {
+    /// unsafe { mcom::Rc::from_out("IUnknown::QueryInterface", |pp: &mut *mut IUnknown| rc.QueryInterface(&IUnknown::uuidof(), (pp as *mut *mut IUnknown).cast())) }
+    /// # }
+    /// ```
+    ///
+    /// ### Safety
+    ///
+    /// * `f` must only write null, or a "valid" [IUnknown]-derived COM interface pointer accessible from the current COM apartment, to it's parameter.
+    /// * Anything written is owned by the resulting [Rc] (or [Release]d on failure.)
+    ///
+    /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[track_caller]
+    pub unsafe fn from_out(method: &'static str, f: impl FnOnce(&mut *mut I) -> HRESULT) -> Result<Self, crate::errors::MethodHResult> {
+        let mut out = OutPtr::new();
+        let hr = f(&mut *out.as_mut_ptr());
+        out.check(method, hr)
+    }
+
+    /// Take ownership of a COM pointer returned through a `void**` out parameter by `f`, for `I`'s IID.  See [OutPtr::check] for the possible errors.
+    ///
+    /// ```
+    /// # use mcom::abi::*;
+    /// # fn example(rc: &mcom::Rc<IUnknown>) -> Result<mcom::Rc<IUnknown>, mcom::errors::MethodHResult> {
+    /// unsafe { mcom::Rc::from_out_iid("IUnknown::QueryInterface", |iid, ppv| rc.QueryInterface(iid, ppv)) }
+    /// # }
+    /// ```
+    ///
+    /// ### Safety
+    ///
+    /// * `f` must only write null, or a "valid" COM interface pointer for the IID it was passed accessible from the current COM apartment, to it's `void**` parameter.
+    /// * Anything written is owned by the resulting [Rc] (or [Release]d on failure.)
+    ///
+    /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    #[track_caller]
+    pub unsafe fn from_out_iid(method: &'static str, f: impl FnOnce(&IID, &mut *mut c_void) -> HRESULT) -> Result<Self, crate::errors::MethodHResult> where I : Interface {
+        let mut out = OutPtr::new();
+        let hr = f(&I::uuidof(), &mut *out.as_mut_void());
+        out.check(method, hr)
+    }
+
     /// [CoCreateInstance](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-cocreateinstance)\[[FromApp](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-cocreateinstancefromapp)\]
     ///
     /// ### Safety
@@ -152,34 +223,41 @@ impl<I: AsIUnknown> Rc<I> {
     /// * `clsid` and `I` are assumed to be well behaved COM APIs.  This is probably a bad assumption, but a failure to be so is a bug in C++ code, not Rust code.
     ///
     /// [winapi#961]:       https://github.com/retep998/winapi-rs/pull/961
+    #[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))]
+    #[track_caller]
     pub unsafe fn co_create(clsid: GUID, outer: Option<&Rc<IUnknown>>) -> Result<Self, MethodHResult> where I : Interface {
         Self::co_create_impl(clsid, outer)
     }
 
+    #[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))]
     #[cfg(any(partition = "desktop", partition = "system", partition = "games"))]
+    #[track_caller]
     unsafe fn co_create_impl(clsid: GUID, outer: Option<&Rc<IUnknown>>) -> Result<Self, MethodHResult> where I : Interface {
         Self::co_create_instance(clsid, outer, CLSCTX_INPROC_SERVER)
     }
 
+    #[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))]
     #[cfg(not(any(partition = "desktop", partition = "system", partition = "games")))]
+    #[track_caller]
     unsafe fn co_create_impl(clsid: GUID, outer: Option<&Rc<IUnknown>>) -> Result<Self, MethodHResult> where I : Interface {
         Self::co_create_instance_from_app(clsid, outer, CLSCTX_INPROC_SERVER, ())
     }
 
     /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-cocreateinstance)\]
+    #[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))]
     #[cfg(any(partition = "desktop", partition="system", partition="games"))]
+    #[track_caller]
     unsafe fn co_create_instance(clsid: GUID, outer: Option<&Rc<IUnknown>>, clsctx: CLSCTX) -> Result<Self, MethodHResult> where I : Interface {
-        let mut ptr = null_mut();
         let outer = outer.map_or(null_mut(), |unk| unk.as_iunknown_ptr());
-        let hr = CoCreateInstance(&clsid, outer, clsctx, &I::uuidof(), &mut ptr);
-        MethodHResult::check("CoCreateInstance", hr)?;
-        Ok(Self::from_raw(ptr.cast()))
+        Self::from_out_iid("CoCreateInstance", |iid, ppv| CoCreateInstance(&clsid, outer, clsctx, iid, ppv))
     }
 
     /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-cocreateinstancefromapp)\]
+    #[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))]
     #[cfg(windows = "8.0")]
     #[cfg(any(partition = "app", partition = "system"))]
     #[allow(dead_code)]
+    #[track_caller]
     unsafe fn co_create_instance_from_app(clsid: GUID, outer: Option<&Rc<IUnknown>>, clsctx: CLSCTX, reserved: ()) -> Result<Self, MethodHResult> where I : Interface {
         let iid = I::uuidof();
         let mut mqi = [MULTI_QI { pIID: &iid, pItf: null_mut(), hr: 0 }];
@@ -192,12 +270,101 @@ impl<I: AsIUnknown> Rc<I> {
     /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void))\]
     ///
     /// Queries a COM object for a pointer to one of its interface; identifying the interface by a reference to its interface identifier (IID).
+    ///
+    /// Discards the reason for any failure - see [Rc::cast] to get it.
+    #[track_caller]
     pub fn try_cast<I2: Interface + AsIUnknown>(&self) -> Option<Rc<I2>> {
+        self.cast().ok()
+    }
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void))\]
+    ///
+    /// Queries a COM object for a pointer to one of its interface; identifying the interface by a reference to its interface identifier (IID).
+    ///
+    /// ### Returns
+    ///
+    /// * `Ok(Rc(...))` - Success!
+    /// * `Err(MethodHResult("IUnknown::QueryInterface", 0x80004002))` - aka `E_NOINTERFACE` - The object doesn't implement `I2`.
+    /// * `Err(MethodHResult("IUnknown::QueryInterface", ...))` - Any other failure, such as `E_OUTOFMEMORY`.
+    /// * `Err(MethodHResult("IUnknown::QueryInterface", S_OK, SucceededWithoutInterface))` - The object violated COM's
+    ///   rules by succeeding without writing an interface pointer.
+    /// * `Err(MethodHResult("IUnknown::QueryInterface", ..., FailedWithInterface))` - The object violated COM's rules by
+    ///   failing without nulling the interface pointer.  The pointer is leaked rather than released, as it may be garbage.
+    ///
+    /// The rule violations keep the object's own [HRESULT](crate::abi::HRESULT), and can be told apart from ordinary
+    /// failures by [MethodHResult::protocol_violation](crate::errors::MethodHResult::protocol_violation).
+    #[track_caller]
+    pub fn cast<I2: Interface + AsIUnknown>(&self) -> Result<Rc<I2>, crate::errors::MethodHResult> {
+        use crate::errors::{MethodHResult, ProtocolViolation};
         let mut ptr = null_mut();
         let hr = unsafe { self.0.as_ref().as_iunknown().QueryInterface(&I2::uuidof(), &mut ptr) };
-        // hr should be S_OK or E_NONTERFACE
-        if !SUCCEEDED(hr) { return None; }
-        unsafe { Rc::from_raw_opt(ptr.cast()) }
+        let rc = match (SUCCEEDED(hr), NonNull::new(ptr.cast())) {
+            (true,  Some(ptr))  => Rc(ptr),
+            (true,  None)       => return Err(MethodHResult::violation("IUnknown::QueryInterface", hr, ProtocolViolation::SucceededWithoutInterface)),
+            (false, Some(_))    => return Err(MethodHResult::violation("IUnknown::QueryInterface", hr, ProtocolViolation::FailedWithInterface)),
+            (false, None)       => return Err(MethodHResult::unchecked("IUnknown::QueryInterface", hr)),
+        };
+        #[cfg(feature = "leak-check")] crate::debug::created(&rc, false);
+        crate::refcount::adopted(&rc);
+        Ok(rc)
+    }
+
+    /// Queries a COM object for the first interface of `L` it implements, such as the newest of several interface versions.
+    ///
+    /// Discards the reason for any failure - see [Rc::cast_first] to get it.
+    #[track_caller]
+    pub fn try_cast_first<L: InterfaceLadder>(&self) -> Option<L::Rung> {
+        self.cast_first::<L>().ok()
+    }
+
+    /// Queries a COM object for the first interface of `L` it implements, such as the newest of several interface versions.
+    ///
+    /// Interfaces are [cast](Rc::cast) in order, moving on to the next only if the object doesn't implement the previous one.
+    ///
+    /// ### Returns
+    ///
+    /// * `Ok(Rung*::A(...))` etc. - Success!  See [crate::ladder] for an example.
+    /// * `Err(MethodHResult("IUnknown::QueryInterface", 0x80004002))` - aka `E_NOINTERFACE` - The object doesn't implement any of `L`.
+    /// * `Err(MethodHResult(...))` - Any other failure [Rc::cast] can return, which stops probing at that interface.
+    ///   This includes protocol violations, even if they claim `E_NOINTERFACE`.
+    #[track_caller]
+    pub fn cast_first<L: InterfaceLadder>(&self) -> Result<L::Rung, crate::errors::MethodHResult> {
+        L::cast_first(self)
+    }
+
+    /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/weakreference/nf-weakreference-iweakreferencesource-getweakreference)\]
+    ///
+    /// Get a [Weak] reference to this object, via [IWeakReferenceSource].  Fails if the object doesn't implement it.
+    ///
+    /// [Weak]:                     crate::Weak
+    /// [IWeakReferenceSource]:     https://learn.microsoft.com/en-us/windows/win32/api/weakreference/nn-weakreference-iweakreferencesource
+    #[track_caller]
+    pub fn downgrade(&self) -> Result<crate::Weak<I>, crate::errors::MethodHResult> where I : Interface {
+        crate::Weak::new(self)
+    }
+
+    /// `true` if `this` and `other` hold the exact same interface pointer.
+    ///
+    /// This is **not** the same as referring to the same object:  different interfaces (or even the same interface,
+    /// for tear-offs) of the same object may have different pointers.  See [Rc::same_object] for that.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.0 == other.0
+    }
+
+    /// The [IUnknown] [Identity](crate::Identity) of this object, for use as a cacheable `HashMap` / `BTreeMap` key.
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    pub fn identity(&self) -> crate::Identity {
+        crate::Identity::of(self)
+    }
+
+    /// `true` if `self` and `other` refer to the same COM object, as determined by querying both for [IUnknown].
+    ///
+    /// This is also what [PartialEq], [Ord], and [Hash] compare.
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    pub fn same_object<I2: AsIUnknown>(&self, other: &Rc<I2>) -> bool {
+        self.as_ptr() as usize == other.as_ptr() as usize || canonical(self.as_iunknown()) == canonical(other.as_iunknown())
     }
 
     /// Retrieve a raw pointer for passing to COM APIs.  This [Rc] maintains ownership of the pointer.
@@ -209,8 +376,10 @@ impl<I: AsIUnknown> Rc<I> {
     /// This is a potential memory leak if the function this pointer was passed to did not assume ownership.
     ///
     /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    #[track_caller]
     pub fn into_raw(self) -> *mut I {
         let p = self.as_ptr();
+        #[cfg(feature = "leak-check")] crate::debug::escaped(p as usize);
         core::mem::forget(self);
         p
     }
@@ -219,36 +388,155 @@ impl<I: AsIUnknown> Rc<I> {
     /// This is a memory leak, and should probably only be used for long lived factory types that never need to be reinitialized.
     ///
     /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    #[track_caller]
     pub fn leak(p: Self) -> &'static I {
         unsafe { &*p.into_raw() }
     }
+
+    /// Borrow `slice` as an array of raw COM pointers, for APIs like [PSSetShaderResources].  [AddRef] will **not** be called.
+    ///
+    /// The array remains valid for as long as `slice` is borrowed.
+    ///
+    /// [AddRef]:               https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+    /// [PSSetShaderResources]: https://learn.microsoft.com/en-us/windows/win32/api/d3d11/nf-d3d11-id3d11devicecontext-pssetshaderresources
+    pub fn slice_as_ptrs(slice: &[Self]) -> *const *mut I {
+        slice.as_ptr().cast()
+    }
+
+    /// Borrow `slice` as an array of nullable raw COM pointers, for APIs like [OMSetRenderTargets].  [AddRef] will **not** be called.
+    ///
+    /// The array remains valid for as long as `slice` is borrowed.
+    ///
+    /// [AddRef]:               https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+    /// [OMSetRenderTargets]:   https://learn.microsoft.com/en-us/windows/win32/api/d3d11/nf-d3d11-id3d11devicecontext-omsetrendertargets
+    pub fn option_slice_as_ptrs(slice: &[Option<Self>]) -> *const *mut I {
+        slice.as_ptr().cast()
+    }
+
+    /// Take ownership of an array of raw COM pointers.  [AddRef] will **not** be called.  [Release] **will** be called when the [Rc]s are dropped.  Requires the `alloc` feature.
+    ///
+    /// ### Safety
+    ///
+    /// * Each pointer in `ptrs` may be null, in which case it becomes `None`.  Otherwise:
+    /// * Each pointer must be a "valid" [IUnknown]-derived COM interface pointer, accessible from the current COM apartment.
+    /// * Each pointer's reference must be owned by the caller, and will be [Release]d when the resulting [Rc]s are dropped.
+    ///
+    /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+    /// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    pub unsafe fn from_raw_slice(ptrs: &[*mut I]) -> Vec<Option<Self>> {
+        ptrs.iter().map(|&ptr| Self::from_raw_opt(ptr)).collect()
+    }
+
+    /// Take ownership of an array of `count` COM pointers returned through an out parameter by `f`, for APIs like [OMGetRenderTargets].  Requires the `alloc` feature.
+    ///
+    /// Pointers are adopted before the [HRESULT] is checked, so any written by a partially failing `f` are [Release]d.
+    ///
+    /// ```
+    /// # use mcom::abi::*;
+    /// # fn example(rc: &mcom::Rc<IUnknown>) -> Result<(), mcom::errors::MethodHResult> {
+    /// let unks = unsafe { mcom::Rc::<IUnknown>::from_out_array("IUnknown::QueryInterface", 2, |pp| {
+    ///     for i in 0 .. 2 { rc.QueryInterface(&IUnknown::uuidof(), pp.add(i).cast()); }
+    ///     S_OK
+    /// }) }?;
+    /// assert!(unks.iter().all(|unk| unk.is_some()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ### Safety
+    ///
+    /// * `f` must only write null, or "valid" [IUnknown]-derived COM interface pointers accessible from the current COM apartment, to the `count` elements of it's parameter.
+    /// * Anything written is owned by the resulting [Rc]s (or [Release]d on failure.)
+    ///
+    /// [HRESULT]:              crate::abi::HRESULT
+    /// [Release]:              https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+    /// [IUnknown]:             https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    /// [OMGetRenderTargets]:   https://learn.microsoft.com/en-us/windows/win32/api/d3d11/nf-d3d11-id3d11devicecontext-omgetrendertargets
+    #[cfg(feature = "alloc")]
+    #[track_caller]
+    pub unsafe fn from_out_array(method: &'static str, count: usize, f: impl FnOnce(*mut *mut I) -> HRESULT) -> Result<Vec<Option<Self>>, crate::errors::MethodHResult> {
+        let mut ptrs = vec![null_mut(); count];
+        let hr = f(ptrs.as_mut_ptr());
+        if let Err(err) = crate::errors::MethodHResult::check(method, hr) {
+            drop(Rc::<crate::abi::IUnknown>::from_raw_slice(&ptrs.iter().map(|ptr| ptr.cast()).collect::<Vec<_>>())); // n.b. not necessarily `I`s
+            return Err(err);
+        }
+        Ok(Self::from_raw_slice(&ptrs))
+    }
 }
 
-impl<I: AsIUnknown + Deref> Rc<I> where I::Target : AsIUnknown + Sized {
-    /// Cast up the COM inheritence tree
-    pub fn up(self) -> Rc<I::Target> {
-        let raw = self.into_raw();
-        let cast : *mut I::Target = raw.cast();
-        let deref : *const I::Target = unsafe { &**raw };
-        assert_eq!(cast as *const _, deref);
-        unsafe { Rc::from_raw(cast) }
+impl<I: ComInterface> Rc<I> {
+    /// Cast up the COM inheritence tree (see also [Rc::upcast])
+    pub fn up(self) -> Rc<I::Parent> {
+        let this = core::mem::ManuallyDrop::new(self); // n.b. not `into_raw`:  the same reference, as far as `leak-check` is concerned
+        Rc(this.0.cast())
+    }
+
+    /// Cast up the COM inheritence tree (see also [Rc::upcast_ref])
+    pub fn up_ref(&self) -> &Rc<I::Parent> {
+        unsafe { &*(self as *const Self).cast() }
+    }
+
+    /// Cast up the COM inheritence tree to any ancestor `A` in one step, without calling `QueryInterface`.
+    ///
+    /// ```
+    /// # use mcom::Rc;
+    /// # use mcom::abi::*;
+    /// mcom::interface! {
+    ///     #[uuid(0x51b7c3e1, 0x0a2d, 0x4f6e, 0x9b, 0x38, 0x7d, 0x21, 0xc4, 0x5e, 0x80, 0x19)]
+    ///     pub interface IFoo(IFooVtbl): IUnknown(IUnknownVtbl) {}
+    ///
+    ///     #[uuid(0x51b7c3e2, 0x0a2d, 0x4f6e, 0x9b, 0x38, 0x7d, 0x21, 0xc4, 0x5e, 0x80, 0x19)]
+    ///     pub interface IFoo2(IFoo2Vtbl): IFoo(IFooVtbl) {}
+    ///
+    ///     #[uuid(0x51b7c3e3, 0x0a2d, 0x4f6e, 0x9b, 0x38, 0x7d, 0x21, 0xc4, 0x5e, 0x80, 0x19)]
+    ///     pub interface IFoo3(IFoo3Vtbl): IFoo2(IFoo2Vtbl) {}
+    /// }
+    ///
+    /// fn foo(foo3: Rc<IFoo3>) -> Rc<IFoo> { foo3.upcast() } // rather than foo3.up().up()
+    /// fn unknown(foo3: Rc<IFoo3>) -> Rc<IUnknown> { foo3.into() }
+    /// ```
+    pub fn upcast<A: ComInterface>(self) -> Rc<A> where I : Inherits<A> {
+        let this = core::mem::ManuallyDrop::new(self); // n.b. not `into_raw`:  the same reference, as far as `leak-check` is concerned
+        Rc(this.0.cast())
+    }
+
+    /// Cast up the COM inheritence tree to any ancestor `A` in one step, without calling `QueryInterface`.
+    pub fn upcast_ref<A: ComInterface>(&self) -> &Rc<A> where I : Inherits<A> {
+        unsafe { &*(self as *const Self).cast() }
+    }
+
+    /// Cast all the way up the COM inheritence tree, without calling `QueryInterface`.
+    ///
+    /// n.b. this is the same interface pointer, which isn't necessarily the object's canonical [IUnknown] - see [Rc::identity] for that.
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    pub fn into_unknown(self) -> Rc<crate::abi::IUnknown> {
+        let this = core::mem::ManuallyDrop::new(self); // n.b. not `into_raw`:  the same reference, as far as `leak-check` is concerned
+        Rc(this.0.cast())
     }
 
-    /// Cast up the COM inheritence tree
-    pub fn up_ref(&self) -> &Rc<I::Target> {
-        let raw = self.as_ptr();
-        let cast : *mut I::Target = raw.cast();
-        let deref : *const I::Target = unsafe { &**raw };
-        assert_eq!(cast as *const _, deref);
-        unsafe { core::mem::transmute(self) }
+    /// Cast all the way up the COM inheritence tree, without calling `QueryInterface`.
+    ///
+    /// n.b. this is the same interface pointer, which isn't necessarily the object's canonical [IUnknown] - see [Rc::identity] for that.
+    ///
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    pub fn as_unknown(&self) -> &Rc<crate::abi::IUnknown> {
+        unsafe { &*(self as *const Self).cast() }
     }
 }
 
 impl<I: AsIUnknown> Clone for Rc<I> {
+    #[track_caller]
     fn clone(&self) -> Self {
-        let _old_rc = unsafe { self.as_iunknown().AddRef() };
-        // XXX: Consider asserting if _old_rc > u32::MAX/3 to avoid RC overflows?
-        Self(self.0)
+        let count = unsafe { self.as_iunknown().AddRef() };
+        crate::refcount::added(count);
+        let rc = Self(self.0);
+        #[cfg(feature = "leak-check")] crate::debug::created(&rc, false);
+        rc
     }
 }
 
@@ -259,6 +547,7 @@ impl<I: AsIUnknown> Deref for Rc<I> {
 
 impl<I: AsIUnknown> Drop for Rc<I> {
     fn drop(&mut self) {
+        #[cfg(feature = "leak-check")] crate::debug::dropped(self.as_ptr() as usize);
         let (unk, release) = {
             let unk = self.as_iunknown_ptr();
             let release = unsafe { (*(*unk).lpVtbl).Release };
@@ -268,28 +557,89 @@ impl<I: AsIUnknown> Drop for Rc<I> {
     }
 }
 
+/// COM identity:  `true` if both refer to the same object.  See [Rc::same_object].
+impl<I: AsIUnknown, I2: AsIUnknown> PartialEq<Rc<I2>> for Rc<I> {
+    fn eq(&self, other: &Rc<I2>) -> bool { self.same_object(other) }
+}
+
+impl<I: AsIUnknown> Eq for Rc<I> {}
+
+/// Orders by the canonical [IUnknown] pointers of the objects.
+///
+/// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+impl<I: AsIUnknown> PartialOrd for Rc<I> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+/// Orders by the canonical [IUnknown] pointers of the objects.
+///
+/// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+impl<I: AsIUnknown> Ord for Rc<I> {
+    fn cmp(&self, other: &Self) -> Ordering { canonical(self.as_iunknown()).cmp(&canonical(other.as_iunknown())) }
+}
+
+/// Hashes the canonical [IUnknown] pointer of the object.
+///
+/// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+impl<I: AsIUnknown> Hash for Rc<I> {
+    fn hash<H: Hasher>(&self, state: &mut H) { canonical(self.as_iunknown()).hash(state) }
+}
+
+/// `Rc<IFoo>(0x...)`:  the [name](crate::names) of the interface, and the interface pointer.
+/// Also shows the current reference count if [enabled](crate::refcount::set_debug_refcounts).
+impl<I: Interface + AsIUnknown> Debug for Rc<I> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Rc<{}>({:?}", crate::names::Name(&I::uuidof()), self.as_ptr())?;
+        if crate::refcount::debug_refcounts() { write!(f, ", refcount: {}", crate::refcount::current(self.as_iunknown()))?; }
+        write!(f, ")")
+    }
+}
+
 impl<I: AsIUnknown> AsRef<Rc<I>> for Rc<I> {
     fn as_ref(&self) -> &Self { self }
 }
 
-#[cfg(feature = "com-0-3")]
+/// Implement <code>[From]&lt;[Rc]&lt;D&gt;&gt; for [Rc]&lt;I&gt;</code> for all `D` that [Inherits] from `I`, via [Rc::upcast].
+///
+/// Only possible for interfaces declared by mcom itself:  the orphan rules prevent other crates from implementing
+/// [From] for [Rc], so interfaces declared with [interface!](macro@crate::interface) elsewhere use [Rc::upcast] instead.
+macro_rules! upcasts {
+    ( $( $interface:ty ),* $(,)? ) => {$(
+        impl<D: $crate::Inherits<$interface>> ::core::convert::From<$crate::Rc<D>> for $crate::Rc<$interface> {
+            fn from(rc: $crate::Rc<D>) -> Self { rc.upcast() }
+        }
+    )*};
+}
+pub(crate) use upcasts;
+
+upcasts! {
+    crate::abi::IUnknown,
+    crate::abi::IAgileObject,
+    crate::abi::IAgileReference,
+    crate::abi::INoMarshal,
+    crate::abi::IGlobalInterfaceTable,
+    crate::abi::IWeakReference,
+    crate::abi::IWeakReferenceSource,
+}
+
+#[cfg(all(windows, feature = "com-0-3"))]
 mod interop_com_0_3_crate {
     use super::*;
 
     impl From<com_0_3::interfaces::IUnknown> for Rc<IUnknown> {
         fn from(com: com_0_3::interfaces::IUnknown) -> Self {
-            unsafe { core::mem::transmute(com) }
+            unsafe { Self::from_raw_unchecked(core::mem::transmute::<com_0_3::interfaces::IUnknown, *mut IUnknown>(com)) }
         }
     }
 
     impl From<Rc<IUnknown>> for com_0_3::interfaces::IUnknown {
         fn from(rc: Rc<IUnknown>) -> Self {
-            unsafe { core::mem::transmute(rc) }
+            unsafe { core::mem::transmute::<*mut IUnknown, com_0_3::interfaces::IUnknown>(rc.into_raw()) }
         }
     }
 }
 
-#[cfg(feature = "wio-0-2")]
+#[cfg(all(windows, feature = "wio-0-2"))]
 mod interop_wio_0_2_crate {
     use super::*;
 
@@ -308,6 +658,7 @@ mod interop_wio_0_2_crate {
 
 
 /// \[[microsoft.com](https://learn.microsoft.com/en-us/windows/win32/api/combaseapi/nf-combaseapi-cocreateinstancefromapp)\]
+#[cfg(all(windows, windows = "2000", any(partition = "app", partition = "system", partition = "games")))]
 #[cfg(windows = "8.0")]
 #[cfg(any(partition = "app", partition = "system"))]
 #[allow(dead_code)]
@@ -320,6 +671,7 @@ unsafe fn co_create_instance_from_app(clsid: GUID, outer: Option<&Rc<IUnknown>>,
 }
 
 #[test] fn layout() {
+    use crate::abi::IUnknown;
     use core::mem::*;
     use core::ffi::c_void;
 
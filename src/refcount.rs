@@ -0,0 +1,143 @@
+//! What to do when [Rc] observes a COM reference count that's about to overflow.
+//!
+//! [AddRef] returns the new reference count, which [Rc] checks whenever it [clones](Clone::clone) a reference.
+//! When [Rc] adopts a reference it didn't `AddRef` itself ([Rc::from_raw], [Rc::try_cast], etc.), the count is probed
+//! with an extra `AddRef` / `Release` pair - but only if the current [OverflowAction] would do something about it.
+//!
+//! The policy is a process-wide setting, defaulting to [OverflowPolicy::DEFAULT], which doesn't check anything.
+//! Opt in with e.g. <code>[set_overflow_policy]\([OverflowPolicy::DEBUG_ASSERT]\)</code>.
+//!
+//! Separately, [set_debug_refcounts] opts [Rc]'s [Debug](core::fmt::Debug) output into showing reference counts,
+//! probed the same way.
+//!
+//! [AddRef]:   https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+
+use crate::Rc;
+use crate::abi::*;
+
+use core::sync::atomic::{AtomicBool, AtomicU8, AtomicU32, Ordering::*};
+
+
+
+/// How to react to a reference count exceeding [OverflowPolicy::threshold] or wrapping around to 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OverflowAction {
+    /// Don't check reference counts at all.
+    Ignore,
+
+    /// `debug_assert!` the reference count is sane:  panics in debug builds, does nothing (and skips probing) in release builds.
+    DebugAssert,
+
+    /// Abort the process, even in release builds.
+    Abort,
+}
+
+/// The process-wide reference count overflow policy of [Rc].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OverflowPolicy {
+    /// What to do when a reference count is suspicious.
+    pub action:     OverflowAction,
+
+    /// Reference counts above this are considered suspicious.  Unused by [OverflowAction::Ignore].
+    pub threshold:  ULONG,
+}
+
+impl OverflowPolicy {
+    /// Don't check reference counts (or probe them) at all.
+    ///
+    /// The [threshold](Self::threshold) only matters once the [action](Self::action) is changed, e.g. by
+    /// `OverflowPolicy { action: OverflowAction::Abort, ..OverflowPolicy::DEFAULT }`.
+    pub const DEFAULT : OverflowPolicy = OverflowPolicy { action: OverflowAction::Ignore, threshold: u32::MAX / 3 };
+
+    /// `debug_assert!` reference counts stay below <code>[u32::MAX] / 3</code>, leaving plenty of headroom for code
+    /// that doesn't check.
+    pub const DEBUG_ASSERT : OverflowPolicy = OverflowPolicy { action: OverflowAction::DebugAssert, threshold: u32::MAX / 3 };
+}
+
+impl Default for OverflowPolicy {
+    fn default() -> Self { Self::DEFAULT }
+}
+
+/// The current process-wide [OverflowPolicy].
+pub fn overflow_policy() -> OverflowPolicy {
+    let action = match ACTION.load(Relaxed) {
+        0 => OverflowAction::Ignore,
+        1 => OverflowAction::DebugAssert,
+        _ => OverflowAction::Abort,
+    };
+    OverflowPolicy { action, threshold: THRESHOLD.load(Relaxed) }
+}
+
+/// Replace the process-wide [OverflowPolicy], returning the previous one.
+pub fn set_overflow_policy(policy: OverflowPolicy) -> OverflowPolicy {
+    let prev = overflow_policy();
+    THRESHOLD.store(policy.threshold, Relaxed);
+    ACTION.store(policy.action as u8, Relaxed);
+    prev
+}
+
+
+/// Whether [Rc]'s [Debug](core::fmt::Debug) output includes the current reference count.  Defaults to `false`.
+pub fn debug_refcounts() -> bool { DEBUG_REFCOUNTS.load(Relaxed) }
+
+/// Enable or disable reference counts in [Rc]'s [Debug](core::fmt::Debug) output, returning the previous setting.
+///
+/// Counts are probed with a balanced `AddRef` / `Release` pair.  Like [AddRef]'s return value, they're only meant for
+/// diagnostics:  other threads, or the object itself, may change the count at any time.
+///
+/// [AddRef]:   https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+pub fn set_debug_refcounts(enabled: bool) -> bool { DEBUG_REFCOUNTS.swap(enabled, Relaxed) }
+
+/// The current reference count of `unk`, as reported by a balanced `AddRef` / `Release` pair.
+pub(crate) fn current(unk: &IUnknown) -> ULONG {
+    unsafe { unk.AddRef() };
+    unsafe { unk.Release() }
+}
+
+
+
+/// `true` if [adopted] would do anything, and is worth probing a reference count for.
+fn probe() -> bool {
+    match ACTION.load(Relaxed) {
+        0 => false,
+        1 => cfg!(debug_assertions),
+        _ => true,
+    }
+}
+
+/// Check the reference count `AddRef` returned when cloning an [Rc].
+#[track_caller] pub(crate) fn added(count: ULONG) {
+    if !probe() { return }
+    if count == 0 || count > THRESHOLD.load(Relaxed) { overflowed(count) }
+}
+
+/// Probe and check the reference count of an object an [Rc] just adopted.
+#[track_caller] pub(crate) fn adopted<I: crate::AsIUnknown>(rc: &Rc<I>) {
+    if !probe() { return }
+    let unk = rc.as_iunknown();
+    let count = unsafe { unk.AddRef() };
+    unsafe { unk.Release() };
+    added(count);
+}
+
+#[track_caller] fn overflowed(count: ULONG) {
+    match overflow_policy().action {
+        OverflowAction::Ignore      => {},
+        OverflowAction::DebugAssert => debug_assert!(false, "COM reference count overflow:  AddRef returned {} (0x{:08x})", count, count),
+        OverflowAction::Abort       => abort(count),
+    }
+}
+
+#[cfg(feature = "std")] fn abort(count: ULONG) -> ! {
+    std::eprintln!("COM reference count overflow:  AddRef returned {} (0x{:08x}), aborting", count, count);
+    std::process::abort()
+}
+
+#[cfg(not(feature = "std"))] fn abort(count: ULONG) -> ! {
+    extern "C" fn abort(count: ULONG) -> ! { panic!("COM reference count overflow:  AddRef returned {} (0x{:08x}), aborting", count, count) } // panics can't unwind out of `extern "C"` fns
+    abort(count)
+}
+
+static ACTION    : AtomicU8  = AtomicU8::new(OverflowPolicy::DEFAULT.action as u8);
+static THRESHOLD : AtomicU32 = AtomicU32::new(OverflowPolicy::DEFAULT.threshold);
+static DEBUG_REFCOUNTS : AtomicBool = AtomicBool::new(false);
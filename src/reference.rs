@@ -0,0 +1,109 @@
+use crate::{AsIUnknown, Rc};
+use crate::abi::Interface;
+
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr::NonNull;
+
+
+
+/// A borrowed COM interface pointer, for interface parameters.  [AddRef] and [Release] are **not** called.
+///
+/// <code>[Option]&lt;[Ref]&lt;I&gt;&gt;</code> has the same ABI as a nullable `*mut I`, so it can be used directly as
+/// the parameter type of FFI functions and vtable entries - letting Rust-implemented methods accept interface
+/// parameters without `unsafe` code:
+///
+/// ```
+/// # use mcom::abi::*;
+/// # use mcom::{Ref, Rc};
+/// mcom::interface! {
+///     #[uuid(0x6d2a4c10, 0x3e5f, 0x4a7b, 0x8c, 0x9d, 0x0e, 0x1f, 0x2a, 0x3b, 0x4c, 0x5d)]
+///     pub interface IHolder(IHolderVtbl, IHolderImpl): IUnknown(IUnknownVtbl) {
+///         fn Hold(unk: Option<Ref<'_, IUnknown>>);
+///     }
+/// }
+///
+/// struct Holder(std::cell::RefCell<Vec<Rc<IUnknown>>>);
+/// mcom::implement!(Holder: IHolder);
+/// impl IHolderImpl for Holder {
+///     fn Hold(&self, unk: Option<Ref<'_, IUnknown>>) -> Result<(), HRESULT> {
+///         self.0.borrow_mut().push(unk.ok_or(E_POINTER)?.to_owned());
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+/// [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+#[repr(transparent)] pub struct Ref<'a, I: AsIUnknown> {
+    ptr:        NonNull<I>,
+    phantom:    PhantomData<&'a Rc<I>>,
+}
+
+impl<'a, I: AsIUnknown> Ref<'a, I> {
+    /// Borrow `rc` as a [Ref].
+    pub fn new(rc: &'a Rc<I>) -> Self { Self { ptr: unsafe { NonNull::new_unchecked(rc.as_ptr()) }, phantom: PhantomData } }
+
+    /// Borrow a raw COM pointer.  [AddRef] will **not** be called.
+    ///
+    /// ### Safety
+    ///
+    /// * `ptr` may be null, in which case `None` will be returned.  Otherwise:
+    /// * `ptr` must be a "valid" [IUnknown]-derived COM interface pointer, accessible from the current COM apartment.
+    /// * `ptr` must remain valid for `'a`.
+    ///
+    /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[track_caller]
+    pub unsafe fn from_raw_opt(ptr: *mut I) -> Option<Self> {
+        #[cfg(feature = "validate")] crate::validate::pointer("borrowed", ptr);
+        Some(Self { ptr: NonNull::new(ptr)?, phantom: PhantomData })
+    }
+
+    /// Borrow a raw COM pointer.  [AddRef] will **not** be called.
+    ///
+    /// ### Safety
+    ///
+    /// * `ptr` may be null, but this will result in a panic.  Otherwise:
+    /// * `ptr` must be a "valid" [IUnknown]-derived COM interface pointer, accessible from the current COM apartment.
+    /// * `ptr` must remain valid for `'a`.
+    ///
+    /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+    /// [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+    #[track_caller]
+    pub unsafe fn from_raw(ptr: *mut I) -> Self {
+        Self::from_raw_opt(ptr).unwrap()
+    }
+
+    /// Retrieve the raw COM pointer.
+    pub fn as_ptr(self) -> *mut I { self.ptr.as_ptr() }
+
+    /// View as an <code>&amp;[Rc]</code>, for access to [Rc::cast] and friends.
+    pub fn as_rc(&self) -> &Rc<I> { unsafe { &*(self as *const Self).cast() } }
+
+    /// Take a new reference to the COM object, via a single [AddRef].
+    ///
+    /// [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+    #[track_caller] pub fn to_owned(self) -> Rc<I> { self.as_rc().clone() }
+}
+
+impl<I: AsIUnknown> Clone for Ref<'_, I> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<I: AsIUnknown> Copy for Ref<'_, I> {}
+
+/// `Ref<IFoo>(0x...)`:  the [name](crate::names) of the interface, and the interface pointer.
+impl<I: Interface + AsIUnknown> Debug for Ref<'_, I> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { write!(f, "Ref<{}>({:?})", crate::names::Name(&I::uuidof()), self.ptr) }
+}
+
+impl<I: AsIUnknown> Deref for Ref<'_, I> {
+    type Target = I;
+    fn deref(&self) -> &I { unsafe { self.ptr.as_ref() } }
+}
+
+impl<'a, I: AsIUnknown> From<&'a Rc<I>> for Ref<'a, I> {
+    fn from(rc: &'a Rc<I>) -> Self { Self::new(rc) }
+}
@@ -0,0 +1,75 @@
+//! Sanity checks for raw interface pointers adopted or borrowed by [Rc](crate::Rc) and friends.  Requires the `validate` feature.
+//!
+//! Catches pointers that are obviously not what they claim to be - such as the wrong interface, a dangling pointer to
+//! zeroed memory, or something that isn't a COM object at all - where they enter mcom, instead of wherever they happen
+//! to explode later.  Panics with a description of the problem, and the location of the `from_raw*` / `borrow_ptr*` call.
+//!
+//! *   The vtable pointer must not be null.
+//! *   The first three vtable entries ([QueryInterface], [AddRef], [Release]) must not be null.
+//! *   [QueryInterface] for [IUnknown] must succeed.
+//! *   [QueryInterface] for the pointer's own interface must succeed, and return the same pointer (unless that interface is [IUnknown].)
+//!
+//! [QueryInterface]:   https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void)
+//! [AddRef]:           https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-addref
+//! [Release]:          https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-release
+//! [IUnknown]:         https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+
+use crate::AsIUnknown;
+use crate::abi::*;
+use crate::names::Name;
+
+use core::cell::Cell;
+use core::panic::Location;
+use core::ptr::null_mut;
+
+
+
+std::thread_local! {
+    /// Set while validating, so objects that adopt pointers within their own [QueryInterface] aren't validated recursively.
+    ///
+    /// [QueryInterface]:   https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nf-unknwn-iunknown-queryinterface(refiid_void)
+    static VALIDATING : Cell<bool> = const { Cell::new(false) };
+}
+
+struct Validating;
+
+impl Drop for Validating {
+    fn drop(&mut self) { VALIDATING.with(|v| v.set(false)) }
+}
+
+/// Panic if `ptr` (`action`: "adopted" / "borrowed") is non-null, but obviously isn't a valid `I` interface pointer.
+#[track_caller] pub(crate) unsafe fn pointer<I: AsIUnknown>(action: &'static str, ptr: *mut I) {
+    if ptr.is_null() { return }
+    if VALIDATING.with(|v| v.replace(true)) { return }
+    let _validating = Validating;
+
+    let location = Location::caller();
+    let iid = I::iid();
+    let name = iid.as_ref().map(Name);
+    macro_rules! fail { ( $($tt:tt)* ) => {
+        match name {
+            Some(name)  => panic!("mcom::validate: interface pointer {:?} {} as {} at {}:  {}", ptr, action, name, location, format_args!($($tt)*)),
+            None        => panic!("mcom::validate: interface pointer {:?} {} at {}:  {}", ptr, action, location, format_args!($($tt)*)),
+        }
+    }}
+
+    let vtbl = *ptr.cast::<*const usize>();
+    if vtbl.is_null() { fail!("vtable pointer is null") }
+    for (i, method) in ["QueryInterface", "AddRef", "Release"].iter().enumerate() {
+        if *vtbl.add(i) == 0 { fail!("vtable entry {} ({}) is null", i, method) }
+    }
+
+    let unk = &*ptr.cast::<IUnknown>();
+    let mut out = null_mut();
+    let hr = unk.QueryInterface(&IUnknown::uuidof(), &mut out);
+    if !SUCCEEDED(hr) || out.is_null() { fail!("QueryInterface(IUnknown) failed with HRESULT == 0x{:08x}", hr as u32) }
+    (*out.cast::<IUnknown>()).Release();
+
+    let Some(iid) = iid else { return };
+    if IsEqualIID(&iid, &IUnknown::uuidof()) { return } // n.b. IUnknown pointers needn't be the object's canonical IUnknown
+    let mut out = null_mut();
+    let hr = unk.QueryInterface(&iid, &mut out);
+    if !SUCCEEDED(hr) || out.is_null() { fail!("QueryInterface({}) failed with HRESULT == 0x{:08x} - wrong interface?", Name(&iid), hr as u32) }
+    (*out.cast::<IUnknown>()).Release();
+    if out != ptr.cast() { fail!("QueryInterface({}) returned a different pointer, {:?} - wrong interface?", Name(&iid), out) }
+}
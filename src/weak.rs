@@ -0,0 +1,53 @@
+use crate::*;
+use crate::abi::{IWeakReference, IWeakReferenceSource, Interface, SUCCEEDED, E_POINTER};
+use crate::errors::MethodHResult;
+
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+use core::ptr::null_mut;
+
+
+
+/// A \![Send]+\![Sync], [IWeakReference]-based weak reference to a COM object, which doesn't keep the object alive.
+///
+/// Created by [Rc::downgrade], for objects implementing [IWeakReferenceSource] - which includes every [ComObject]
+/// that isn't aggregated.
+///
+/// [IWeakReference]:           https://learn.microsoft.com/en-us/windows/win32/api/weakreference/nn-weakreference-iweakreference
+/// [IWeakReferenceSource]:     https://learn.microsoft.com/en-us/windows/win32/api/weakreference/nn-weakreference-iweakreferencesource
+pub struct Weak<I: Interface + AsIUnknown>(Rc<IWeakReference>, PhantomData<Rc<I>>);
+
+impl<I: Interface + AsIUnknown> Weak<I> {
+    /// Get a weak reference to the object referenced by `rc`.  Fails if the object doesn't implement [IWeakReferenceSource].
+    ///
+    /// [IWeakReferenceSource]:     https://learn.microsoft.com/en-us/windows/win32/api/weakreference/nn-weakreference-iweakreferencesource
+    #[track_caller]
+    pub fn new(rc: &Rc<I>) -> Result<Self, MethodHResult> {
+        let source = unsafe { Rc::<IWeakReferenceSource>::from_out_iid("IUnknown::QueryInterface(IWeakReferenceSource)", |iid, ppv| rc.as_iunknown().QueryInterface(iid, ppv)) }?;
+        let weak = source.GetWeakReference()?.ok_or(MethodHResult::unchecked("IWeakReferenceSource::GetWeakReference", E_POINTER))?;
+        Ok(Self(weak, PhantomData))
+    }
+
+    /// Get a strong reference to the object, if it's still alive.
+    #[track_caller]
+    pub fn upgrade(&self) -> Option<Rc<I>> {
+        let mut ptr = null_mut();
+        let hr = unsafe { self.0.Resolve(&I::uuidof(), &mut ptr) };
+        if !SUCCEEDED(hr) { return None; }
+        unsafe { Rc::from_raw_opt(ptr.cast()) }
+    }
+
+    /// The underlying [IWeakReference].
+    ///
+    /// [IWeakReference]:           https://learn.microsoft.com/en-us/windows/win32/api/weakreference/nn-weakreference-iweakreference
+    pub fn as_weak_reference(&self) -> &Rc<IWeakReference> { &self.0 }
+}
+
+impl<I: Interface + AsIUnknown> Clone for Weak<I> {
+    fn clone(&self) -> Self { Self(self.0.clone(), PhantomData) }
+}
+
+impl<I: Interface + AsIUnknown> TryFrom<&Rc<I>> for Weak<I> {
+    type Error = MethodHResult;
+    fn try_from(src: &Rc<I>) -> Result<Self, Self::Error> { Self::new(src) }
+}
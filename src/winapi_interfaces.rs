@@ -0,0 +1,1284 @@
+//! [ComInterface](crate::ComInterface) implementations (and [From] upcasts between [Rc](crate::Rc)s) for winapi 0.3's
+//! [IUnknown]-derived interfaces, and [RawInterface](crate::RawInterface) implementations for the rest (e.g. `ID3D12FunctionReflection`.)
+//!
+//! Generated from winapi 0.3.9's `RIDL!` declarations.  Interfaces from winapi modules mcom always depends on are
+//! always implemented.  The rest are gated behind `winapi-*` features named after their winapi module (e.g.
+//! `winapi-d3d11` for `winapi::um::d3d11`), which also enable the winapi module, and the features of any parent
+//! interfaces' modules.
+//!
+//! [IUnknown]:     https://learn.microsoft.com/en-us/windows/win32/api/unknwn/nn-unknwn-iunknown
+
+macro_rules! interfaces {
+    ( $( [$($cfg:tt)*] $top:ident::$module:ident { $( $interface:ident : $ptop:ident::$pmodule:ident::$parent:ident ),* $(,)? } )* ) => {$(
+        #[cfg($($cfg)*)] crate::com_interface! {
+            $( unsafe impl ComInterface for ::winapi::$top::$module::$interface { type Parent = ::winapi::$ptop::$pmodule::$parent; } )*
+        }
+        #[cfg($($cfg)*)] crate::rc::upcasts! {
+            $( ::winapi::$top::$module::$interface, )*
+        }
+    )*};
+}
+
+macro_rules! raw_interfaces {
+    ( $( [$($cfg:tt)*] $top:ident::$module:ident { $( $interface:ident ),* $(,)? } )* ) => {$(
+        #[cfg($($cfg)*)] const _ : () = {
+            $( unsafe impl crate::RawInterface for ::winapi::$top::$module::$interface {} )*
+        };
+    )*};
+}
+
+crate::com_interface! {
+    @root unsafe impl ComInterface for ::winapi::um::unknwnbase::IUnknown { type Parent = Self; }
+}
+
+interfaces! {
+    [all()] um::objidlbase {
+        IMarshal:               um::unknwnbase::IUnknown,
+        INoMarshal:             um::unknwnbase::IUnknown,
+        IAgileObject:           um::unknwnbase::IUnknown,
+        IActivationFilter:      um::unknwnbase::IUnknown,
+        IMarshal2:              um::objidlbase::IMarshal,
+        IMalloc:                um::unknwnbase::IUnknown,
+        IStdMarshalInfo:        um::unknwnbase::IUnknown,
+        IExternalConnection:    um::unknwnbase::IUnknown,
+        IMultiQI:               um::unknwnbase::IUnknown,
+        AsyncIMultiQI:          um::unknwnbase::IUnknown,
+        IInternalUnknown:       um::unknwnbase::IUnknown,
+        IEnumUnknown:           um::unknwnbase::IUnknown,
+        IEnumString:            um::unknwnbase::IUnknown,
+        ISequentialStream:      um::unknwnbase::IUnknown,
+        IStream:                um::objidlbase::ISequentialStream,
+        IRpcChannelBuffer:      um::unknwnbase::IUnknown,
+        IRpcChannelBuffer2:     um::objidlbase::IRpcChannelBuffer,
+        IAsyncRpcChannelBuffer: um::objidlbase::IRpcChannelBuffer2,
+        IRpcChannelBuffer3:     um::objidlbase::IRpcChannelBuffer2,
+        IRpcSyntaxNegotiate:    um::unknwnbase::IUnknown,
+        IRpcProxyBuffer:        um::unknwnbase::IUnknown,
+        IRpcStubBuffer:         um::unknwnbase::IUnknown,
+        IPSFactoryBuffer:       um::unknwnbase::IUnknown,
+        IChannelHook:           um::unknwnbase::IUnknown,
+        IClientSecurity:        um::unknwnbase::IUnknown,
+        IServerSecurity:        um::unknwnbase::IUnknown,
+        IRpcOptions:            um::unknwnbase::IUnknown,
+        IGlobalOptions:         um::unknwnbase::IUnknown,
+        ISurrogate:             um::unknwnbase::IUnknown,
+        IGlobalInterfaceTable:  um::unknwnbase::IUnknown,
+        ISynchronize:           um::unknwnbase::IUnknown,
+        ISynchronizeHandle:     um::unknwnbase::IUnknown,
+        ISynchronizeEvent:      um::objidlbase::ISynchronizeHandle,
+        ISynchronizeContainer:  um::unknwnbase::IUnknown,
+        ISynchronizeMutex:      um::objidlbase::ISynchronize,
+        ICancelMethodCalls:     um::unknwnbase::IUnknown,
+        IAsyncManager:          um::unknwnbase::IUnknown,
+        ICallFactory:           um::unknwnbase::IUnknown,
+        IRpcHelper:             um::unknwnbase::IUnknown,
+        IReleaseMarshalBuffers: um::unknwnbase::IUnknown,
+        IWaitMultiple:          um::unknwnbase::IUnknown,
+        IAddrTrackingControl:   um::unknwnbase::IUnknown,
+        IAddrExclusionControl:  um::unknwnbase::IUnknown,
+        IPipeByte:              um::unknwnbase::IUnknown,
+        AsyncIPipeByte:         um::unknwnbase::IUnknown,
+        IPipeLong:              um::unknwnbase::IUnknown,
+        AsyncIPipeLong:         um::unknwnbase::IUnknown,
+        IPipeDouble:            um::unknwnbase::IUnknown,
+        AsyncIPipeDouble:       um::unknwnbase::IUnknown,
+        IEnumContextProps:      um::unknwnbase::IUnknown,
+        IContext:               um::unknwnbase::IUnknown,
+        IObjContext:            um::objidlbase::IContext,
+        IComThreadingInfo:      um::unknwnbase::IUnknown,
+        IProcessInitControl:    um::unknwnbase::IUnknown,
+        IFastRundown:           um::unknwnbase::IUnknown,
+        IMarshalingStream:      um::objidlbase::IStream,
+        IAgileReference:        um::unknwnbase::IUnknown,
+    }
+
+    [all()] um::unknwnbase {
+        AsyncIUnknown: um::unknwnbase::IUnknown,
+        IClassFactory: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-activation"] winrt::activation {
+        IActivationFactory: winrt::inspectable::IInspectable,
+    }
+
+    [feature = "winapi-adhoc"] um::adhoc {
+        IDot11AdHocManager:                   um::unknwnbase::IUnknown,
+        IDot11AdHocManagerNotificationSink:   um::unknwnbase::IUnknown,
+        IEnumDot11AdHocNetworks:              um::unknwnbase::IUnknown,
+        IDot11AdHocNetwork:                   um::unknwnbase::IUnknown,
+        IDot11AdHocNetworkNotificationSink:   um::unknwnbase::IUnknown,
+        IDot11AdHocInterface:                 um::unknwnbase::IUnknown,
+        IEnumDot11AdHocInterfaces:            um::unknwnbase::IUnknown,
+        IEnumDot11AdHocSecuritySettings:      um::unknwnbase::IUnknown,
+        IDot11AdHocSecuritySettings:          um::unknwnbase::IUnknown,
+        IDot11AdHocInterfaceNotificationSink: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-audioclient"] um::audioclient {
+        IAudioClient:        um::unknwnbase::IUnknown,
+        IAudioRenderClient:  um::unknwnbase::IUnknown,
+        IAudioCaptureClient: um::unknwnbase::IUnknown,
+        IAudioClock:         um::unknwnbase::IUnknown,
+        IAudioStreamVolume:  um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-bits"] um::bits {
+        IBackgroundCopyFile:          um::unknwnbase::IUnknown,
+        IEnumBackgroundCopyFiles:     um::unknwnbase::IUnknown,
+        IBackgroundCopyError:         um::unknwnbase::IUnknown,
+        IBackgroundCopyJob:           um::unknwnbase::IUnknown,
+        IEnumBackgroundCopyJobs:      um::unknwnbase::IUnknown,
+        IBackgroundCopyCallback:      um::unknwnbase::IUnknown,
+        AsyncIBackgroundCopyCallback: um::unknwnbase::IUnknown,
+        IBackgroundCopyManager:       um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-bits10_1"] um::bits10_1 {
+        IBackgroundCopyCallback3: um::bits3_0::IBackgroundCopyCallback2,
+        IBackgroundCopyFile6:     um::bits5_0::IBackgroundCopyFile5,
+    }
+
+    [feature = "winapi-bits1_5"] um::bits1_5 {
+        IBackgroundCopyJob2: um::bits::IBackgroundCopyJob,
+    }
+
+    [feature = "winapi-bits2_0"] um::bits2_0 {
+        IBackgroundCopyJob3:  um::bits1_5::IBackgroundCopyJob2,
+        IBackgroundCopyFile2: um::bits::IBackgroundCopyFile,
+    }
+
+    [feature = "winapi-bits2_5"] um::bits2_5 {
+        IBackgroundCopyJobHttpOptions: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-bits3_0"] um::bits3_0 {
+        IBitsPeerCacheRecord:         um::unknwnbase::IUnknown,
+        IEnumBitsPeerCacheRecords:    um::unknwnbase::IUnknown,
+        IBitsPeer:                    um::unknwnbase::IUnknown,
+        IEnumBitsPeers:               um::unknwnbase::IUnknown,
+        IBitsPeerCacheAdministration: um::unknwnbase::IUnknown,
+        IBackgroundCopyJob4:          um::bits2_0::IBackgroundCopyJob3,
+        IBackgroundCopyFile3:         um::bits2_0::IBackgroundCopyFile2,
+        IBackgroundCopyCallback2:     um::bits::IBackgroundCopyCallback,
+    }
+
+    [feature = "winapi-bits4_0"] um::bits4_0 {
+        IBitsTokenOptions:    um::unknwnbase::IUnknown,
+        IBackgroundCopyFile4: um::bits3_0::IBackgroundCopyFile3,
+    }
+
+    [feature = "winapi-bits5_0"] um::bits5_0 {
+        IBackgroundCopyJob5:  um::bits3_0::IBackgroundCopyJob4,
+        IBackgroundCopyFile5: um::bits4_0::IBackgroundCopyFile4,
+    }
+
+    [feature = "winapi-bitscfg"] um::bitscfg {
+        IBITSExtensionSetup:        um::oaidl::IDispatch,
+        IBITSExtensionSetupFactory: um::oaidl::IDispatch,
+    }
+
+    [feature = "winapi-commdlg"] um::commdlg {
+        IPrintDialogCallback: um::unknwnbase::IUnknown,
+        IPrintDialogServices: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-commoncontrols"] um::commoncontrols {
+        IImageList:  um::unknwnbase::IUnknown,
+        IImageList2: um::commoncontrols::IImageList,
+    }
+
+    [feature = "winapi-corsym"] um::corsym {
+        ISymUnmanagedBinder:  um::unknwnbase::IUnknown,
+        ISymUnmanagedBinder2: um::corsym::ISymUnmanagedBinder,
+    }
+
+    [feature = "winapi-d2d1"] um::d2d1 {
+        ID2D1Resource:                 um::unknwnbase::IUnknown,
+        ID2D1Image:                    um::d2d1::ID2D1Resource,
+        ID2D1Bitmap:                   um::d2d1::ID2D1Image,
+        ID2D1GradientStopCollection:   um::d2d1::ID2D1Resource,
+        ID2D1Brush:                    um::d2d1::ID2D1Resource,
+        ID2D1BitmapBrush:              um::d2d1::ID2D1Brush,
+        ID2D1SolidColorBrush:          um::d2d1::ID2D1Brush,
+        ID2D1LinearGradientBrush:      um::d2d1::ID2D1Brush,
+        ID2D1RadialGradientBrush:      um::d2d1::ID2D1Brush,
+        ID2D1StrokeStyle:              um::d2d1::ID2D1Resource,
+        ID2D1Geometry:                 um::d2d1::ID2D1Resource,
+        ID2D1RectangleGeometry:        um::d2d1::ID2D1Geometry,
+        ID2D1RoundedRectangleGeometry: um::d2d1::ID2D1Geometry,
+        ID2D1EllipseGeometry:          um::d2d1::ID2D1Geometry,
+        ID2D1GeometryGroup:            um::d2d1::ID2D1Geometry,
+        ID2D1TransformedGeometry:      um::d2d1::ID2D1Geometry,
+        ID2D1SimplifiedGeometrySink:   um::unknwnbase::IUnknown,
+        ID2D1GeometrySink:             um::d2d1::ID2D1SimplifiedGeometrySink,
+        ID2D1TessellationSink:         um::unknwnbase::IUnknown,
+        ID2D1PathGeometry:             um::d2d1::ID2D1Geometry,
+        ID2D1Mesh:                     um::d2d1::ID2D1Resource,
+        ID2D1Layer:                    um::d2d1::ID2D1Resource,
+        ID2D1DrawingStateBlock:        um::d2d1::ID2D1Resource,
+        ID2D1RenderTarget:             um::d2d1::ID2D1Resource,
+        ID2D1BitmapRenderTarget:       um::d2d1::ID2D1RenderTarget,
+        ID2D1HwndRenderTarget:         um::d2d1::ID2D1RenderTarget,
+        ID2D1GdiInteropRenderTarget:   um::unknwnbase::IUnknown,
+        ID2D1DCRenderTarget:           um::d2d1::ID2D1RenderTarget,
+        ID2D1Factory:                  um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d2d1_1"] um::d2d1_1 {
+        ID2D1GdiMetafileSink:         um::unknwnbase::IUnknown,
+        ID2D1GdiMetafile:             um::d2d1::ID2D1Resource,
+        ID2D1CommandSink:             um::unknwnbase::IUnknown,
+        ID2D1CommandList:             um::d2d1::ID2D1Image,
+        ID2D1PrintControl:            um::unknwnbase::IUnknown,
+        ID2D1ImageBrush:              um::d2d1::ID2D1Brush,
+        ID2D1BitmapBrush1:            um::d2d1::ID2D1BitmapBrush,
+        ID2D1StrokeStyle1:            um::d2d1::ID2D1StrokeStyle,
+        ID2D1PathGeometry1:           um::d2d1::ID2D1PathGeometry,
+        ID2D1Properties:              um::unknwnbase::IUnknown,
+        ID2D1Effect:                  um::d2d1_1::ID2D1Properties,
+        ID2D1Bitmap1:                 um::d2d1::ID2D1Bitmap,
+        ID2D1ColorContext:            um::d2d1::ID2D1Resource,
+        ID2D1GradientStopCollection1: um::d2d1::ID2D1GradientStopCollection,
+        ID2D1DrawingStateBlock1:      um::d2d1::ID2D1DrawingStateBlock,
+        ID2D1DeviceContext:           um::d2d1::ID2D1RenderTarget,
+        ID2D1Device:                  um::d2d1::ID2D1Resource,
+        ID2D1Factory1:                um::d2d1::ID2D1Factory,
+        ID2D1Multithread:             um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d2d1_2"] um::d2d1_2 {
+        ID2D1GeometryRealization: um::d2d1::ID2D1Resource,
+        ID2D1DeviceContext1:      um::d2d1_1::ID2D1DeviceContext,
+        ID2D1Device1:             um::d2d1_1::ID2D1Device,
+        ID2D1Factory2:            um::d2d1_1::ID2D1Factory1,
+        ID2D1CommandSink1:        um::d2d1_1::ID2D1CommandSink,
+    }
+
+    [feature = "winapi-d2d1_3"] um::d2d1_3 {
+        ID2D1InkStyle:               um::d2d1::ID2D1Resource,
+        ID2D1Ink:                    um::d2d1::ID2D1Resource,
+        ID2D1GradientMesh:           um::d2d1::ID2D1Resource,
+        ID2D1ImageSource:            um::d2d1::ID2D1Image,
+        ID2D1ImageSourceFromWic:     um::d2d1_3::ID2D1ImageSource,
+        ID2D1TransformedImageSource: um::d2d1::ID2D1Image,
+        ID2D1LookupTable3D:          um::d2d1::ID2D1Resource,
+        ID2D1DeviceContext2:         um::d2d1_2::ID2D1DeviceContext1,
+        ID2D1Device2:                um::d2d1_2::ID2D1Device1,
+        ID2D1Factory3:               um::d2d1_2::ID2D1Factory2,
+        ID2D1CommandSink2:           um::d2d1_2::ID2D1CommandSink1,
+        ID2D1GdiMetafile1:           um::d2d1_1::ID2D1GdiMetafile,
+        ID2D1GdiMetafileSink1:       um::d2d1_1::ID2D1GdiMetafileSink,
+        ID2D1SpriteBatch:            um::d2d1::ID2D1Resource,
+        ID2D1DeviceContext3:         um::d2d1_3::ID2D1DeviceContext2,
+        ID2D1Device3:                um::d2d1_3::ID2D1Device2,
+        ID2D1Factory4:               um::d2d1_3::ID2D1Factory3,
+        ID2D1CommandSink3:           um::d2d1_3::ID2D1CommandSink2,
+        ID2D1SvgGlyphStyle:          um::d2d1::ID2D1Resource,
+        ID2D1DeviceContext4:         um::d2d1_3::ID2D1DeviceContext3,
+        ID2D1Device4:                um::d2d1_3::ID2D1Device3,
+        ID2D1Factory5:               um::d2d1_3::ID2D1Factory4,
+        ID2D1CommandSink4:           um::d2d1_3::ID2D1CommandSink3,
+        ID2D1ColorContext1:          um::d2d1_1::ID2D1ColorContext,
+        ID2D1DeviceContext5:         um::d2d1_3::ID2D1DeviceContext4,
+        ID2D1Device5:                um::d2d1_3::ID2D1Device4,
+        ID2D1Factory6:               um::d2d1_3::ID2D1Factory5,
+        ID2D1CommandSink5:           um::d2d1_3::ID2D1CommandSink4,
+        ID2D1DeviceContext6:         um::d2d1_3::ID2D1DeviceContext5,
+        ID2D1Device6:                um::d2d1_3::ID2D1Device5,
+        ID2D1Factory7:               um::d2d1_3::ID2D1Factory6,
+    }
+
+    [feature = "winapi-d2d1effectauthor"] um::d2d1effectauthor {
+        ID2D1VertexBuffer:              um::unknwnbase::IUnknown,
+        ID2D1ResourceTexture:           um::unknwnbase::IUnknown,
+        ID2D1RenderInfo:                um::unknwnbase::IUnknown,
+        ID2D1DrawInfo:                  um::d2d1effectauthor::ID2D1RenderInfo,
+        ID2D1ComputeInfo:               um::d2d1effectauthor::ID2D1RenderInfo,
+        ID2D1TransformNode:             um::unknwnbase::IUnknown,
+        ID2D1TransformGraph:            um::unknwnbase::IUnknown,
+        ID2D1Transform:                 um::d2d1effectauthor::ID2D1TransformNode,
+        ID2D1DrawTransform:             um::d2d1effectauthor::ID2D1Transform,
+        ID2D1ComputeTransform:          um::d2d1effectauthor::ID2D1Transform,
+        ID2D1AnalysisTransform:         um::unknwnbase::IUnknown,
+        ID2D1SourceTransform:           um::d2d1effectauthor::ID2D1Transform,
+        ID2D1ConcreteTransform:         um::d2d1effectauthor::ID2D1TransformNode,
+        ID2D1BlendTransform:            um::d2d1effectauthor::ID2D1ConcreteTransform,
+        ID2D1BorderTransform:           um::d2d1effectauthor::ID2D1ConcreteTransform,
+        ID2D1OffsetTransform:           um::d2d1effectauthor::ID2D1TransformNode,
+        ID2D1BoundsAdjustmentTransform: um::d2d1effectauthor::ID2D1TransformNode,
+        ID2D1EffectImpl:                um::unknwnbase::IUnknown,
+        ID2D1EffectContext:             um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d2d1svg"] um::d2d1svg {
+        ID2D1SvgAttribute:       um::d2d1::ID2D1Resource,
+        ID2D1SvgPaint:           um::d2d1svg::ID2D1SvgAttribute,
+        ID2D1SvgStrokeDashArray: um::d2d1svg::ID2D1SvgAttribute,
+        ID2D1SvgPointCollection: um::d2d1svg::ID2D1SvgAttribute,
+        ID2D1SvgPathData:        um::d2d1svg::ID2D1SvgAttribute,
+        ID2D1SvgElement:         um::d2d1::ID2D1Resource,
+        ID2D1SvgDocument:        um::d2d1::ID2D1Resource,
+    }
+
+    [feature = "winapi-d3d10shader"] um::d3d10shader {
+        ID3D10ShaderReflection: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d3d11"] um::d3d11 {
+        ID3D11DeviceChild:              um::unknwnbase::IUnknown,
+        ID3D11DepthStencilState:        um::d3d11::ID3D11DeviceChild,
+        ID3D11BlendState:               um::d3d11::ID3D11DeviceChild,
+        ID3D11RasterizerState:          um::d3d11::ID3D11DeviceChild,
+        ID3D11Resource:                 um::d3d11::ID3D11DeviceChild,
+        ID3D11Buffer:                   um::d3d11::ID3D11Resource,
+        ID3D11Texture1D:                um::d3d11::ID3D11Resource,
+        ID3D11Texture2D:                um::d3d11::ID3D11Resource,
+        ID3D11Texture3D:                um::d3d11::ID3D11Resource,
+        ID3D11View:                     um::d3d11::ID3D11DeviceChild,
+        ID3D11ShaderResourceView:       um::d3d11::ID3D11View,
+        ID3D11RenderTargetView:         um::d3d11::ID3D11View,
+        ID3D11DepthStencilView:         um::d3d11::ID3D11View,
+        ID3D11UnorderedAccessView:      um::d3d11::ID3D11View,
+        ID3D11VertexShader:             um::d3d11::ID3D11DeviceChild,
+        ID3D11HullShader:               um::d3d11::ID3D11DeviceChild,
+        ID3D11DomainShader:             um::d3d11::ID3D11DeviceChild,
+        ID3D11GeometryShader:           um::d3d11::ID3D11DeviceChild,
+        ID3D11PixelShader:              um::d3d11::ID3D11DeviceChild,
+        ID3D11ComputeShader:            um::d3d11::ID3D11DeviceChild,
+        ID3D11InputLayout:              um::d3d11::ID3D11DeviceChild,
+        ID3D11SamplerState:             um::d3d11::ID3D11DeviceChild,
+        ID3D11Asynchronous:             um::d3d11::ID3D11DeviceChild,
+        ID3D11Query:                    um::d3d11::ID3D11Asynchronous,
+        ID3D11Predicate:                um::d3d11::ID3D11Query,
+        ID3D11Counter:                  um::d3d11::ID3D11Asynchronous,
+        ID3D11ClassInstance:            um::d3d11::ID3D11DeviceChild,
+        ID3D11ClassLinkage:             um::d3d11::ID3D11DeviceChild,
+        ID3D11CommandList:              um::d3d11::ID3D11DeviceChild,
+        ID3D11DeviceContext:            um::d3d11::ID3D11DeviceChild,
+        ID3D11VideoDecoder:             um::d3d11::ID3D11DeviceChild,
+        ID3D11VideoProcessorEnumerator: um::d3d11::ID3D11DeviceChild,
+        ID3D11VideoProcessor:           um::d3d11::ID3D11DeviceChild,
+        ID3D11AuthenticatedChannel:     um::d3d11::ID3D11DeviceChild,
+        ID3D11CryptoSession:            um::d3d11::ID3D11DeviceChild,
+        ID3D11VideoDecoderOutputView:   um::d3d11::ID3D11View,
+        ID3D11VideoProcessorInputView:  um::d3d11::ID3D11View,
+        ID3D11VideoProcessorOutputView: um::d3d11::ID3D11View,
+        ID3D11VideoContext:             um::d3d11::ID3D11DeviceChild,
+        ID3D11VideoDevice:              um::unknwnbase::IUnknown,
+        ID3D11Device:                   um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d3d11_1"] um::d3d11_1 {
+        ID3D11BlendState1:               um::d3d11::ID3D11BlendState,
+        ID3D11RasterizerState1:          um::d3d11::ID3D11RasterizerState,
+        ID3DDeviceContextState:          um::d3d11::ID3D11DeviceChild,
+        ID3D11DeviceContext1:            um::d3d11::ID3D11DeviceContext,
+        ID3D11VideoContext1:             um::d3d11::ID3D11VideoContext,
+        ID3D11VideoDevice1:              um::d3d11::ID3D11VideoDevice,
+        ID3D11VideoProcessorEnumerator1: um::d3d11::ID3D11VideoProcessorEnumerator,
+        ID3D11Device1:                   um::d3d11::ID3D11Device,
+        ID3DUserDefinedAnnotation:       um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d3d11_2"] um::d3d11_2 {
+        ID3D11DeviceContext2: um::d3d11_1::ID3D11DeviceContext1,
+        ID3D11Device2:        um::d3d11_1::ID3D11Device1,
+    }
+
+    [feature = "winapi-d3d11on12"] um::d3d11on12 {
+        ID3D11On12Device: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d3d11sdklayers"] um::d3d11sdklayers {
+        ID3D11Debug:                     um::unknwnbase::IUnknown,
+        ID3D11SwitchToRef:               um::unknwnbase::IUnknown,
+        ID3D11TracingDevice:             um::unknwnbase::IUnknown,
+        ID3D11RefTrackingOptions:        um::unknwnbase::IUnknown,
+        ID3D11RefDefaultTrackingOptions: um::unknwnbase::IUnknown,
+        ID3D11InfoQueue:                 um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d3d11shader"] um::d3d11shader {
+        ID3D11ShaderReflection:     um::unknwnbase::IUnknown,
+        ID3D11LibraryReflection:    um::unknwnbase::IUnknown,
+        ID3D11Module:               um::unknwnbase::IUnknown,
+        ID3D11ModuleInstance:       um::unknwnbase::IUnknown,
+        ID3D11Linker:               um::unknwnbase::IUnknown,
+        ID3D11LinkingNode:          um::unknwnbase::IUnknown,
+        ID3D11FunctionLinkingGraph: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d3d12"] um::d3d12 {
+        ID3D12RootSignature:                      um::d3d12::ID3D12DeviceChild,
+        ID3D12RootSignatureDeserializer:          um::unknwnbase::IUnknown,
+        ID3D12VersionedRootSignatureDeserializer: um::unknwnbase::IUnknown,
+        ID3D12Object:                             um::unknwnbase::IUnknown,
+        ID3D12DeviceChild:                        um::d3d12::ID3D12Object,
+        ID3D12Pageable:                           um::d3d12::ID3D12DeviceChild,
+        ID3D12Heap:                               um::d3d12::ID3D12Pageable,
+        ID3D12Resource:                           um::d3d12::ID3D12Pageable,
+        ID3D12CommandAllocator:                   um::d3d12::ID3D12Pageable,
+        ID3D12Fence:                              um::d3d12::ID3D12Pageable,
+        ID3D12PipelineState:                      um::d3d12::ID3D12Pageable,
+        ID3D12DescriptorHeap:                     um::d3d12::ID3D12Pageable,
+        ID3D12QueryHeap:                          um::d3d12::ID3D12Pageable,
+        ID3D12CommandSignature:                   um::d3d12::ID3D12Pageable,
+        ID3D12CommandList:                        um::d3d12::ID3D12DeviceChild,
+        ID3D12GraphicsCommandList:                um::d3d12::ID3D12CommandList,
+        ID3D12GraphicsCommandList1:               um::d3d12::ID3D12GraphicsCommandList,
+        ID3D12CommandQueue:                       um::d3d12::ID3D12Pageable,
+        ID3D12Device:                             um::d3d12::ID3D12Object,
+        ID3D12PipelineLibrary:                    um::d3d12::ID3D12DeviceChild,
+        ID3D12PipelineLibrary1:                   um::d3d12::ID3D12PipelineLibrary,
+        ID3D12Device1:                            um::d3d12::ID3D12Device,
+        ID3D12Device2:                            um::d3d12::ID3D12Device1,
+        ID3D12Tools:                              um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d3d12sdklayers"] um::d3d12sdklayers {
+        ID3D12Debug:             um::unknwnbase::IUnknown,
+        ID3D12Debug1:            um::unknwnbase::IUnknown,
+        ID3D12Debug2:            um::unknwnbase::IUnknown,
+        ID3D12DebugDevice1:      um::unknwnbase::IUnknown,
+        ID3D12DebugDevice:       um::unknwnbase::IUnknown,
+        ID3D12DebugCommandQueue: um::unknwnbase::IUnknown,
+        ID3D12DebugCommandList1: um::unknwnbase::IUnknown,
+        ID3D12DebugCommandList:  um::unknwnbase::IUnknown,
+        ID3D12InfoQueue:         um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d3d12shader"] um::d3d12shader {
+        ID3D12LibraryReflection: um::unknwnbase::IUnknown,
+        ID3D12ShaderReflection:  um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d3d9"] shared::d3d9 {
+        IDirect3D9:                     um::unknwnbase::IUnknown,
+        IDirect3DDevice9:               um::unknwnbase::IUnknown,
+        IDirect3DStateBlock9:           um::unknwnbase::IUnknown,
+        IDirect3DSwapChain9:            um::unknwnbase::IUnknown,
+        IDirect3DResource9:             um::unknwnbase::IUnknown,
+        IDirect3DVertexDeclaration9:    um::unknwnbase::IUnknown,
+        IDirect3DVertexShader9:         um::unknwnbase::IUnknown,
+        IDirect3DPixelShader9:          um::unknwnbase::IUnknown,
+        IDirect3DBaseTexture9:          shared::d3d9::IDirect3DResource9,
+        IDirect3DTexture9:              shared::d3d9::IDirect3DBaseTexture9,
+        IDirect3DVolumeTexture9:        shared::d3d9::IDirect3DBaseTexture9,
+        IDirect3DCubeTexture9:          shared::d3d9::IDirect3DBaseTexture9,
+        IDirect3DVertexBuffer9:         shared::d3d9::IDirect3DResource9,
+        IDirect3DIndexBuffer9:          shared::d3d9::IDirect3DResource9,
+        IDirect3DSurface9:              shared::d3d9::IDirect3DResource9,
+        IDirect3DVolume9:               um::unknwnbase::IUnknown,
+        IDirect3DQuery9:                um::unknwnbase::IUnknown,
+        IDirect3D9Ex:                   shared::d3d9::IDirect3D9,
+        IDirect3DDevice9Ex:             shared::d3d9::IDirect3DDevice9,
+        IDirect3DSwapChain9Ex:          shared::d3d9::IDirect3DSwapChain9,
+        IDirect3D9ExOverlayExtension:   um::unknwnbase::IUnknown,
+        IDirect3DDevice9Video:          um::unknwnbase::IUnknown,
+        IDirect3DAuthenticatedChannel9: um::unknwnbase::IUnknown,
+        IDirect3DCryptoSession9:        um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-d3dcommon"] um::d3dcommon {
+        ID3D10Blob: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-dcomp"] um::dcomp {
+        IDCompositionDevice:                    um::unknwnbase::IUnknown,
+        IDCompositionTarget:                    um::unknwnbase::IUnknown,
+        IDCompositionVisual:                    um::unknwnbase::IUnknown,
+        IDCompositionEffect:                    um::unknwnbase::IUnknown,
+        IDCompositionTransform3D:               um::dcomp::IDCompositionEffect,
+        IDCompositionTransform:                 um::dcomp::IDCompositionTransform3D,
+        IDCompositionTranslateTransform:        um::dcomp::IDCompositionTransform,
+        IDCompositionScaleTransform:            um::dcomp::IDCompositionTransform,
+        IDCompositionRotateTransform:           um::dcomp::IDCompositionTransform,
+        IDCompositionSkewTransform:             um::dcomp::IDCompositionTransform,
+        IDCompositionMatrixTransform:           um::dcomp::IDCompositionTransform,
+        IDCompositionEffectGroup:               um::dcomp::IDCompositionEffect,
+        IDCompositionTranslateTransform3D:      um::dcomp::IDCompositionTransform3D,
+        IDCompositionScaleTransform3D:          um::dcomp::IDCompositionTransform3D,
+        IDCompositionRotateTransform3D:         um::dcomp::IDCompositionTransform3D,
+        IDCompositionMatrixTransform3D:         um::dcomp::IDCompositionTransform3D,
+        IDCompositionClip:                      um::unknwnbase::IUnknown,
+        IDCompositionRectangleClip:             um::dcomp::IDCompositionClip,
+        IDCompositionSurface:                   um::unknwnbase::IUnknown,
+        IDCompositionVirtualSurface:            um::dcomp::IDCompositionSurface,
+        IDCompositionDevice2:                   um::unknwnbase::IUnknown,
+        IDCompositionDesktopDevice:             um::dcomp::IDCompositionDevice2,
+        IDCompositionDeviceDebug:               um::unknwnbase::IUnknown,
+        IDCompositionSurfaceFactory:            um::unknwnbase::IUnknown,
+        IDCompositionVisual2:                   um::dcomp::IDCompositionVisual,
+        IDCompositionVisualDebug:               um::dcomp::IDCompositionVisual2,
+        IDCompositionVisual3:                   um::dcomp::IDCompositionVisualDebug,
+        IDCompositionDevice3:                   um::dcomp::IDCompositionDevice2,
+        IDCompositionFilterEffect:              um::dcomp::IDCompositionEffect,
+        IDCompositionGaussianBlurEffect:        um::dcomp::IDCompositionFilterEffect,
+        IDCompositionBrightnessEffect:          um::dcomp::IDCompositionFilterEffect,
+        IDCompositionColorMatrixEffect:         um::dcomp::IDCompositionFilterEffect,
+        IDCompositionShadowEffect:              um::dcomp::IDCompositionFilterEffect,
+        IDCompositionHueRotationEffect:         um::dcomp::IDCompositionFilterEffect,
+        IDCompositionSaturationEffect:          um::dcomp::IDCompositionFilterEffect,
+        IDCompositionTurbulenceEffect:          um::dcomp::IDCompositionFilterEffect,
+        IDCompositionLinearTransferEffect:      um::dcomp::IDCompositionFilterEffect,
+        IDCompositionTableTransferEffect:       um::dcomp::IDCompositionFilterEffect,
+        IDCompositionCompositeEffect:           um::dcomp::IDCompositionFilterEffect,
+        IDCompositionBlendEffect:               um::dcomp::IDCompositionFilterEffect,
+        IDCompositionArithmeticCompositeEffect: um::dcomp::IDCompositionFilterEffect,
+        IDCompositionAffineTransform2DEffect:   um::dcomp::IDCompositionFilterEffect,
+    }
+
+    [feature = "winapi-dcompanimation"] um::dcompanimation {
+        IDCompositionAnimation: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-devicetopology"] um::devicetopology {
+        IKsControl:              um::unknwnbase::IUnknown,
+        IPerChannelDbLevel:      um::unknwnbase::IUnknown,
+        IAudioVolumeLevel:       um::devicetopology::IPerChannelDbLevel,
+        IAudioChannelConfig:     um::unknwnbase::IUnknown,
+        IAudioLoudness:          um::unknwnbase::IUnknown,
+        IAudioInputSelector:     um::unknwnbase::IUnknown,
+        IAudioOutputSelector:    um::unknwnbase::IUnknown,
+        IAudioMute:              um::unknwnbase::IUnknown,
+        IAudioBass:              um::devicetopology::IPerChannelDbLevel,
+        IAudioMidrange:          um::devicetopology::IPerChannelDbLevel,
+        IAudioTreble:            um::devicetopology::IPerChannelDbLevel,
+        IAudioAutoGainControl:   um::unknwnbase::IUnknown,
+        IAudioPeakMeter:         um::unknwnbase::IUnknown,
+        IDeviceSpecificProperty: um::unknwnbase::IUnknown,
+        IKsFormatSupport:        um::unknwnbase::IUnknown,
+        IKsJackDescription:      um::unknwnbase::IUnknown,
+        IKsJackDescription2:     um::unknwnbase::IUnknown,
+        IKsJackSinkInformation:  um::unknwnbase::IUnknown,
+        IKsJackContainerId:      um::unknwnbase::IUnknown,
+        IPartsList:              um::unknwnbase::IUnknown,
+        IPart:                   um::unknwnbase::IUnknown,
+        IConnector:              um::unknwnbase::IUnknown,
+        ISubunit:                um::unknwnbase::IUnknown,
+        IControlInterface:       um::unknwnbase::IUnknown,
+        IControlChangeNotify:    um::unknwnbase::IUnknown,
+        IDeviceTopology:         um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-dispex"] um::dispex {
+        IDispatchEx:            um::oaidl::IDispatch,
+        IDispError:             um::unknwnbase::IUnknown,
+        IVariantChangeType:     um::unknwnbase::IUnknown,
+        IObjectIdentity:        um::unknwnbase::IUnknown,
+        ICanHandleException:    um::unknwnbase::IUnknown,
+        IProvideRuntimeContext: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-docobj"] um::docobj {
+        IOleCommandTarget: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-documenttarget"] um::documenttarget {
+        IPrintDocumentPackageTarget: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-dsound"] um::dsound {
+        IDirectSoundBuffer: um::unknwnbase::IUnknown,
+        IDirectSound:       um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-dwrite"] um::dwrite {
+        IDWriteFontFileLoader:       um::unknwnbase::IUnknown,
+        IDWriteLocalFontFileLoader:  um::dwrite::IDWriteFontFileLoader,
+        IDWriteFontFileStream:       um::unknwnbase::IUnknown,
+        IDWriteFontFile:             um::unknwnbase::IUnknown,
+        IDWriteRenderingParams:      um::unknwnbase::IUnknown,
+        IDWriteFontFace:             um::unknwnbase::IUnknown,
+        IDWriteFontFace1:            um::dwrite::IDWriteFontFace,
+        IDWriteFontCollectionLoader: um::unknwnbase::IUnknown,
+        IDWriteFontFileEnumerator:   um::unknwnbase::IUnknown,
+        IDWriteLocalizedStrings:     um::unknwnbase::IUnknown,
+        IDWriteFontCollection:       um::unknwnbase::IUnknown,
+        IDWriteFontList:             um::unknwnbase::IUnknown,
+        IDWriteFontFamily:           um::dwrite::IDWriteFontList,
+        IDWriteFont:                 um::unknwnbase::IUnknown,
+        IDWriteTextFormat:           um::unknwnbase::IUnknown,
+        IDWriteTypography:           um::unknwnbase::IUnknown,
+        IDWriteNumberSubstitution:   um::unknwnbase::IUnknown,
+        IDWriteTextAnalysisSource:   um::unknwnbase::IUnknown,
+        IDWriteTextAnalysisSink:     um::unknwnbase::IUnknown,
+        IDWriteTextAnalyzer:         um::unknwnbase::IUnknown,
+        IDWriteInlineObject:         um::unknwnbase::IUnknown,
+        IDWritePixelSnapping:        um::unknwnbase::IUnknown,
+        IDWriteTextRenderer:         um::dwrite::IDWritePixelSnapping,
+        IDWriteTextLayout:           um::dwrite::IDWriteTextFormat,
+        IDWriteBitmapRenderTarget:   um::unknwnbase::IUnknown,
+        IDWriteGdiInterop:           um::unknwnbase::IUnknown,
+        IDWriteGlyphRunAnalysis:     um::unknwnbase::IUnknown,
+        IDWriteFactory:              um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-dwrite_1"] um::dwrite_1 {
+        IDWriteFactory1:            um::dwrite::IDWriteFactory,
+        IDWriteFontFace1:           um::dwrite::IDWriteFontFace,
+        IDWriteFont1:               um::dwrite::IDWriteFont,
+        IDWriteRenderingParams1:    um::dwrite::IDWriteRenderingParams,
+        IDWriteTextAnalyzer1:       um::dwrite::IDWriteTextAnalyzer,
+        IDWriteTextAnalysisSource1: um::dwrite::IDWriteTextAnalysisSource,
+        IDWriteTextAnalysisSink1:   um::dwrite::IDWriteTextAnalysisSink,
+        IDWriteTextLayout1:         um::dwrite::IDWriteTextLayout,
+        IDWriteBitmapRenderTarget1: um::dwrite::IDWriteBitmapRenderTarget,
+    }
+
+    [feature = "winapi-dwrite_2"] um::dwrite_2 {
+        IDWriteTextRenderer1:           um::dwrite::IDWriteTextRenderer,
+        IDWriteTextFormat1:             um::dwrite::IDWriteTextFormat,
+        IDWriteTextLayout2:             um::dwrite_1::IDWriteTextLayout1,
+        IDWriteTextAnalyzer2:           um::dwrite_1::IDWriteTextAnalyzer1,
+        IDWriteFontFallback:            um::unknwnbase::IUnknown,
+        IDWriteFontFallbackBuilder:     um::unknwnbase::IUnknown,
+        IDWriteFont2:                   um::dwrite_1::IDWriteFont1,
+        IDWriteFontFace2:               um::dwrite_1::IDWriteFontFace1,
+        IDWriteColorGlyphRunEnumerator: um::unknwnbase::IUnknown,
+        IDWriteRenderingParams2:        um::dwrite_1::IDWriteRenderingParams1,
+        IDWriteFactory2:                um::dwrite_1::IDWriteFactory1,
+    }
+
+    [feature = "winapi-dwrite_3"] um::dwrite_3 {
+        IDWriteRenderingParams3:     um::dwrite_2::IDWriteRenderingParams2,
+        IDWriteFactory3:             um::dwrite_2::IDWriteFactory2,
+        IDWriteFontSet:              um::unknwnbase::IUnknown,
+        IDWriteFontSetBuilder:       um::unknwnbase::IUnknown,
+        IDWriteFontCollection1:      um::dwrite::IDWriteFontCollection,
+        IDWriteFontFamily1:          um::dwrite::IDWriteFontFamily,
+        IDWriteFontList1:            um::dwrite::IDWriteFontList,
+        IDWriteFontFaceReference:    um::unknwnbase::IUnknown,
+        IDWriteFont3:                um::dwrite_2::IDWriteFont2,
+        IDWriteFontFace3:            um::dwrite_2::IDWriteFontFace2,
+        IDWriteStringList:           um::unknwnbase::IUnknown,
+        IDWriteFontDownloadListener: um::unknwnbase::IUnknown,
+        IDWriteFontDownloadQueue:    um::unknwnbase::IUnknown,
+        IDWriteGdiInterop1:          um::dwrite::IDWriteGdiInterop,
+        IDWriteTextFormat2:          um::dwrite_2::IDWriteTextFormat1,
+        IDWriteTextLayout3:          um::dwrite_2::IDWriteTextLayout2,
+        IDWriteFontFace4:            um::dwrite_3::IDWriteFontFace3,
+        IDWriteFontFace5:            um::dwrite_3::IDWriteFontFace4,
+        IDWriteFontFaceReference1:   um::dwrite_3::IDWriteFontFaceReference,
+        IDWriteFontResource:         um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-dxgi"] shared::dxgi {
+        IDXGIObject:          um::unknwnbase::IUnknown,
+        IDXGIDeviceSubObject: shared::dxgi::IDXGIObject,
+        IDXGIResource:        shared::dxgi::IDXGIDeviceSubObject,
+        IDXGIKeyedMutex:      shared::dxgi::IDXGIDeviceSubObject,
+        IDXGISurface:         shared::dxgi::IDXGIDeviceSubObject,
+        IDXGISurface1:        shared::dxgi::IDXGISurface,
+        IDXGIAdapter:         shared::dxgi::IDXGIObject,
+        IDXGIOutput:          shared::dxgi::IDXGIObject,
+        IDXGISwapChain:       shared::dxgi::IDXGIDeviceSubObject,
+        IDXGIFactory:         shared::dxgi::IDXGIObject,
+        IDXGIDevice:          shared::dxgi::IDXGIObject,
+        IDXGIFactory1:        shared::dxgi::IDXGIFactory,
+        IDXGIAdapter1:        shared::dxgi::IDXGIAdapter,
+        IDXGIDevice1:         shared::dxgi::IDXGIDevice,
+    }
+
+    [feature = "winapi-dxgi1_2"] shared::dxgi1_2 {
+        IDXGIAdapter2:          shared::dxgi::IDXGIAdapter1,
+        IDXGIDevice2:           shared::dxgi::IDXGIDevice1,
+        IDXGIDisplayControl:    um::unknwnbase::IUnknown,
+        IDXGIFactory2:          shared::dxgi::IDXGIFactory1,
+        IDXGIOutput1:           shared::dxgi::IDXGIOutput,
+        IDXGIOutputDuplication: shared::dxgi::IDXGIObject,
+        IDXGIResource1:         shared::dxgi::IDXGIResource,
+        IDXGISurface2:          shared::dxgi::IDXGISurface1,
+        IDXGISwapChain1:        shared::dxgi::IDXGISwapChain,
+    }
+
+    [feature = "winapi-dxgi1_3"] shared::dxgi1_3 {
+        IDXGIDecodeSwapChain: um::unknwnbase::IUnknown,
+        IDXGIDevice3:         shared::dxgi1_2::IDXGIDevice2,
+        IDXGIFactory3:        shared::dxgi1_2::IDXGIFactory2,
+        IDXGIFactoryMedia:    um::unknwnbase::IUnknown,
+        IDXGIOutput2:         shared::dxgi1_2::IDXGIOutput1,
+        IDXGIOutput3:         shared::dxgi1_3::IDXGIOutput2,
+        IDXGISwapChain2:      shared::dxgi1_2::IDXGISwapChain1,
+        IDXGISwapChainMedia:  um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-dxgi1_4"] shared::dxgi1_4 {
+        IDXGIAdapter3:   shared::dxgi1_2::IDXGIAdapter2,
+        IDXGIFactory4:   shared::dxgi1_3::IDXGIFactory3,
+        IDXGIOutput4:    shared::dxgi1_3::IDXGIOutput3,
+        IDXGISwapChain3: shared::dxgi1_3::IDXGISwapChain2,
+    }
+
+    [feature = "winapi-dxgi1_5"] shared::dxgi1_5 {
+        IDXGIOutput5:    shared::dxgi1_4::IDXGIOutput4,
+        IDXGISwapChain4: shared::dxgi1_4::IDXGISwapChain3,
+        IDXGIDevice4:    shared::dxgi1_3::IDXGIDevice3,
+        IDXGIFactory5:   shared::dxgi1_4::IDXGIFactory4,
+    }
+
+    [feature = "winapi-dxgi1_6"] shared::dxgi1_6 {
+        IDXGIAdapter4: shared::dxgi1_4::IDXGIAdapter3,
+        IDXGIOutput6:  shared::dxgi1_5::IDXGIOutput5,
+        IDXGIFactory6: shared::dxgi1_5::IDXGIFactory5,
+    }
+
+    [feature = "winapi-dxgidebug"] um::dxgidebug {
+        IDXGIInfoQueue: um::unknwnbase::IUnknown,
+        IDXGIDebug:     um::unknwnbase::IUnknown,
+        IDXGIDebug1:    um::dxgidebug::IDXGIDebug,
+    }
+
+    [feature = "winapi-dxva2api"] um::dxva2api {
+        IDirect3DDeviceManager9:          um::unknwnbase::IUnknown,
+        IDirectXVideoAccelerationService: um::unknwnbase::IUnknown,
+        IDirectXVideoDecoderService:      um::dxva2api::IDirectXVideoAccelerationService,
+        IDirectXVideoProcessorService:    um::dxva2api::IDirectXVideoAccelerationService,
+        IDirectXVideoDecoder:             um::unknwnbase::IUnknown,
+        IDirectXVideoProcessor:           um::unknwnbase::IUnknown,
+        IDirectXVideoMemoryConfiguration: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-dxvahd"] um::dxvahd {
+        IDXVAHD_Device:         um::unknwnbase::IUnknown,
+        IDXVAHD_VideoProcessor: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-endpointvolume"] um::endpointvolume {
+        IAudioEndpointVolumeCallback: um::unknwnbase::IUnknown,
+        IAudioEndpointVolume:         um::unknwnbase::IUnknown,
+        IAudioEndpointVolumeEx:       um::endpointvolume::IAudioEndpointVolume,
+        IAudioMeterInformation:       um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-exdisp"] um::exdisp {
+        IWebBrowser:    um::oaidl::IDispatch,
+        IWebBrowserApp: um::exdisp::IWebBrowser,
+        IWebBrowser2:   um::exdisp::IWebBrowserApp,
+    }
+
+    [feature = "winapi-inspectable"] winrt::inspectable {
+        IInspectable: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-mmdeviceapi"] um::mmdeviceapi {
+        IMMNotificationClient:                    um::unknwnbase::IUnknown,
+        IMMDevice:                                um::unknwnbase::IUnknown,
+        IMMDeviceCollection:                      um::unknwnbase::IUnknown,
+        IMMEndpoint:                              um::unknwnbase::IUnknown,
+        IMMDeviceEnumerator:                      um::unknwnbase::IUnknown,
+        IMMDeviceActivator:                       um::unknwnbase::IUnknown,
+        IActivateAudioInterfaceCompletionHandler: um::unknwnbase::IUnknown,
+        IActivateAudioInterfaceAsyncOperation:    um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-oaidl"] um::oaidl {
+        ICreateTypeInfo:  um::unknwnbase::IUnknown,
+        IDispatch:        um::unknwnbase::IUnknown,
+        IRecordInfo:      um::unknwnbase::IUnknown,
+        ITypeComp:        um::unknwnbase::IUnknown,
+        ITypeLib:         um::unknwnbase::IUnknown,
+        ITypeInfo:        um::unknwnbase::IUnknown,
+        IErrorInfo:       um::unknwnbase::IUnknown,
+        ICreateErrorInfo: um::unknwnbase::IUnknown,
+        IErrorLog:        um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-objidl"] um::objidl {
+        IBindCtx:            um::unknwnbase::IUnknown,
+        IEnumMoniker:        um::unknwnbase::IUnknown,
+        IRunningObjectTable: um::unknwnbase::IUnknown,
+        IPersist:            um::unknwnbase::IUnknown,
+        IPersistStream:      um::objidl::IPersist,
+        IMoniker:            um::objidl::IPersistStream,
+        IEnumSTATSTG:        um::unknwnbase::IUnknown,
+        IStorage:            um::unknwnbase::IUnknown,
+        IPersistFile:        um::objidl::IPersist,
+        IEnumFORMATETC:      um::unknwnbase::IUnknown,
+        IEnumSTATDATA:       um::unknwnbase::IUnknown,
+        IAdviseSink:         um::unknwnbase::IUnknown,
+        IDataObject:         um::unknwnbase::IUnknown,
+        IApartmentShutdown:  um::unknwnbase::IUnknown,
+        IMarshal:            um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-ocidl"] um::ocidl {
+        IPropertyBag2: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-oleidl"] um::oleidl {
+        IDropTarget: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-opmapi"] um::opmapi {
+        IOPMVideoOutput: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-portabledeviceapi"] um::portabledeviceapi {
+        IPortableDeviceManager:       um::unknwnbase::IUnknown,
+        IPortableDevice:              um::unknwnbase::IUnknown,
+        IPortableDeviceContent:       um::unknwnbase::IUnknown,
+        IPortableDeviceContent2:      um::portabledeviceapi::IPortableDeviceContent,
+        IEnumPortableDeviceObjectIDs: um::unknwnbase::IUnknown,
+        IPortableDeviceProperties:    um::unknwnbase::IUnknown,
+        IPortableDeviceResources:     um::unknwnbase::IUnknown,
+        IPortableDeviceCapabilities:  um::unknwnbase::IUnknown,
+        IPortableDeviceEventCallback: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-portabledevicetypes"] um::portabledevicetypes {
+        IPortableDeviceValues:                um::unknwnbase::IUnknown,
+        IPortableDeviceKeyCollection:         um::unknwnbase::IUnknown,
+        IPortableDevicePropVariantCollection: um::unknwnbase::IUnknown,
+        IPortableDeviceValuesCollection:      um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-propidl"] um::propidl {
+        IPropertyStorage:    um::unknwnbase::IUnknown,
+        IPropertySetStorage: um::unknwnbase::IUnknown,
+        IEnumSTATPROPSTG:    um::unknwnbase::IUnknown,
+        IEnumSTATPROPSETSTG: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-propsys"] um::propsys {
+        IPropertyStore: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-restrictederrorinfo"] um::restrictederrorinfo {
+        IRestrictedErrorInfo: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-sapi"] um::sapi {
+        ISpRecoCategory: um::unknwnbase::IUnknown,
+        ISpRecognizer3:  um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-sapi51"] um::sapi51 {
+        ISpNotifySource:                    um::unknwnbase::IUnknown,
+        ISpNotifySink:                      um::unknwnbase::IUnknown,
+        ISpNotifyTranslator:                um::sapi51::ISpNotifySink,
+        ISpDataKey:                         um::unknwnbase::IUnknown,
+        ISpRegDataKey:                      um::sapi51::ISpDataKey,
+        ISpObjectTokenCategory:             um::sapi51::ISpDataKey,
+        ISpObjectToken:                     um::sapi51::ISpDataKey,
+        ISpObjectTokenInit:                 um::sapi51::ISpObjectToken,
+        IEnumSpObjectTokens:                um::unknwnbase::IUnknown,
+        ISpObjectWithToken:                 um::unknwnbase::IUnknown,
+        ISpResourceManager:                 um::servprov::IServiceProvider,
+        ISpEventSource:                     um::sapi51::ISpNotifySource,
+        ISpEventSink:                       um::unknwnbase::IUnknown,
+        ISpStreamFormat:                    um::objidlbase::IStream,
+        ISpStream:                          um::sapi51::ISpStreamFormat,
+        ISpStreamFormatConverter:           um::sapi51::ISpStreamFormat,
+        ISpAudio:                           um::sapi51::ISpStreamFormat,
+        ISpMMSysAudio:                      um::sapi51::ISpAudio,
+        ISpTranscript:                      um::unknwnbase::IUnknown,
+        ISpLexicon:                         um::unknwnbase::IUnknown,
+        ISpContainerLexicon:                um::sapi51::ISpLexicon,
+        ISpPhoneConverter:                  um::sapi51::ISpObjectWithToken,
+        ISpVoice:                           um::sapi51::ISpEventSource,
+        ISpPhrase:                          um::unknwnbase::IUnknown,
+        ISpPhraseAlt:                       um::sapi51::ISpPhrase,
+        ISpRecoResult:                      um::sapi51::ISpPhrase,
+        ISpGrammarBuilder:                  um::unknwnbase::IUnknown,
+        ISpRecoGrammar:                     um::sapi51::ISpGrammarBuilder,
+        ISpRecoContext:                     um::sapi51::ISpEventSource,
+        ISpProperties:                      um::unknwnbase::IUnknown,
+        ISpRecognizer:                      um::sapi51::ISpProperties,
+        ISpeechDataKey:                     um::oaidl::IDispatch,
+        ISpeechObjectToken:                 um::oaidl::IDispatch,
+        ISpeechObjectTokens:                um::oaidl::IDispatch,
+        ISpeechObjectTokenCategory:         um::oaidl::IDispatch,
+        ISpeechAudioBufferInfo:             um::oaidl::IDispatch,
+        ISpeechAudioStatus:                 um::oaidl::IDispatch,
+        ISpeechAudioFormat:                 um::oaidl::IDispatch,
+        ISpeechWaveFormatEx:                um::oaidl::IDispatch,
+        ISpeechBaseStream:                  um::oaidl::IDispatch,
+        ISpeechFileStream:                  um::sapi51::ISpeechBaseStream,
+        ISpeechMemoryStream:                um::sapi51::ISpeechBaseStream,
+        ISpeechCustomStream:                um::sapi51::ISpeechBaseStream,
+        ISpeechAudio:                       um::sapi51::ISpeechBaseStream,
+        ISpeechMMSysAudio:                  um::sapi51::ISpeechAudio,
+        ISpeechVoice:                       um::oaidl::IDispatch,
+        ISpeechVoiceStatus:                 um::oaidl::IDispatch,
+        _ISpeechVoiceEvents:                um::oaidl::IDispatch,
+        ISpeechRecognizer:                  um::oaidl::IDispatch,
+        ISpeechRecognizerStatus:            um::oaidl::IDispatch,
+        ISpeechRecoContext:                 um::oaidl::IDispatch,
+        ISpeechRecoGrammar:                 um::oaidl::IDispatch,
+        _ISpeechRecoContextEvents:          um::oaidl::IDispatch,
+        ISpeechGrammarRule:                 um::oaidl::IDispatch,
+        ISpeechGrammarRules:                um::oaidl::IDispatch,
+        ISpeechGrammarRuleState:            um::oaidl::IDispatch,
+        ISpeechGrammarRuleStateTransition:  um::oaidl::IDispatch,
+        ISpeechGrammarRuleStateTransitions: um::oaidl::IDispatch,
+        ISpeechTextSelectionInformation:    um::oaidl::IDispatch,
+        ISpeechRecoResult:                  um::oaidl::IDispatch,
+        ISpeechRecoResultTimes:             um::oaidl::IDispatch,
+        ISpeechPhraseAlternate:             um::oaidl::IDispatch,
+        ISpeechPhraseAlternates:            um::oaidl::IDispatch,
+        ISpeechPhraseInfo:                  um::oaidl::IDispatch,
+        ISpeechPhraseElement:               um::oaidl::IDispatch,
+        ISpeechPhraseElements:              um::oaidl::IDispatch,
+        ISpeechPhraseReplacement:           um::oaidl::IDispatch,
+        ISpeechPhraseReplacements:          um::oaidl::IDispatch,
+        ISpeechPhraseProperty:              um::oaidl::IDispatch,
+        ISpeechPhraseProperties:            um::oaidl::IDispatch,
+        ISpeechPhraseRule:                  um::oaidl::IDispatch,
+        ISpeechPhraseRules:                 um::oaidl::IDispatch,
+        ISpeechLexicon:                     um::oaidl::IDispatch,
+        ISpeechLexiconWords:                um::oaidl::IDispatch,
+        ISpeechLexiconWord:                 um::oaidl::IDispatch,
+        ISpeechLexiconPronunciations:       um::oaidl::IDispatch,
+        ISpeechLexiconPronunciation:        um::oaidl::IDispatch,
+        ISpeechPhraseInfoBuilder:           um::oaidl::IDispatch,
+        ISpeechPhoneConverter:              um::oaidl::IDispatch,
+    }
+
+    [feature = "winapi-sapi53"] um::sapi53 {
+        ISpEventSource2:              um::sapi51::ISpEventSource,
+        ISpShortcut:                  um::unknwnbase::IUnknown,
+        ISpPhoneticAlphabetConverter: um::unknwnbase::IUnknown,
+        ISpPhoneticAlphabetSelection: um::unknwnbase::IUnknown,
+        ISpPhrase2:                   um::sapi51::ISpPhrase,
+        ISpRecoResult2:               um::sapi51::ISpRecoResult,
+        ISpXMLRecoResult:             um::sapi51::ISpRecoResult,
+        ISpGrammarBuilder2:           um::unknwnbase::IUnknown,
+        ISpRecoGrammar2:              um::unknwnbase::IUnknown,
+        ISpeechResourceLoader:        um::oaidl::IDispatch,
+        ISpRecoContext2:              um::unknwnbase::IUnknown,
+        ISpSerializeState:            um::unknwnbase::IUnknown,
+        ISpRecognizer2:               um::unknwnbase::IUnknown,
+        ISpEnginePronunciation:       um::unknwnbase::IUnknown,
+        ISpDisplayAlternates:         um::unknwnbase::IUnknown,
+        ISpeechRecoResult2:           um::sapi51::ISpeechRecoResult,
+        ISpeechXMLRecoResult:         um::sapi51::ISpeechRecoResult,
+        ISpeechRecoResultDispatch:    um::oaidl::IDispatch,
+    }
+
+    [feature = "winapi-sapiddk"] um::sapiddk {
+        ISpSREngineSite2:       um::sapiddk51::ISpSREngineSite,
+        ISpSREngine2:           um::sapiddk51::ISpSREngine,
+        ISpSRAlternates:        um::unknwnbase::IUnknown,
+        ISpSRAlternates2:       um::sapiddk::ISpSRAlternates,
+        ISpPrivateEngineCallEx: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-sapiddk51"] um::sapiddk51 {
+        ISpTokenUI:                um::unknwnbase::IUnknown,
+        ISpObjectTokenEnumBuilder: um::sapi51::IEnumSpObjectTokens,
+        ISpErrorLog:               um::unknwnbase::IUnknown,
+        ISpGrammarCompiler:        um::unknwnbase::IUnknown,
+        ISpGramCompBackend:        um::sapi51::ISpGrammarBuilder,
+        ISpITNProcessor:           um::unknwnbase::IUnknown,
+        ISpPhraseBuilder:          um::sapi51::ISpPhrase,
+        ISpThreadControl:          um::sapi51::ISpNotifySink,
+        ISpTaskManager:            um::unknwnbase::IUnknown,
+        ISpTTSEngineSite:          um::sapi51::ISpEventSink,
+        ISpTTSEngine:              um::unknwnbase::IUnknown,
+        ISpCFGInterpreterSite:     um::unknwnbase::IUnknown,
+        ISpCFGInterpreter:         um::unknwnbase::IUnknown,
+        ISpSREngineSite:           um::unknwnbase::IUnknown,
+        ISpSREngine:               um::unknwnbase::IUnknown,
+        _ISpPrivateEngineCall:     um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-servprov"] um::servprov {
+        IServiceProvider: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-shobjidl"] um::shobjidl {
+        IFileDialogEvents:        um::unknwnbase::IUnknown,
+        IFileDialog:              um::shobjidl_core::IModalWindow,
+        IFileSaveDialog:          um::shobjidl::IFileDialog,
+        IFileOpenDialog:          um::shobjidl::IFileDialog,
+        IFileDialogCustomize:     um::unknwnbase::IUnknown,
+        IFileDialogControlEvents: um::unknwnbase::IUnknown,
+        IFileDialog2:             um::shobjidl::IFileDialog,
+    }
+
+    [feature = "winapi-shobjidl_core"] um::shobjidl_core {
+        IShellItem:        um::unknwnbase::IUnknown,
+        IShellItemArray:   um::unknwnbase::IUnknown,
+        IModalWindow:      um::unknwnbase::IUnknown,
+        IShellItemFilter:  um::unknwnbase::IUnknown,
+        ITaskbarList:      um::unknwnbase::IUnknown,
+        ITaskbarList2:     um::shobjidl_core::ITaskbarList,
+        ITaskbarList3:     um::shobjidl_core::ITaskbarList2,
+        ITaskbarList4:     um::shobjidl_core::ITaskbarList3,
+        IDesktopWallpaper: um::unknwnbase::IUnknown,
+        IShellLinkA:       um::unknwnbase::IUnknown,
+        IShellLinkW:       um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-spellcheck"] um::spellcheck {
+        ISpellingError:                   um::unknwnbase::IUnknown,
+        IEnumSpellingError:               um::unknwnbase::IUnknown,
+        IOptionDescription:               um::unknwnbase::IUnknown,
+        ISpellCheckerChangedEventHandler: um::unknwnbase::IUnknown,
+        ISpellChecker:                    um::unknwnbase::IUnknown,
+        ISpellChecker2:                   um::spellcheck::ISpellChecker,
+        ISpellCheckerFactory:             um::unknwnbase::IUnknown,
+        IUserDictionariesRegistrar:       um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-taskschd"] um::taskschd {
+        ITaskFolderCollection:      um::oaidl::IDispatch,
+        ITaskFolder:                um::oaidl::IDispatch,
+        IRegisteredTask:            um::oaidl::IDispatch,
+        IRunningTask:               um::oaidl::IDispatch,
+        IRunningTaskCollection:     um::oaidl::IDispatch,
+        ITaskDefinition:            um::oaidl::IDispatch,
+        IRegistrationInfo:          um::oaidl::IDispatch,
+        ITriggerCollection:         um::oaidl::IDispatch,
+        ITrigger:                   um::oaidl::IDispatch,
+        IRepetitionPattern:         um::oaidl::IDispatch,
+        ITaskSettings:              um::oaidl::IDispatch,
+        IIdleSettings:              um::oaidl::IDispatch,
+        INetworkSettings:           um::oaidl::IDispatch,
+        IPrincipal:                 um::oaidl::IDispatch,
+        IActionCollection:          um::oaidl::IDispatch,
+        IAction:                    um::oaidl::IDispatch,
+        IRegisteredTaskCollection:  um::oaidl::IDispatch,
+        ITaskService:               um::oaidl::IDispatch,
+        ITaskHandler:               um::unknwnbase::IUnknown,
+        ITaskHandlerStatus:         um::unknwnbase::IUnknown,
+        ITaskVariables:             um::unknwnbase::IUnknown,
+        ITaskNamedValuePair:        um::oaidl::IDispatch,
+        ITaskNamedValueCollection:  um::oaidl::IDispatch,
+        IIdleTrigger:               um::taskschd::ITrigger,
+        ILogonTrigger:              um::taskschd::ITrigger,
+        ISessionStateChangeTrigger: um::taskschd::ITrigger,
+        IEventTrigger:              um::taskschd::ITrigger,
+        ITimeTrigger:               um::taskschd::ITrigger,
+        IDailyTrigger:              um::taskschd::ITrigger,
+        IWeeklyTrigger:             um::taskschd::ITrigger,
+        IMonthlyTrigger:            um::taskschd::ITrigger,
+        IMonthlyDOWTrigger:         um::taskschd::ITrigger,
+        IBootTrigger:               um::taskschd::ITrigger,
+        IRegistrationTrigger:       um::taskschd::ITrigger,
+        IExecAction:                um::taskschd::IAction,
+        IExecAction2:               um::taskschd::IExecAction,
+        IShowMessageAction:         um::taskschd::IAction,
+        IComHandlerAction:          um::taskschd::IAction,
+        IEmailAction:               um::taskschd::IAction,
+        IPrincipal2:                um::oaidl::IDispatch,
+        ITaskSettings2:             um::oaidl::IDispatch,
+        ITaskSettings3:             um::taskschd::ITaskSettings,
+        IMaintenanceSettings:       um::oaidl::IDispatch,
+    }
+
+    [feature = "winapi-urlhist"] um::urlhist {
+        IEnumSTATURL:      um::unknwnbase::IUnknown,
+        IUrlHistoryStg:    um::unknwnbase::IUnknown,
+        IUrlHistoryStg2:   um::urlhist::IUrlHistoryStg,
+        IUrlHistoryNotify: um::docobj::IOleCommandTarget,
+    }
+
+    [feature = "winapi-urlmon"] um::urlmon {
+        IInternetSecurityManager: um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-vsbackup"] um::vsbackup {
+        IVssWMComponent:              um::unknwnbase::IUnknown,
+        IVssExamineWriterMetadata:    um::unknwnbase::IUnknown,
+        IVssExamineWriterMetadataEx:  um::vsbackup::IVssExamineWriterMetadata,
+        IVssExamineWriterMetadataEx2: um::vsbackup::IVssExamineWriterMetadataEx,
+        IVssBackupComponents:         um::unknwnbase::IUnknown,
+        IVssBackupComponentsEx:       um::vsbackup::IVssBackupComponents,
+        IVssBackupComponentsEx2:      um::vsbackup::IVssBackupComponentsEx,
+        IVssBackupComponentsEx3:      um::vsbackup::IVssBackupComponentsEx2,
+        IVssBackupComponentsEx4:      um::vsbackup::IVssBackupComponentsEx3,
+    }
+
+    [feature = "winapi-vss"] um::vss {
+        IVssEnumObject: um::unknwnbase::IUnknown,
+        IVssAsync:      um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-vswriter"] um::vswriter {
+        IVssWMFiledesc:   um::unknwnbase::IUnknown,
+        IVssWMDependency: um::unknwnbase::IUnknown,
+        IVssComponent:    um::unknwnbase::IUnknown,
+        IVssComponentEx:  um::vswriter::IVssComponent,
+        IVssComponentEx2: um::vswriter::IVssComponentEx,
+    }
+
+    [feature = "winapi-wbemads"] um::wbemads {
+        IWMIExtension: um::oaidl::IDispatch,
+    }
+
+    [feature = "winapi-wbemcli"] um::wbemcli {
+        IWbemClassObject:        um::unknwnbase::IUnknown,
+        IWbemObjectAccess:       um::wbemcli::IWbemClassObject,
+        IWbemQualifierSet:       um::unknwnbase::IUnknown,
+        IWbemServices:           um::unknwnbase::IUnknown,
+        IWbemLocator:            um::unknwnbase::IUnknown,
+        IWbemObjectSink:         um::unknwnbase::IUnknown,
+        IEnumWbemClassObject:    um::unknwnbase::IUnknown,
+        IWbemCallResult:         um::unknwnbase::IUnknown,
+        IWbemContext:            um::unknwnbase::IUnknown,
+        IUnsecuredApartment:     um::unknwnbase::IUnknown,
+        IWbemUnsecuredApartment: um::wbemcli::IUnsecuredApartment,
+        IWbemStatusCodeText:     um::unknwnbase::IUnknown,
+        IWbemBackupRestore:      um::unknwnbase::IUnknown,
+        IWbemBackupRestoreEx:    um::wbemcli::IWbemBackupRestore,
+        IWbemRefresher:          um::unknwnbase::IUnknown,
+        IWbemHiPerfEnum:         um::unknwnbase::IUnknown,
+        IWbemConfigureRefresher: um::unknwnbase::IUnknown,
+        IWbemObjectSinkEx:       um::wbemcli::IWbemObjectSink,
+        IWbemShutdown:           um::unknwnbase::IUnknown,
+        IWbemObjectTextSrc:      um::unknwnbase::IUnknown,
+        IMofCompiler:            um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-wbemdisp"] um::wbemdisp {
+        ISWbemServices:        um::oaidl::IDispatch,
+        ISWbemLocator:         um::oaidl::IDispatch,
+        ISWbemObject:          um::oaidl::IDispatch,
+        ISWbemObjectSet:       um::oaidl::IDispatch,
+        ISWbemNamedValue:      um::oaidl::IDispatch,
+        ISWbemNamedValueSet:   um::oaidl::IDispatch,
+        ISWbemQualifier:       um::oaidl::IDispatch,
+        ISWbemQualifierSet:    um::oaidl::IDispatch,
+        ISWbemProperty:        um::oaidl::IDispatch,
+        ISWbemPropertySet:     um::oaidl::IDispatch,
+        ISWbemMethod:          um::oaidl::IDispatch,
+        ISWbemMethodSet:       um::oaidl::IDispatch,
+        ISWbemEventSource:     um::oaidl::IDispatch,
+        ISWbemObjectPath:      um::oaidl::IDispatch,
+        ISWbemLastError:       um::wbemdisp::ISWbemObject,
+        ISWbemSinkEvents:      um::oaidl::IDispatch,
+        ISWbemSink:            um::oaidl::IDispatch,
+        ISWbemSecurity:        um::oaidl::IDispatch,
+        ISWbemPrivilege:       um::oaidl::IDispatch,
+        ISWbemPrivilegeSet:    um::oaidl::IDispatch,
+        ISWbemServicesEx:      um::wbemdisp::ISWbemServices,
+        ISWbemObjectEx:        um::wbemdisp::ISWbemObject,
+        ISWbemDateTime:        um::oaidl::IDispatch,
+        ISWbemRefresher:       um::oaidl::IDispatch,
+        ISWbemRefreshableItem: um::oaidl::IDispatch,
+    }
+
+    [feature = "winapi-wbemprov"] um::wbemprov {
+        IWbemPropertyProvider:            um::unknwnbase::IUnknown,
+        IWbemUnboundObjectSink:           um::unknwnbase::IUnknown,
+        IWbemEventProvider:               um::unknwnbase::IUnknown,
+        IWbemEventProviderQuerySink:      um::unknwnbase::IUnknown,
+        IWbemEventProviderSecurity:       um::unknwnbase::IUnknown,
+        IWbemEventConsumerProvider:       um::unknwnbase::IUnknown,
+        IWbemProviderInitSink:            um::unknwnbase::IUnknown,
+        IWbemProviderInit:                um::unknwnbase::IUnknown,
+        IWbemHiPerfProvider:              um::unknwnbase::IUnknown,
+        IWbemDecoupledRegistrar:          um::unknwnbase::IUnknown,
+        IWbemProviderIdentity:            um::unknwnbase::IUnknown,
+        IWbemDecoupledBasicEventProvider: um::wbemprov::IWbemDecoupledRegistrar,
+        IWbemEventSink:                   um::wbemcli::IWbemObjectSink,
+    }
+
+    [feature = "winapi-wbemtran"] um::wbemtran {
+        IWbemTransport:                 um::unknwnbase::IUnknown,
+        IWbemLevel1Login:               um::unknwnbase::IUnknown,
+        IWbemConnectorLogin:            um::unknwnbase::IUnknown,
+        IWbemAddressResolution:         um::unknwnbase::IUnknown,
+        IWbemClientTransport:           um::unknwnbase::IUnknown,
+        IWbemClientConnectionTransport: um::unknwnbase::IUnknown,
+        IWbemConstructClassObject:      um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-wincodec"] um::wincodec {
+        IWICPalette:                         um::unknwnbase::IUnknown,
+        IWICBitmapSource:                    um::unknwnbase::IUnknown,
+        IWICFormatConverter:                 um::wincodec::IWICBitmapSource,
+        IWICPlanarFormatConverter:           um::wincodec::IWICBitmapSource,
+        IWICBitmapScaler:                    um::wincodec::IWICBitmapSource,
+        IWICBitmapClipper:                   um::wincodec::IWICBitmapSource,
+        IWICBitmapFlipRotator:               um::wincodec::IWICBitmapSource,
+        IWICBitmapLock:                      um::unknwnbase::IUnknown,
+        IWICBitmap:                          um::wincodec::IWICBitmapSource,
+        IWICColorContext:                    um::unknwnbase::IUnknown,
+        IWICColorTransform:                  um::wincodec::IWICBitmapSource,
+        IWICFastMetadataEncoder:             um::unknwnbase::IUnknown,
+        IWICStream:                          um::objidlbase::IStream,
+        IWICEnumMetadataItem:                um::unknwnbase::IUnknown,
+        IWICMetadataQueryReader:             um::unknwnbase::IUnknown,
+        IWICMetadataQueryWriter:             um::wincodec::IWICMetadataQueryReader,
+        IWICBitmapEncoder:                   um::unknwnbase::IUnknown,
+        IWICBitmapFrameEncode:               um::unknwnbase::IUnknown,
+        IWICPlanarBitmapFrameEncode:         um::unknwnbase::IUnknown,
+        IWICImageEncoder:                    um::unknwnbase::IUnknown,
+        IWICBitmapDecoder:                   um::unknwnbase::IUnknown,
+        IWICBitmapSourceTransform:           um::unknwnbase::IUnknown,
+        IWICPlanarBitmapSourceTransform:     um::unknwnbase::IUnknown,
+        IWICBitmapFrameDecode:               um::wincodec::IWICBitmapSource,
+        IWICProgressiveLevelControl:         um::unknwnbase::IUnknown,
+        IWICProgressCallback:                um::unknwnbase::IUnknown,
+        IWICBitmapCodecProgressNotification: um::unknwnbase::IUnknown,
+        IWICComponentInfo:                   um::unknwnbase::IUnknown,
+        IWICFormatConverterInfo:             um::wincodec::IWICComponentInfo,
+        IWICBitmapCodecInfo:                 um::wincodec::IWICComponentInfo,
+        IWICBitmapEncoderInfo:               um::wincodec::IWICBitmapCodecInfo,
+        IWICBitmapDecoderInfo:               um::wincodec::IWICBitmapCodecInfo,
+        IWICPixelFormatInfo:                 um::wincodec::IWICComponentInfo,
+        IWICPixelFormatInfo2:                um::wincodec::IWICPixelFormatInfo,
+        IWICImagingFactory:                  um::unknwnbase::IUnknown,
+        IWICImagingFactory2:                 um::wincodec::IWICImagingFactory,
+        IWICDevelopRawNotificationCallback:  um::unknwnbase::IUnknown,
+        IWICDevelopRaw:                      um::wincodec::IWICBitmapFrameDecode,
+        IWICDdsDecoder:                      um::unknwnbase::IUnknown,
+        IWICDdsEncoder:                      um::unknwnbase::IUnknown,
+        IWICDdsFrameDecode:                  um::unknwnbase::IUnknown,
+        IWICJpegFrameDecode:                 um::unknwnbase::IUnknown,
+        IWICJpegFrameEncode:                 um::unknwnbase::IUnknown,
+    }
+
+    [feature = "winapi-wincodecsdk"] um::wincodecsdk {
+        IWICMetadataBlockReader: um::unknwnbase::IUnknown,
+        IWICMetadataBlockWriter: um::wincodecsdk::IWICMetadataBlockReader,
+        IWICMetadataReader:      um::unknwnbase::IUnknown,
+        IWICMetadataWriter:      um::wincodecsdk::IWICMetadataReader,
+        IWICStreamProvider:      um::unknwnbase::IUnknown,
+        IWICPersistStream:       um::objidl::IPersistStream,
+        IWICMetadataHandlerInfo: um::wincodec::IWICComponentInfo,
+        IWICMetadataReaderInfo:  um::wincodecsdk::IWICMetadataHandlerInfo,
+        IWICMetadataWriterInfo:  um::wincodecsdk::IWICMetadataHandlerInfo,
+        IWICComponentFactory:    um::wincodec::IWICImagingFactory,
+    }
+}
+
+raw_interfaces! {
+    [feature = "winapi-d3d10shader"] um::d3d10shader {
+        ID3D10ShaderReflectionType,
+        ID3D10ShaderReflectionVariable,
+        ID3D10ShaderReflectionConstantBuffer,
+    }
+
+    [feature = "winapi-d3d11shader"] um::d3d11shader {
+        ID3D11ShaderReflectionType,
+        ID3D11ShaderReflectionVariable,
+        ID3D11ShaderReflectionConstantBuffer,
+        ID3D11FunctionReflection,
+        ID3D11FunctionParameterReflection,
+    }
+
+    [feature = "winapi-d3d12shader"] um::d3d12shader {
+        ID3D12FunctionParameterReflection,
+        ID3D12FunctionReflection,
+        ID3D12ShaderReflectionConstantBuffer,
+        ID3D12ShaderReflectionType,
+        ID3D12ShaderReflectionVariable,
+    }
+
+    [feature = "winapi-d3dcommon"] um::d3dcommon {
+        ID3DInclude,
+    }
+
+    [feature = "winapi-sapi51"] um::sapi51 {
+        ISpNotifyCallback,
+    }
+
+    [feature = "winapi-vswriter"] um::vswriter {
+        IVssWriterComponents,
+        IVssCreateWriterMetadata,
+    }
+}
@@ -0,0 +1,177 @@
+//! Exercise COM aggregation of [mcom::ComObject]s.  Runs on any host.
+
+use mcom::ComObject;
+use mcom::abi::*;
+use mcom::object::Aggregated;
+
+use std::ptr::null_mut;
+use std::sync::atomic::{AtomicU32, Ordering::*};
+
+
+
+mcom::interface! {
+    #[uuid(0x0b7d53a4, 0x2c61, 0x4f0e, 0xa3, 0x5c, 0x71, 0x2e, 0x90, 0x44, 0x1d, 0x01)]
+    pub interface IOuter(IOuterVtbl, IOuterImpl): IUnknown(IUnknownVtbl) {
+        fn Outer() -> u32;
+    }
+
+    #[uuid(0x0b7d53a4, 0x2c61, 0x4f0e, 0xa3, 0x5c, 0x71, 0x2e, 0x90, 0x44, 0x1d, 0x02)]
+    pub interface IInner(IInnerVtbl, IInnerImpl): IUnknown(IUnknownVtbl) {
+        fn Inner() -> u32;
+    }
+
+    #[uuid(0x0b7d53a4, 0x2c61, 0x4f0e, 0xa3, 0x5c, 0x71, 0x2e, 0x90, 0x44, 0x1d, 0x03)]
+    pub interface IHidden(IHiddenVtbl, IHiddenImpl): IUnknown(IUnknownVtbl) {
+        fn Hidden() -> u32;
+    }
+}
+
+struct Outer {
+    inner:  Aggregated,
+    drops:  &'static AtomicU32,
+}
+
+mcom::implement!(Outer: IOuter; aggregate inner: IInner);
+
+impl IOuterImpl for Outer {
+    fn Outer(&self) -> Result<u32, HRESULT> { Ok(1) }
+}
+
+impl Drop for Outer {
+    fn drop(&mut self) { self.drops.fetch_add(1, Relaxed); }
+}
+
+struct Inner {
+    drops:  &'static AtomicU32,
+}
+
+mcom::implement!(Inner: IInner, IHidden);
+
+impl IInnerImpl for Inner {
+    fn Inner(&self) -> Result<u32, HRESULT> { Ok(2) }
+}
+
+impl IHiddenImpl for Inner {
+    fn Hidden(&self) -> Result<u32, HRESULT> { Ok(3) }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) { self.drops.fetch_add(10, Relaxed); }
+}
+
+fn create(drops: &'static AtomicU32) -> mcom::Rc<IOuter> {
+    let outer = ComObject::new(Outer { inner: Aggregated::new(), drops });
+    let inner = unsafe { ComObject::new_aggregated(Inner { drops }, &outer.to_rc()) };
+    assert!(outer.inner.set(inner.into_rc()).is_ok());
+    outer.into_rc()
+}
+
+unsafe fn refs(unk: *mut IUnknown) -> ULONG {
+    (*unk).AddRef();
+    (*unk).Release()
+}
+
+
+
+#[test] fn identity() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let outer = create(&DROPS);
+    let outer_unk = outer.try_cast::<IUnknown>().unwrap();
+
+    let inner = outer.try_cast::<IInner>().unwrap();
+    assert_eq!(2, inner.Inner().unwrap());
+    assert_eq!(outer_unk.as_ptr(), inner.try_cast::<IUnknown>().unwrap().as_ptr());
+    assert_eq!(outer.as_ptr(), inner.try_cast::<IOuter>().unwrap().as_ptr());
+    assert_eq!(inner.as_ptr(), inner.try_cast::<IInner>().unwrap().as_ptr());
+    assert_eq!(inner.as_ptr(), outer_unk.try_cast::<IInner>().unwrap().as_ptr());
+
+    // interfaces of the inner object not exposed by the outer object are unreachable
+    assert!(outer.try_cast::<IHidden>().is_none());
+    assert!(inner.try_cast::<IHidden>().is_none());
+}
+
+#[test] fn non_delegating_unknown() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let outer = ComObject::new(Outer { inner: Aggregated::new(), drops: &DROPS });
+    let outer_unk : mcom::Rc<IUnknown> = outer.to_rc();
+    let inner_unk : mcom::Rc<IUnknown> = unsafe { ComObject::new_aggregated(Inner { drops: &DROPS }, &outer_unk) }.into_rc();
+    assert_ne!(outer_unk.as_ptr(), inner_unk.as_ptr());
+
+    // QI(IUnknown) of the inner unknown returns itself, counting towards the inner object
+    let inner_unk2 = inner_unk.try_cast::<IUnknown>().unwrap();
+    assert_eq!(inner_unk.as_ptr(), inner_unk2.as_ptr());
+    assert_eq!(2, unsafe { refs(inner_unk.as_ptr()) });
+    drop(inner_unk2);
+
+    // QI(other) of the inner unknown returns an interface counting towards the outer object
+    assert_eq!(2, unsafe { refs(outer_unk.as_ptr()) });
+    let hidden = inner_unk.try_cast::<IHidden>().unwrap();
+    assert_eq!(3, hidden.Hidden().unwrap());
+    assert_eq!(3, unsafe { refs(outer_unk.as_ptr()) });
+    assert_eq!(1, unsafe { refs(inner_unk.as_ptr()) });
+    drop(hidden);
+    assert_eq!(2, unsafe { refs(outer_unk.as_ptr()) });
+
+    assert!(outer.inner.set(inner_unk).is_ok());
+    drop((outer, outer_unk));
+    assert_eq!(11, DROPS.load(Relaxed));
+}
+
+#[test] fn refcounts() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let outer = create(&DROPS);
+    let outer_unk = outer.try_cast::<IUnknown>().unwrap().into_raw();
+    assert_eq!(2, unsafe { refs(outer_unk) });
+
+    let inner = outer.try_cast::<IInner>().unwrap();
+    assert_eq!(3, unsafe { refs(outer_unk) });
+    let inner2 = inner.clone();
+    assert_eq!(4, unsafe { refs(outer_unk) });
+    assert_eq!(4, unsafe { refs(inner.as_ptr().cast()) });
+
+    drop((inner, inner2));
+    assert_eq!(2, unsafe { refs(outer_unk) });
+    drop(outer);
+    assert_eq!(0, DROPS.load(Relaxed));
+
+    // the outer object releases the inner object when destroyed
+    assert_eq!(0, unsafe { (*outer_unk).Release() });
+    assert_eq!(11, DROPS.load(Relaxed));
+}
+
+#[test] fn inner_outlives_interfaces() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let inner = create(&DROPS).try_cast::<IInner>().unwrap();
+    assert_eq!(0, DROPS.load(Relaxed));
+    assert_eq!(1, inner.try_cast::<IOuter>().unwrap().Outer().unwrap());
+    drop(inner);
+    assert_eq!(11, DROPS.load(Relaxed));
+}
+
+#[test] fn set_once() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let outer = ComObject::new(Outer { inner: Aggregated::new(), drops: &DROPS });
+    assert!(outer.inner.get().is_none());
+    let outer_unk : mcom::Rc<IUnknown> = outer.to_rc();
+    let a : mcom::Rc<IUnknown> = unsafe { ComObject::new_aggregated(Inner { drops: &DROPS }, &outer_unk) }.into_rc();
+    let b : mcom::Rc<IUnknown> = unsafe { ComObject::new_aggregated(Inner { drops: &DROPS }, &outer_unk) }.into_rc();
+    let a_ptr = a.as_ptr();
+    assert!(outer.inner.set(a).is_ok());
+    assert!(outer.inner.set(b).is_err()); // `b` is released here
+    assert_eq!(10, DROPS.load(Relaxed));
+    assert_eq!(a_ptr, outer.inner.get().unwrap() as *const IUnknown as *mut IUnknown);
+
+    let mut ppv = 1 as *mut c_void;
+    assert_eq!(E_NOINTERFACE, unsafe { outer_unk.QueryInterface(&IHidden::uuidof(), &mut ppv) });
+    assert!(ppv.is_null());
+    assert_eq!(E_POINTER, unsafe { outer_unk.QueryInterface(&IInner::uuidof(), null_mut()) });
+    drop((outer, outer_unk));
+    assert_eq!(21, DROPS.load(Relaxed));
+}
+
+#[test] #[should_panic = "aggregated objects can only be converted into their non-delegating IUnknown"] fn into_rc_delegating() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let outer = ComObject::new(Outer { inner: Aggregated::new(), drops: &DROPS });
+    let inner = unsafe { ComObject::new_aggregated(Inner { drops: &DROPS }, &outer.to_rc()) };
+    let _ : mcom::Rc<IInner> = inner.into_rc();
+}
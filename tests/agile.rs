@@ -0,0 +1,194 @@
+//! Exercise [mcom::Agile]'s pure-Rust `IAgileReference` against [mcom::emulator].  Runs on non-Windows hosts with `--features emulator`.
+#![cfg(all(feature = "emulator", not(windows)))]
+
+use mcom::{Agile, ComObject, Rc};
+use mcom::abi::*;
+use mcom::agile::get_agile_reference;
+use mcom::init;
+
+use std::convert::TryFrom;
+use std::ptr::null_mut;
+use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicU32, Ordering::*};
+use std::thread::spawn;
+
+
+
+mcom::interface! {
+    #[uuid(0x2f6a91d4, 0x8c03, 0x4e57, 0xa1, 0x6b, 0x5d, 0x22, 0x9e, 0x40, 0xc7, 0x38)]
+    pub interface IValue(IValueVtbl, IValueImpl): IUnknown(IUnknownVtbl) {
+        fn Get() -> u32;
+    }
+}
+
+struct Value { value: u32, drops: &'static AtomicU32 }
+struct AgileValue(Value);
+struct NoMarshalValue(Value);
+
+mcom::implement!(Value: IValue);
+mcom::implement!(AgileValue: IValue, IAgileObject);
+mcom::implement!(NoMarshalValue: IValue, INoMarshal, IAgileObject);
+
+impl IValueImpl for Value { fn Get(&self) -> Result<u32, HRESULT> { Ok(self.value) } }
+impl IValueImpl for AgileValue { fn Get(&self) -> Result<u32, HRESULT> { Ok(self.0.value) } }
+impl IValueImpl for NoMarshalValue { fn Get(&self) -> Result<u32, HRESULT> { Ok(self.0.value) } }
+impl IAgileObjectImpl for AgileValue {}
+impl IAgileObjectImpl for NoMarshalValue {}
+impl INoMarshalImpl for NoMarshalValue {}
+impl Drop for Value { fn drop(&mut self) { self.drops.fetch_add(1, Relaxed); } }
+
+fn value(value: u32, drops: &'static AtomicU32) -> Rc<IValue> { ComObject::new(Value { value, drops }).into_rc() }
+
+/// The MTA is process-wide:  serialize tests that might observe or create it.
+fn lock_mta() -> MutexGuard<'static, ()> {
+    static MTA : Mutex<()> = Mutex::new(());
+    MTA.lock().unwrap_or_else(|poison| poison.into_inner())
+}
+
+fn on_new_sta<R: Send + 'static>(f: impl FnOnce() -> R + Send + 'static) -> R {
+    spawn(move || {
+        init::sta().unwrap();
+        let r = f();
+        unsafe { init::uninitialize() };
+        r
+    }).join().unwrap()
+}
+
+fn in_sta<R>(f: impl FnOnce() -> R) -> R {
+    init::sta().unwrap();
+    let r = f();
+    unsafe { init::uninitialize() };
+    r
+}
+
+
+
+#[test] fn eager() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    in_sta(|| {
+        let v = value(1, &DROPS);
+        let agile = Agile::try_from(&v).unwrap();
+        assert_eq!(v.as_ptr(), Rc::try_from(&agile).unwrap().as_ptr());
+
+        let agile2 = agile.clone();
+        let hr = on_new_sta(move || agile2.resolve().err().unwrap().hresult());
+        assert_eq!(RPC_E_WRONG_THREAD, hr, "the emulator doesn't generate proxies");
+
+        drop((v, agile));
+        assert_eq!(1, DROPS.load(SeqCst), "dropping the last Agile should revoke it's cookie");
+    });
+}
+
+#[test] fn lazy() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    in_sta(|| {
+        let v = value(2, &DROPS);
+        let agile = Agile::try_from_lazy(&v).unwrap();
+        assert_eq!(2, agile.resolve().unwrap().Get().unwrap());
+
+        let agile2 = agile.clone();
+        let hr = on_new_sta(move || agile2.resolve().err().unwrap().hresult());
+        assert_eq!(RPC_E_WRONG_THREAD, hr);
+
+        drop((v, agile));
+        assert_eq!(1, DROPS.load(SeqCst));
+
+        let agile = Agile::try_from_lazy(value(3, &DROPS)).unwrap();
+        on_new_sta(move || drop(agile));
+        assert_eq!(1, DROPS.load(SeqCst), "released from the wrong apartment:  should leak instead");
+    });
+}
+
+#[test] fn eager_vs_lazy() {
+    static EAGER : AtomicU32 = AtomicU32::new(0);
+    static LAZY  : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    in_sta(|| {
+        let eager = Agile::try_from_eager(value(10, &EAGER)).unwrap();
+        let lazy  = Agile::try_from_lazy(value(11, &LAZY)).unwrap();
+        on_new_sta(move || drop((eager, lazy)));
+        assert_eq!(1, EAGER.load(SeqCst), "the eager reference's cookie can be revoked from any apartment");
+        assert_eq!(0, LAZY.load(SeqCst), "the lazy reference is held by it's creating apartment, and leaks when released from another");
+    });
+}
+
+#[test] fn agile_object() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    in_sta(|| {
+        let v : Rc<IValue> = ComObject::new(AgileValue(Value { value: 4, drops: &DROPS })).into_rc();
+        let eager = Agile::try_from_eager(&v).unwrap();
+        let lazy  = Agile::try_from_lazy(&v).unwrap();
+        let ptr = v.as_ptr() as usize;
+        drop(v);
+        assert_eq!((ptr, 4, ptr, 4), on_new_sta(move || {
+            let (eager, lazy) = (eager.resolve().unwrap(), lazy.resolve().unwrap());
+            (eager.as_ptr() as usize, eager.Get().unwrap(), lazy.as_ptr() as usize, lazy.Get().unwrap())
+        }));
+    });
+    assert_eq!(1, DROPS.load(SeqCst));
+}
+
+#[test] fn no_marshal() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    in_sta(|| {
+        let v : Rc<IValue> = ComObject::new(NoMarshalValue(Value { value: 5, drops: &DROPS })).into_rc();
+        assert_eq!(CO_E_NOT_SUPPORTED, Agile::try_from_eager(&v).err().unwrap().hresult());
+        assert_eq!(CO_E_NOT_SUPPORTED, Agile::try_from_lazy(&v).err().unwrap().hresult(), "INoMarshal should win over IAgileObject");
+    });
+    assert_eq!(1, DROPS.load(SeqCst));
+}
+
+#[test] fn not_initialized() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    let v = value(6, &DROPS);
+    assert_eq!(CO_E_NOTINITIALIZED, Agile::try_from_eager(&v).err().unwrap().hresult());
+    assert_eq!(CO_E_NOTINITIALIZED, Agile::try_from_lazy(&v).err().unwrap().hresult());
+    drop(v);
+    assert_eq!(1, DROPS.load(SeqCst));
+}
+
+#[test] fn raw() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    let v = value(7, &DROPS);
+    in_sta(|| unsafe {
+        let mut agile = null_mut();
+        assert_eq!(E_POINTER,       get_agile_reference(AGILEREFERENCE_DEFAULT, &IValue::uuidof(), v.as_ptr().cast(), null_mut()));
+        assert_eq!(E_INVALIDARG,    get_agile_reference(AGILEREFERENCE_DEFAULT, &IValue::uuidof(), null_mut(), &mut agile));
+        assert_eq!(E_INVALIDARG,    get_agile_reference(42, &IValue::uuidof(), v.as_ptr().cast(), &mut agile));
+        assert_eq!(E_NOINTERFACE,   get_agile_reference(AGILEREFERENCE_DEFAULT, &IAgileObject::uuidof(), v.as_ptr().cast(), &mut agile));
+        assert!(agile.is_null());
+
+        assert_eq!(S_OK, get_agile_reference(AGILEREFERENCE_DELAYEDMARSHAL, &IValue::uuidof(), v.as_ptr().cast(), &mut agile));
+        let agile = Rc::<IAgileReference>::from_raw(agile);
+        assert!(agile.try_cast::<IAgileObject>().is_some(), "the agile reference itself should be agile");
+
+        let mut ptr = null_mut();
+        assert_eq!(E_POINTER, agile.Resolve(&IValue::uuidof(), null_mut()));
+        assert_eq!(E_NOINTERFACE, agile.Resolve(&IAgileObject::uuidof(), &mut ptr));
+        assert!(ptr.is_null());
+        assert_eq!(S_OK, agile.Resolve(&IValue::uuidof(), &mut ptr));
+        assert_eq!(7, Rc::<IValue>::from_raw(ptr.cast()).Get().unwrap());
+    });
+    drop(v);
+    assert_eq!(1, DROPS.load(SeqCst));
+}
+
+#[test] fn debug() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    in_sta(|| {
+        let v = value(9, &DROPS);
+        let eager = Agile::try_from_eager(&v).unwrap();
+        let lazy  = Agile::try_from_lazy(&v).unwrap();
+        assert!(format!("{:?}", eager).starts_with("Agile<{2f6a91d4-8c03-4e57-a16b-5d229e40c738}>(ReferenceOptions::DEFAULT, 0x"), "{:?}", eager);
+        mcom::names::register_interface::<IValue>();
+        assert!(format!("{:?}", eager).starts_with("Agile<IValue>(ReferenceOptions::DEFAULT, 0x"), "{:?}", eager);
+        assert!(format!("{:?}", lazy ).starts_with("Agile<IValue>(ReferenceOptions::DELAYED_MARSHAL, 0x"), "{:?}", lazy);
+    });
+}
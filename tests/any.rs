@@ -0,0 +1,56 @@
+//! Exercise [mcom::AnyRc] against a Rust-implemented COM object.  Runs on any host.
+
+use mcom::{AnyRc, ComObject, Rc};
+use mcom::abi::*;
+
+use std::collections::HashSet;
+
+
+
+mcom::interface! {
+    #[uuid(0x7b3c5d21, 0x4f60, 0x4b8c, 0x9d, 0x0e, 0x1f, 0x2a, 0x3b, 0x4c, 0x5d, 0x01)]
+    pub interface IFoo(IFooVtbl, IFooImpl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x7b3c5d21, 0x4f60, 0x4b8c, 0x9d, 0x0e, 0x1f, 0x2a, 0x3b, 0x4c, 0x5d, 0x02)]
+    pub interface IBar(IBarVtbl, IBarImpl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x7b3c5d21, 0x4f60, 0x4b8c, 0x9d, 0x0e, 0x1f, 0x2a, 0x3b, 0x4c, 0x5d, 0x03)]
+    pub interface INotImplemented(INotImplementedVtbl): IUnknown(IUnknownVtbl) {}
+}
+
+struct Object;
+mcom::implement!(Object: IFoo, IBar);
+impl IFooImpl for Object {}
+impl IBarImpl for Object {}
+
+
+
+#[test] fn downcast() {
+    let foo : Rc<IFoo> = ComObject::new(Object).into_rc();
+    let any = AnyRc::from(&foo);
+    assert!(IsEqualIID(&IFoo::uuidof(), any.iid()));
+    assert!(any.is::<IFoo>() && !any.is::<IBar>());
+
+    assert!(Rc::ptr_eq(&foo, any.downcast_ref::<IFoo>().unwrap()));
+    assert!(any.downcast_ref::<IBar>().is_none());
+    assert!(Rc::ptr_eq(&foo, &any.downcast::<IFoo>().unwrap()));
+
+    let bar = any.downcast::<IBar>().unwrap();
+    assert!(bar.same_object(&foo));
+    assert_eq!(E_NOINTERFACE, any.downcast::<INotImplemented>().err().unwrap().hresult());
+}
+
+#[test] fn identity() {
+    let foo  : Rc<IFoo> = ComObject::new(Object).into_rc();
+    let foo2 : Rc<IFoo> = ComObject::new(Object).into_rc();
+    let bar = foo.try_cast::<IBar>().unwrap();
+
+    let set = vec![AnyRc::from(&foo), AnyRc::from(bar), AnyRc::from(&foo2)].into_iter().collect::<HashSet<_>>();
+    assert_eq!(2, set.len(), "AnyRc<IFoo> and AnyRc<IBar> of the same object should be equal");
+    assert!(set.contains(&AnyRc::from(foo.as_unknown())));
+
+    #[cfg(feature = "alloc")] { // names::register_interface
+        mcom::names::register_interface::<IFoo>();
+        assert_eq!(format!("AnyRc<IFoo>({:?})", foo.as_ptr()), format!("{:?}", AnyRc::from(foo)));
+    }
+}
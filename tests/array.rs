@@ -0,0 +1,100 @@
+//! Exercise [mcom::ComArray] with a counting [Allocator].  Runs on any host.
+#![cfg(feature = "alloc")] // RustHeap
+
+use mcom::{ComArray, ComObject, Rc};
+use mcom::abi::*;
+use mcom::array::{Allocator, RustHeap};
+
+use std::ptr::null_mut;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering::*};
+
+
+
+struct Counted(Arc<AtomicUsize>);
+mcom::implement!(Counted: IAgileObject);
+impl IAgileObjectImpl for Counted {}
+impl Drop for Counted { fn drop(&mut self) { self.0.fetch_add(1, Relaxed); } }
+
+fn counted(drops: &Arc<AtomicUsize>) -> Rc<IAgileObject> { ComObject::new(Counted(drops.clone())).into_rc() }
+
+struct Counting(AtomicUsize);
+unsafe impl Allocator for Counting {
+    fn alloc(&self, bytes: usize) -> *mut c_void { self.0.fetch_add(1, Relaxed); RustHeap.alloc(bytes) }
+    unsafe fn free(&self, ptr: *mut c_void) { self.0.fetch_sub(1, Relaxed); RustHeap.free(ptr) }
+}
+
+/// Allocate an array like a COM API returning one would.
+fn raw_array(allocator: &'static Counting, rcs: Vec<Option<Rc<IAgileObject>>>) -> ComArray<IAgileObject> {
+    let len = rcs.len();
+    let ptrs = allocator.alloc(len * std::mem::size_of::<*mut IAgileObject>()).cast::<*mut IAgileObject>();
+    for (i, rc) in rcs.into_iter().enumerate() { unsafe { ptrs.add(i).write(rc.map_or(null_mut(), Rc::into_raw)) } }
+    unsafe { ComArray::from_raw_in(allocator, ptrs, len) }
+}
+
+
+
+#[test] fn from_raw_in() {
+    static ALLOCATOR : Counting = Counting(AtomicUsize::new(0));
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let mut array = raw_array(&ALLOCATOR, vec![Some(counted(&drops)), None, Some(counted(&drops))]);
+    assert_eq!(1, ALLOCATOR.0.load(Relaxed));
+    assert_eq!(vec![true, false, true], array.iter().map(Option::is_some).collect::<Vec<_>>());
+    array[2] = array[0].clone();
+    assert_eq!(1, drops.load(Relaxed), "replacing the only reference should've released it");
+    array[0] = None;
+    assert_eq!(1, drops.load(Relaxed));
+
+    drop(array);
+    assert_eq!(2, drops.load(Relaxed));
+    assert_eq!(0, ALLOCATOR.0.load(Relaxed));
+}
+
+#[test] fn into_iter() {
+    static ALLOCATOR : Counting = Counting(AtomicUsize::new(0));
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let mut iter = raw_array(&ALLOCATOR, vec![Some(counted(&drops)), None, Some(counted(&drops))]).into_iter();
+    assert_eq!(3, iter.len());
+    let first = iter.next().unwrap().unwrap();
+    assert!(iter.next().unwrap().is_none());
+    drop(iter);
+    assert_eq!(1, drops.load(Relaxed), "the unconsumed element should've been released");
+    assert_eq!(0, ALLOCATOR.0.load(Relaxed));
+    drop(first);
+    assert_eq!(2, drops.load(Relaxed));
+}
+
+#[test] fn zero_length() {
+    static ALLOCATOR : Counting = Counting(AtomicUsize::new(0));
+
+    let array = raw_array(&ALLOCATOR, Vec::new());
+    assert!(array.is_empty());
+    assert_eq!(1, ALLOCATOR.0.load(Relaxed));
+    drop(array);
+    assert_eq!(0, ALLOCATOR.0.load(Relaxed), "zero-length allocations should still be freed");
+
+    let array = raw_array(&ALLOCATOR, Vec::new());
+    let (ptr, len) = array.into_raw();
+    assert!(!ptr.is_null(), "into_raw should return the allocation");
+    assert_eq!(0, len);
+    drop(unsafe { ComArray::from_raw_in(&ALLOCATOR, ptr, len) }.into_iter());
+    assert_eq!(0, ALLOCATOR.0.load(Relaxed));
+}
+
+#[test] fn collect() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let array = vec![Some(counted(&drops)), None].into_iter().collect::<ComArray<_>>();
+    assert_eq!(2, array.len());
+
+    let (ptr, len) = array.into_raw();
+    let array = unsafe { ComArray::from_raw(ptr, len) };
+    assert!(array[0].is_some() && array[1].is_none());
+    drop(array);
+    assert_eq!(1, drops.load(Relaxed));
+
+    let empty = unsafe { ComArray::<IAgileObject>::from_raw(null_mut(), 0) };
+    assert!(empty.is_empty());
+    assert_eq!((null_mut(), 0), empty.into_raw());
+}
@@ -0,0 +1,105 @@
+//! Exercise [mcom::Cached] against an object counting `QueryInterface` and reference count changes.  Runs on any host.
+#![cfg(feature = "alloc")]
+
+use mcom::{Cached, Rc};
+use mcom::abi::*;
+
+use std::cell::Cell;
+use std::ptr::null_mut;
+
+
+
+mcom::interface! {
+    #[uuid(0x5c0e2a71, 0x8d4b, 0x4e19, 0xa2, 0x63, 0x1b, 0x7f, 0x90, 0x3c, 0xd5, 0x01)]
+    pub interface IA(IAVtbl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x5c0e2a71, 0x8d4b, 0x4e19, 0xa2, 0x63, 0x1b, 0x7f, 0x90, 0x3c, 0xd5, 0x02)]
+    pub interface IB(IBVtbl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x5c0e2a71, 0x8d4b, 0x4e19, 0xa2, 0x63, 0x1b, 0x7f, 0x90, 0x3c, 0xd5, 0x03)]
+    pub interface IC(ICVtbl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x5c0e2a71, 0x8d4b, 0x4e19, 0xa2, 0x63, 0x1b, 0x7f, 0x90, 0x3c, 0xd5, 0x04)]
+    pub interface INotImplemented(INotImplementedVtbl): IUnknown(IUnknownVtbl) {}
+}
+
+/// Implements [IUnknown], [IA], [IB] and [IC] with a single vtable.  Never freed.
+#[repr(C)] struct Counting {
+    vtbl:       &'static IUnknownVtbl,
+    queries:    Cell<u32>,
+    refs:       Cell<ULONG>,
+}
+
+impl Counting {
+    fn create() -> Rc<IUnknown> {
+        static VTBL : IUnknownVtbl = IUnknownVtbl { QueryInterface: query_interface, AddRef: add_ref, Release: release };
+        let this = Box::leak(Box::new(Counting { vtbl: &VTBL, queries: Cell::new(0), refs: Cell::new(1) }));
+        unsafe { Rc::from_raw((this as *mut Counting).cast()) }
+    }
+
+    fn get<'a, I>(ptr: *mut I) -> &'a Counting { unsafe { &*ptr.cast() } } // leaked, so valid for any lifetime
+}
+
+unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+    let counting = &*this.cast::<Counting>();
+    counting.queries.set(counting.queries.get() + 1);
+    let riid = &*riid;
+    if [IUnknown::uuidof(), IA::uuidof(), IB::uuidof(), IC::uuidof()].iter().any(|iid| IsEqualIID(iid, riid)) {
+        add_ref(this);
+        *ppv = this.cast();
+        S_OK
+    } else {
+        *ppv = null_mut();
+        E_NOINTERFACE
+    }
+}
+
+unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG { let this = &*this.cast::<Counting>(); this.refs.set(this.refs.get() + 1); this.refs.get() }
+unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG { let this = &*this.cast::<Counting>(); this.refs.set(this.refs.get() - 1); this.refs.get() }
+
+
+
+// n.b. queries are compared against snapshots, as `leak-check` makes QueryInterface calls of it's own
+
+#[test] fn cast() {
+    let cached = Cached::<IUnknown>::new(Counting::create());
+    let counting = Counting::get(cached.as_ptr());
+
+    let a = cached.cast::<IA>().unwrap();
+    let queries = counting.queries.get();
+    assert_eq!(2, counting.refs.get());
+    assert!(Rc::ptr_eq(a, cached.cast::<IA>().unwrap()));
+    assert!(Rc::ptr_eq(a, cached.try_cast::<IA>().unwrap()));
+    assert_eq!((queries, 2), (counting.queries.get(), counting.refs.get()), "cached casts shouldn't QueryInterface, AddRef, or Release");
+
+    assert!(Rc::ptr_eq(cached.as_rc(), cached.cast::<IUnknown>().unwrap()));
+    assert_eq!(queries, counting.queries.get(), "casting to the wrapped interface shouldn't QueryInterface");
+    assert_eq!(1, cached.len());
+
+    assert_eq!(E_NOINTERFACE, cached.cast::<INotImplemented>().unwrap_err().hresult());
+    assert!(cached.try_cast::<INotImplemented>().is_none());
+    assert_eq!(queries + 2, counting.queries.get(), "failed casts shouldn't be cached");
+    assert_eq!(1, cached.len());
+}
+
+#[test] fn spill() {
+    let mut cached = Cached::<IUnknown, 1>::new(Counting::create());
+    let counting = Counting::get(cached.as_ptr());
+    let a = cached.cast::<IA>().unwrap();
+    let b = cached.cast::<IB>().unwrap();
+    let c = cached.cast::<IC>().unwrap();
+    let queries = counting.queries.get();
+    assert!(Rc::ptr_eq(a, cached.cast::<IA>().unwrap()));
+    assert!(Rc::ptr_eq(b, cached.cast::<IB>().unwrap()));
+    assert!(Rc::ptr_eq(c, cached.cast::<IC>().unwrap()));
+    assert_eq!((queries, 4, 3), (counting.queries.get(), counting.refs.get(), cached.len()));
+
+    cached.clear();
+    assert_eq!((1, 0), (counting.refs.get(), cached.len()));
+
+    let _ = cached.cast::<IB>().unwrap();
+    let _ = cached.cast::<IC>().unwrap();
+    assert_eq!(3, counting.refs.get());
+    let _unk = cached.into_rc();
+    assert_eq!(1, counting.refs.get(), "into_rc should release cached interfaces");
+}
@@ -0,0 +1,93 @@
+//! Exercise [mcom::Child] against vtable-only interfaces that don't derive from [IUnknown].  Runs on any host.
+
+use mcom::{Child, ComObject, Rc};
+use mcom::abi::*;
+
+use std::ptr::null_mut;
+
+
+
+mcom::interface! {
+    /// Owned by an [IReflection], like `ID3D12ShaderReflectionConstantBuffer`.
+    #[uuid(0x5e1f3a70, 0x2b4c, 0x4d8e, 0x9f, 0x01, 0x6a, 0x7b, 0x8c, 0x9d, 0xae, 0xc1)]
+    pub interface IBuffer(IBufferVtbl) {
+        unsafe fn GetVariable(index: u32) -> *mut IVariable;
+    }
+
+    /// Owned by an [IReflection], like `ID3D12ShaderReflectionVariable`.
+    #[uuid(0x5e1f3a70, 0x2b4c, 0x4d8e, 0x9f, 0x01, 0x6a, 0x7b, 0x8c, 0x9d, 0xae, 0xc2)]
+    pub interface IVariable(IVariableVtbl) {
+        fn GetValue() -> u32;
+        unsafe fn GetBuffer() -> *mut IBuffer;
+    }
+
+    #[uuid(0x5e1f3a70, 0x2b4c, 0x4d8e, 0x9f, 0x01, 0x6a, 0x7b, 0x8c, 0x9d, 0xae, 0xc3)]
+    pub interface IReflection(IReflectionVtbl, IReflectionImpl): IUnknown(IUnknownVtbl) {
+        unsafe fn GetBuffer() -> *mut IBuffer;
+    }
+}
+
+#[repr(C)] struct Buffer {
+    iface:      IBuffer,
+    variables:  Vec<Variable>,
+}
+
+#[repr(C)] struct Variable {
+    iface:      IVariable,
+    value:      u32,
+    buffer:     *mut IBuffer,
+}
+
+struct Reflection(Box<Buffer>);
+mcom::implement!(Reflection: IReflection);
+
+impl Reflection {
+    fn create(values: &[u32]) -> Rc<IReflection> {
+        let mut buffer = Box::new(Buffer { iface: unsafe { IBuffer::from_vtbl(&BUFFER_VTBL) }, variables: Vec::new() });
+        let buffer_ptr : *mut IBuffer = &mut buffer.iface;
+        buffer.variables = values.iter().map(|&value| Variable { iface: unsafe { IVariable::from_vtbl(&VARIABLE_VTBL) }, value, buffer: buffer_ptr }).collect();
+        ComObject::new(Reflection(buffer)).into_rc()
+    }
+}
+
+impl IReflectionImpl for Reflection {
+    unsafe fn GetBuffer(&self) -> *mut IBuffer { &self.0.iface as *const IBuffer as *mut IBuffer }
+}
+
+const BUFFER_VTBL : IBufferVtbl = IBufferVtbl { GetVariable: buffer_get_variable };
+unsafe extern "system" fn buffer_get_variable(this: *mut IBuffer, index: u32) -> *mut IVariable {
+    match (*this.cast::<Buffer>()).variables.get(index as usize) {
+        Some(v) => &v.iface as *const IVariable as *mut IVariable,
+        None    => null_mut(),
+    }
+}
+
+const VARIABLE_VTBL : IVariableVtbl = IVariableVtbl { GetValue: variable_get_value, GetBuffer: variable_get_buffer };
+unsafe extern "system" fn variable_get_value(this: *mut IVariable, out: *mut u32) -> HRESULT { *out = (*this.cast::<Variable>()).value; S_OK }
+unsafe extern "system" fn variable_get_buffer(this: *mut IVariable) -> *mut IBuffer { (*this.cast::<Variable>()).buffer }
+
+
+
+#[test] fn borrow() {
+    let reflection = Reflection::create(&[1, 2, 3]);
+    let buffer = unsafe { Child::from_raw(&reflection, reflection.GetBuffer()) };
+    let values = (0 .. 4).map(|i| unsafe { Child::from_raw_sibling_opt(buffer, buffer.GetVariable(i)) }.map(|v| v.GetValue().unwrap())).collect::<Vec<_>>();
+    assert_eq!(vec![Some(1), Some(2), Some(3), None], values);
+
+    let variable = unsafe { Child::from_raw_sibling(buffer, buffer.GetVariable(1)) };
+    let copy = variable;
+    assert_eq!(2, copy.GetValue().unwrap());
+    assert_eq!(buffer.as_ptr(), unsafe { variable.GetBuffer() });
+    assert!(unsafe { Child::<IVariable>::from_raw_opt(&reflection, null_mut()) }.is_none());
+}
+
+#[test] fn debug() {
+    let reflection = Reflection::create(&[1]);
+    let buffer = unsafe { Child::from_raw(&reflection, reflection.GetBuffer()) };
+    assert_eq!(format!("Child<IBuffer>({:?})", buffer.as_ptr()), format!("{:?}", buffer));
+}
+
+#[test] #[should_panic] fn from_raw_null() {
+    let reflection = Reflection::create(&[]);
+    let _ = unsafe { Child::<IVariable>::from_raw(&reflection, null_mut()) };
+}
@@ -1,4 +1,4 @@
-use mcom::errors::MethodHResult;
+#![cfg(windows)]
 
 use winapi::shared::winerror::REGDB_E_IIDNOTREG;
 use winapi::um::d3dcommon::*;
@@ -12,10 +12,7 @@ use core::ptr::{null, null_mut};
 #[test] fn d3d11() {
     mcom::init::sta().unwrap();
 
-    let mut device = null_mut();
-    let hr = unsafe { D3D11CreateDevice(null_mut(), D3D_DRIVER_TYPE_NULL, null_mut(), 0, null(), 0, D3D11_SDK_VERSION, &mut device, null_mut(), null_mut()) };
-    MethodHResult::check("D3D11CreateDevice", hr).unwrap();
-    let device = unsafe { mcom::Rc::from_raw(device) };
+    let device = unsafe { mcom::Rc::from_out("D3D11CreateDevice", |device| D3D11CreateDevice(null_mut(), D3D_DRIVER_TYPE_NULL, null_mut(), 0, null(), 0, D3D11_SDK_VERSION, device, null_mut(), null_mut())) }.unwrap();
 
     // Fails - ID3D11Device doesn't implement a proxy type factory
     assert_eq!(REGDB_E_IIDNOTREG, mcom::Agile::try_from(&device).map(|_| ()).unwrap_err().hresult());
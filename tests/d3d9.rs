@@ -1,3 +1,5 @@
+#![cfg(windows)]
+
 use winapi::shared::d3d9::*;
 use winapi::shared::winerror::REGDB_E_IIDNOTREG;
 
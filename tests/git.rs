@@ -0,0 +1,181 @@
+//! Exercise [mcom::Git] against [mcom::emulator]'s global interface table.  Runs on non-Windows hosts with `--features emulator`.
+#![cfg(all(feature = "emulator", not(windows)))]
+
+use mcom::{ComObject, Git, Rc};
+use mcom::abi::*;
+use mcom::init;
+
+use std::convert::TryFrom;
+use std::ptr::null_mut;
+use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicU32, Ordering::*};
+use std::thread::spawn;
+
+
+
+mcom::interface! {
+    #[uuid(0x7d0b3c61, 0x52e4, 0x4f0a, 0x9b, 0x28, 0x3e, 0x51, 0xc4, 0x90, 0x6a, 0x17)]
+    pub interface IValue(IValueVtbl, IValueImpl): IUnknown(IUnknownVtbl) {
+        fn Get() -> u32;
+    }
+}
+
+struct Value { value: u32, drops: &'static AtomicU32 }
+struct AgileValue(Value);
+
+mcom::implement!(Value: IValue);
+mcom::implement!(AgileValue: IValue, IAgileObject);
+
+impl IValueImpl for Value { fn Get(&self) -> Result<u32, HRESULT> { Ok(self.value) } }
+impl IValueImpl for AgileValue { fn Get(&self) -> Result<u32, HRESULT> { Ok(self.0.value) } }
+impl IAgileObjectImpl for AgileValue {}
+impl Drop for Value { fn drop(&mut self) { self.drops.fetch_add(1, Relaxed); } }
+
+fn value(value: u32, drops: &'static AtomicU32) -> Rc<IValue> { ComObject::new(Value { value, drops }).into_rc() }
+
+/// The MTA is process-wide:  serialize tests that might observe or create it.
+fn lock_mta() -> MutexGuard<'static, ()> {
+    static MTA : Mutex<()> = Mutex::new(());
+    MTA.lock().unwrap_or_else(|poison| poison.into_inner())
+}
+
+fn on_new_thread<R: Send + 'static>(f: impl FnOnce() -> R + Send + 'static) -> R { spawn(f).join().unwrap() }
+
+fn uninit_after<R>(f: impl FnOnce() -> R) -> R {
+    let r = f();
+    unsafe { init::uninitialize() };
+    r
+}
+
+
+
+#[test] fn same_apartment() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    init::sta().unwrap();
+    uninit_after(|| {
+        let v = value(42, &DROPS);
+        let git = Git::try_from(&v).unwrap();
+        let git2 = git.clone();
+        let v2 = Rc::try_from(&git2).unwrap();
+        assert_eq!(v.as_ptr(), v2.as_ptr());
+        assert_eq!(42, v2.Get().unwrap());
+
+        drop((v, v2, git));
+        assert_eq!(0, DROPS.load(SeqCst), "git2 should still be keeping the value alive");
+        drop(git2);
+        assert_eq!(1, DROPS.load(SeqCst), "dropping the last Git should revoke it's cookie");
+    });
+}
+
+#[test] fn wrong_thread() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    init::sta().unwrap();
+    uninit_after(|| {
+        let git = Git::try_from(value(1, &DROPS)).unwrap();
+        let git2 = git.clone();
+        let hr = on_new_thread(move || {
+            init::sta().unwrap();
+            uninit_after(|| Rc::try_from(git2).err().unwrap().hresult())
+        });
+        assert_eq!(RPC_E_WRONG_THREAD, hr);
+        assert_eq!(1, Rc::try_from(git).unwrap().Get().unwrap()); // still usable from the original apartment
+    });
+    assert_eq!(1, DROPS.load(SeqCst));
+}
+
+#[test] fn agile() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    init::sta().unwrap();
+    uninit_after(|| {
+        let agile : Rc<IValue> = ComObject::new(AgileValue(Value { value: 2, drops: &DROPS })).into_rc();
+        let git = Git::try_from(agile).unwrap();
+        let value = on_new_thread(move || {
+            init::sta().unwrap();
+            uninit_after(|| Rc::try_from(git).unwrap().Get().unwrap())
+        });
+        assert_eq!(2, value);
+    });
+    assert_eq!(1, DROPS.load(SeqCst));
+}
+
+#[test] fn mta() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    init::mta().unwrap();
+    uninit_after(|| {
+        let git = Git::try_from(value(3, &DROPS)).unwrap();
+        let (a, b) = (git.clone(), git.clone());
+        assert_eq!(3, on_new_thread(move || { // explicitly in the MTA
+            init::mta().unwrap();
+            uninit_after(|| Rc::try_from(a).unwrap().Get().unwrap())
+        }));
+        assert_eq!(3, on_new_thread(move || Rc::try_from(b).unwrap().Get().unwrap())); // implicitly in the MTA
+        assert_eq!(RPC_E_WRONG_THREAD, on_new_thread(move || {
+            init::sta().unwrap();
+            uninit_after(|| Rc::try_from(git).err().unwrap().hresult())
+        }));
+    });
+    assert_eq!(1, DROPS.load(SeqCst));
+}
+
+#[test] fn not_initialized() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    let v = value(4, &DROPS);
+    assert_eq!(CO_E_NOTINITIALIZED, Git::try_from(&v).err().unwrap().hresult());
+
+    init::sta().unwrap();
+    let git = uninit_after(|| Git::try_from(&v).unwrap());
+    assert_eq!(CO_E_NOTINITIALIZED, Rc::try_from(&git).err().unwrap().hresult());
+    drop((v, git));
+    assert_eq!(1, DROPS.load(SeqCst));
+}
+
+#[test] fn raw() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    let git = mcom::emulator::global_interface_table();
+    let v = value(5, &DROPS);
+    init::sta().unwrap();
+    uninit_after(|| unsafe {
+        let mut cookies = [0; 3];
+        for cookie in cookies.iter_mut() {
+            assert_eq!(S_OK, git.RegisterInterfaceInGlobal(v.as_ptr().cast(), &IValue::uuidof(), cookie));
+            assert_ne!(0, *cookie, "\"The value of an invalid cookie is 0.\"");
+        }
+        assert!(cookies[0] != cookies[1] && cookies[1] != cookies[2] && cookies[0] != cookies[2]);
+
+        let mut ptr = null_mut();
+        assert_eq!(E_NOINTERFACE, git.GetInterfaceFromGlobal(cookies[0], &IAgileObject::uuidof(), &mut ptr));
+        assert!(ptr.is_null());
+
+        assert_eq!(E_NOINTERFACE, git.RegisterInterfaceInGlobal(v.as_ptr().cast(), &IAgileObject::uuidof(), &mut cookies[0]));
+        assert_eq!(0, cookies[0]);
+        assert_eq!(E_POINTER, git.RegisterInterfaceInGlobal(v.as_ptr().cast(), &IValue::uuidof(), null_mut()));
+
+        for cookie in cookies.iter().skip(1) {
+            assert_eq!(S_OK, git.RevokeInterfaceFromGlobal(*cookie));
+            assert_eq!(E_INVALIDARG, git.RevokeInterfaceFromGlobal(*cookie));
+            assert_eq!(E_INVALIDARG, git.GetInterfaceFromGlobal(*cookie, &IValue::uuidof(), &mut ptr));
+        }
+    });
+    assert_eq!(0, DROPS.load(SeqCst));
+    drop(v);
+    assert_eq!(0, DROPS.load(SeqCst), "leaked cookies[0] should still keep the value alive");
+}
+
+#[test] fn debug() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let _mta = lock_mta();
+    init::sta().unwrap();
+    uninit_after(|| {
+        mcom::names::register_interface::<IValue>();
+        let git = Git::try_from(value(8, &DROPS)).unwrap();
+        let debug = format!("{:?}", git);
+        assert!(debug.starts_with("Git<IValue>(cookie: "), "{}", debug);
+        assert_ne!("Git<IValue>(cookie: 0)", debug);
+    });
+}
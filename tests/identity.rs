@@ -0,0 +1,137 @@
+//! Exercise identity based comparisons of [mcom::Rc] and [mcom::Identity].  Runs on any host.
+
+use mcom::{ComObject, Identity, Rc};
+use mcom::abi::*;
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+
+
+mcom::interface! {
+    #[uuid(0x3a7f0e21, 0xc4d8, 0x4b69, 0x92, 0x1e, 0x58, 0x0b, 0xd6, 0x4a, 0x7c, 0x01)]
+    pub interface ISink(ISinkVtbl, ISinkImpl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x3a7f0e21, 0xc4d8, 0x4b69, 0x92, 0x1e, 0x58, 0x0b, 0xd6, 0x4a, 0x7c, 0x02)]
+    pub interface IOther(IOtherVtbl, IOtherImpl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x3a7f0e21, 0xc4d8, 0x4b69, 0x92, 0x1e, 0x58, 0x0b, 0xd6, 0x4a, 0x7c, 0x03)]
+    pub interface ITornOff(ITornOffVtbl, ITornOffImpl): IUnknown(IUnknownVtbl) {}
+}
+
+struct Sink;
+mcom::implement!(Sink: ISink, IOther; tearoff ITornOff);
+impl ISinkImpl for Sink {}
+impl IOtherImpl for Sink {}
+impl ITornOffImpl for Sink {}
+
+fn sink() -> Rc<ISink> { ComObject::new(Sink).into_rc() }
+
+
+
+#[test] fn ptr_eq() {
+    let a = sink();
+    let b = sink();
+    assert!( Rc::ptr_eq(&a, &a.clone()));
+    assert!(!Rc::ptr_eq(&a, &b));
+
+    let t1 = a.try_cast::<ITornOff>().unwrap();
+    let t2 = a.try_cast::<ITornOff>().unwrap();
+    assert!(!Rc::ptr_eq(&t1, &t2), "each tear-off query allocates a new tear-off");
+    assert!(t1.same_object(&t2));
+    assert!(t1 == t2);
+}
+
+#[test] fn same_object() {
+    let a = sink();
+    let b = sink();
+    let a_other = a.try_cast::<IOther>().unwrap();
+    let a_torn  = a.try_cast::<ITornOff>().unwrap();
+
+    assert_ne!(a.as_ptr() as usize, a_other.as_ptr() as usize);
+    assert!(a.same_object(&a_other));
+    assert!(a.same_object(&a_torn));
+    assert!(a_torn.same_object(&a_other));
+    assert!(!a.same_object(&b));
+    assert!(!b.same_object(&a_torn));
+
+    assert!(a == a_other);
+    assert!(a != b);
+    assert_eq!(a.identity(), a_torn.identity());
+    assert_ne!(a.identity(), b.identity());
+    assert_eq!(a.identity().as_ptr(), a.try_cast::<IUnknown>().unwrap().as_ptr());
+}
+
+#[test] fn collections() {
+    let a = sink();
+    let b = sink();
+    let a_torn = a.try_cast::<ITornOff>().unwrap();
+
+    let sinks : HashSet<Rc<ISink>> = vec![a.clone(), b.clone(), a.clone()].into_iter().collect();
+    assert_eq!(2, sinks.len());
+
+    let torn : BTreeSet<Rc<ITornOff>> = vec![a_torn.clone(), a.try_cast().unwrap(), b.try_cast().unwrap()].into_iter().collect();
+    assert_eq!(2, torn.len());
+    assert_eq!(a < b, a_torn < b.try_cast::<ITornOff>().unwrap());
+
+    let mut names = HashMap::<Identity, &str>::new();
+    names.insert(a.identity(), "a");
+    names.insert(Identity::from(&b), "b");
+    assert_eq!(Some(&"a"), names.get(&a_torn.identity()));
+    assert_eq!(Some(&"b"), names.get(&b.try_cast::<IOther>().unwrap().identity()));
+}
+
+#[test] fn keeps_alive() {
+    let identity = sink().identity();
+    let unk = identity.as_unknown();
+    assert!(unk.try_cast::<ISink>().is_some(), "Identity should keep the object alive");
+    assert_eq!(identity.as_ptr(), identity.clone().into_unknown().as_ptr());
+}
+
+/// Not tested with `validate`, which rejects objects that fail to `QueryInterface` themselves for `IUnknown`.
+#[cfg(not(feature = "validate"))] mod fails_with_pointer {
+    use mcom::Rc;
+    use mcom::abi::*;
+
+    use std::ptr::null_mut;
+    use std::sync::atomic::{AtomicU32, Ordering::*};
+
+    /// Violates COM's rules by failing `QueryInterface(IUnknown)`, but writing it's (un-`AddRef`ed) pointer anyways.
+    #[repr(C)] struct FailsWithPointer {
+        vtbl:   *const IUnknownVtbl,
+        refs:   AtomicU32,
+    }
+
+    impl FailsWithPointer {
+        const VTBL : IUnknownVtbl = IUnknownVtbl { QueryInterface: Self::query_interface, AddRef: Self::add_ref, Release: Self::release };
+
+        fn create() -> Rc<IUnknown> {
+            let obj = Box::new(FailsWithPointer { vtbl: &Self::VTBL, refs: AtomicU32::new(1) });
+            unsafe { Rc::from_raw(Box::into_raw(obj).cast()) }
+        }
+
+        fn refs(unk: &Rc<IUnknown>) -> u32 { unsafe { (*unk.as_ptr().cast::<FailsWithPointer>()).refs.load(Relaxed) } }
+
+        unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+            *ppv = if IsEqualIID(&*riid, &IUnknown::uuidof()) { this.cast() } else { null_mut() };
+            E_NOINTERFACE
+        }
+
+        unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG { (*this.cast::<FailsWithPointer>()).refs.fetch_add(1, Relaxed) + 1 }
+
+        unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG {
+            let refs = (*this.cast::<FailsWithPointer>()).refs.fetch_sub(1, Relaxed) - 1;
+            if refs == 0 { drop(Box::from_raw(this.cast::<FailsWithPointer>())) }
+            refs
+        }
+    }
+
+    #[test] fn fails_with_pointer() {
+        let unk = FailsWithPointer::create();
+        let identity = unk.identity();
+        assert_eq!(unk.as_ptr(), identity.as_ptr(), "should fall back on the object's own pointer");
+        assert_eq!(2, FailsWithPointer::refs(&unk), "the failing QueryInterface's pointer shouldn't have been released");
+        drop(identity);
+        assert!(unk.same_object(&unk.clone()));
+        assert_eq!(1, FailsWithPointer::refs(&unk));
+    }
+}
@@ -0,0 +1,132 @@
+//! Exercise [mcom::init] against [mcom::emulator].  Runs on non-Windows hosts with `--features emulator`.
+#![cfg(all(feature = "emulator", not(windows)))]
+
+use mcom::abi::*;
+use mcom::emulator::{self, Apartment};
+use mcom::init::{self, CoInit, MTAUsageScope};
+
+use std::ptr::null_mut;
+use std::sync::{Mutex, MutexGuard};
+use std::thread::spawn;
+
+
+
+/// The MTA is process-wide:  serialize tests that might observe or create it.
+fn lock_mta() -> MutexGuard<'static, ()> {
+    static MTA : Mutex<()> = Mutex::new(());
+    MTA.lock().unwrap_or_else(|poison| poison.into_inner())
+}
+
+fn on_new_thread<R: Send + 'static>(f: impl FnOnce() -> R + Send + 'static) -> R { spawn(f).join().unwrap() }
+
+
+
+#[test] fn sta_reinit() {
+    let _mta = lock_mta();
+    assert_eq!(None, emulator::current_apartment());
+    assert!(init::sta().unwrap());
+    let sta = emulator::current_apartment().unwrap();
+    assert!(matches!(sta, Apartment::Sta(_)));
+
+    assert!(!init::sta().unwrap());
+    assert!(!(CoInit::STA | CoInit::DISABLE_OLE1DDE).init().unwrap());
+    assert_eq!(3, emulator::init_count());
+
+    let err = init::mta().unwrap_err();
+    assert_eq!(RPC_E_CHANGED_MODE, err.hresult());
+    assert_eq!("CoInitializeEx failed with HRESULT == 0x80010106", err.to_string());
+    assert_eq!(3, emulator::init_count()); // failures aren't counted
+    assert!(!emulator::mta_exists());
+
+    unsafe { init::uninitialize() };
+    unsafe { init::uninitialize() };
+    assert_eq!(Some(sta), emulator::current_apartment());
+    unsafe { init::uninitialize() };
+    assert_eq!(None, emulator::current_apartment());
+    assert_eq!(0, emulator::init_count());
+
+    unsafe { init::uninitialize() }; // unbalanced uninitialize is a no-op
+    assert_eq!(0, emulator::init_count());
+
+    // re-entering creates a new STA
+    assert!(init::sta().unwrap());
+    assert_ne!(Some(sta), emulator::current_apartment());
+    unsafe { init::uninitialize() };
+}
+
+#[test] fn sta_per_thread() {
+    let _mta = lock_mta();
+    init::sta().unwrap();
+    let a = emulator::current_apartment();
+    let b = on_new_thread(|| { init::sta().unwrap(); emulator::current_apartment() });
+    assert_ne!(a, b);
+    assert_eq!(None, on_new_thread(emulator::current_apartment));
+    unsafe { init::uninitialize() };
+}
+
+#[test] fn mta_reinit() {
+    let _mta = lock_mta();
+    assert!(init::mta().unwrap());
+    assert!(!init::mta().unwrap());
+    assert_eq!(Some(Apartment::Mta), emulator::current_apartment());
+    assert!(!emulator::is_implicit_mta());
+    assert_eq!(RPC_E_CHANGED_MODE, init::sta().unwrap_err().hresult());
+
+    // other threads are implicitly in the MTA while it exists...
+    assert_eq!((Some(Apartment::Mta), true), on_new_thread(|| (emulator::current_apartment(), emulator::is_implicit_mta())));
+    // ...but may still create their own STA
+    assert!(on_new_thread(|| { let r = init::sta(); unsafe { init::uninitialize() }; r }).unwrap());
+    // ...or explicitly join the MTA
+    assert!(on_new_thread(|| { let r = init::mta(); unsafe { init::uninitialize() }; r }).unwrap());
+
+    unsafe { init::uninitialize() };
+    assert!(emulator::mta_exists());
+    unsafe { init::uninitialize() };
+    assert!(!emulator::mta_exists());
+    assert_eq!(None, emulator::current_apartment());
+}
+
+#[test] fn mta_thread_exit() {
+    let _mta = lock_mta();
+    on_new_thread(|| init::mta().unwrap()); // never uninitialized
+    assert!(!emulator::mta_exists());
+}
+
+#[test] fn mta_usage() {
+    let _mta = lock_mta();
+    assert!(!emulator::mta_exists());
+    let a = unsafe { MTAUsageScope::new() }.unwrap();
+    let b = unsafe { MTAUsageScope::new() }.unwrap();
+    assert_eq!(2, emulator::mta_usage());
+    assert_eq!(Some(Apartment::Mta), emulator::current_apartment());
+    assert!(emulator::is_implicit_mta());
+    assert_eq!(0, emulator::init_count());
+
+    // implicit MTA threads may still enter an STA
+    assert!(init::sta().unwrap());
+    assert!(matches!(emulator::current_apartment(), Some(Apartment::Sta(_))));
+    unsafe { init::uninitialize() };
+
+    drop(a);
+    assert!(emulator::mta_exists());
+    drop(b);
+    assert!(!emulator::mta_exists());
+    assert_eq!(None, emulator::current_apartment());
+}
+
+#[test] fn raw() {
+    let _mta = lock_mta();
+    unsafe {
+        assert_eq!(E_INVALIDARG, emulator::co_initialize_ex(1 as *mut c_void, COINIT_MULTITHREADED));
+        assert_eq!(E_INVALIDARG, emulator::co_initialize_ex(null_mut(), 0x1000));
+        assert_eq!(0, emulator::init_count());
+
+        assert_eq!(E_POINTER, emulator::co_increment_mta_usage(null_mut()));
+        let mut cookie = null_mut();
+        assert_eq!(S_OK, emulator::co_increment_mta_usage(&mut cookie));
+        assert!(!cookie.is_null());
+        assert_eq!(S_OK,         emulator::co_decrement_mta_usage(cookie));
+        assert_eq!(E_INVALIDARG, emulator::co_decrement_mta_usage(cookie));
+        assert_eq!(E_INVALIDARG, emulator::co_decrement_mta_usage(null_mut()));
+    }
+}
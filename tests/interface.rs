@@ -0,0 +1,156 @@
+//! Exercise [mcom::interface!] against a hand-rolled vtable.  Runs on any host.
+
+use mcom::abi::*;
+
+use std::cell::Cell;
+use std::ptr::null_mut;
+
+
+
+mcom::interface! {
+    /// A simple counter.
+    #[uuid(0x3c8e2a3b, 0x3f6b, 0x4a1e, 0x9a, 0x5e, 0x0d, 0x7b, 0x34, 0x52, 0x21, 0x01)]
+    pub interface ICounter(ICounterVtbl): IUnknown(IUnknownVtbl) {
+        fn Add(amount: u32);
+        fn Get() -> u32;
+        unsafe fn GetRaw(value: *mut u32) -> HRESULT;
+        unsafe fn Refs() -> ULONG;
+    }
+
+    #[uuid(0x3c8e2a3b, 0x3f6b, 0x4a1e, 0x9a, 0x5e, 0x0d, 0x7b, 0x34, 0x52, 0x21, 0x02)]
+    pub interface ICounter2(ICounter2Vtbl): ICounter(ICounterVtbl) {
+        fn Reset();
+    }
+}
+
+#[repr(C)] struct Counter {
+    vtbl:   *const ICounter2Vtbl,
+    refs:   Cell<u32>,
+    value:  Cell<u32>,
+}
+
+impl Counter {
+    const VTBL : ICounter2Vtbl = ICounter2Vtbl {
+        parent: ICounterVtbl {
+            parent: IUnknownVtbl { QueryInterface: Self::query_interface, AddRef: Self::add_ref, Release: Self::release },
+            Add:    Self::add,
+            Get:    Self::get,
+            GetRaw: Self::get,
+            Refs:   Self::refs,
+        },
+        Reset:  Self::reset,
+    };
+
+    fn create() -> mcom::Rc<ICounter2> {
+        let obj = Box::new(Counter { vtbl: &Self::VTBL, refs: Cell::new(1), value: Cell::new(0) });
+        unsafe { mcom::Rc::from_raw(Box::into_raw(obj).cast()) }
+    }
+
+    unsafe fn this<'a, I>(this: *mut I) -> &'a Counter { &*this.cast() }
+
+    unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        let riid = &*riid;
+        if [IUnknown::uuidof(), ICounter::uuidof(), ICounter2::uuidof()].iter().any(|iid| IsEqualIID(iid, riid)) {
+            Self::add_ref(this);
+            *ppv = this.cast();
+            S_OK
+        } else {
+            *ppv = null_mut();
+            E_NOINTERFACE
+        }
+    }
+
+    unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG {
+        let this = Self::this(this);
+        this.refs.set(this.refs.get() + 1);
+        this.refs.get()
+    }
+
+    unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG {
+        let refs = Self::this(this).refs.get() - 1;
+        Self::this(this).refs.set(refs);
+        if refs == 0 { drop(Box::from_raw(this.cast::<Counter>())) }
+        refs
+    }
+
+    unsafe extern "system" fn add(this: *mut ICounter, amount: u32) -> HRESULT {
+        let this = Self::this(this);
+        match this.value.get().checked_add(amount) {
+            Some(value) => { this.value.set(value); S_OK },
+            None        => E_INVALIDARG,
+        }
+    }
+
+    unsafe extern "system" fn get(this: *mut ICounter, value: *mut u32) -> HRESULT {
+        if value.is_null() { return E_POINTER }
+        *value = Self::this(this).value.get();
+        S_OK
+    }
+
+    unsafe extern "system" fn refs(this: *mut ICounter) -> ULONG {
+        Self::this(this).refs.get()
+    }
+
+    unsafe extern "system" fn reset(this: *mut ICounter2) -> HRESULT {
+        Self::this(this).value.set(0);
+        S_OK
+    }
+}
+
+
+
+#[test] fn methods() {
+    let counter = Counter::create();
+    counter.Add(3).unwrap();
+    counter.Add(4).unwrap();
+    assert_eq!(7, counter.Get().unwrap());
+
+    let err = counter.Add(u32::MAX).unwrap_err();
+    assert_eq!(E_INVALIDARG, err.hresult());
+    assert_eq!("ICounter::Add failed with HRESULT == 0x80070057", err.to_string());
+
+    let mut raw = 0;
+    assert_eq!(S_OK, unsafe { counter.GetRaw(&mut raw) });
+    assert_eq!(7, raw);
+    assert_eq!(E_POINTER, unsafe { counter.GetRaw(null_mut()) });
+
+    counter.Reset().unwrap();
+    assert_eq!(0, counter.Get().unwrap());
+}
+
+#[test] fn up_and_cast() {
+    let counter2 = Counter::create();
+    let counter = counter2.clone().up();
+    assert_eq!(2, unsafe { counter.Refs() });
+    let unk = counter.up_ref().clone();
+    assert_eq!(3, unsafe { counter.Refs() });
+
+    let counter2b = unk.try_cast::<ICounter2>().unwrap();
+    assert_eq!(counter2.as_ptr(), counter2b.as_ptr());
+    drop((unk, counter2b, counter2));
+    assert_eq!(1, unsafe { counter.Refs() });
+}
+
+#[test] fn upcast() {
+    let counter2 = Counter::create();
+    let counter : &mcom::Rc<ICounter> = counter2.upcast_ref();
+    assert_eq!(counter2.as_ptr().cast::<ICounter>(), counter.as_ptr());
+    assert_eq!(1, unsafe { counter.Refs() });
+
+    let unk = counter2.clone().upcast::<IUnknown>();
+    assert_eq!(2, unsafe { counter2.Refs() });
+    let unk2 : mcom::Rc<IUnknown> = counter2.clone().into();
+    assert_eq!(3, unsafe { counter2.Refs() });
+    assert_eq!(unk.as_ptr(), unk2.as_ptr());
+    assert_eq!(counter2.as_ptr().cast::<IUnknown>(), unk.as_ptr());
+    drop((unk, unk2));
+    assert_eq!(1, unsafe { counter2.Refs() });
+}
+
+#[test] fn uuid() {
+    let iid = ICounter::uuidof();
+    assert_eq!(0x3c8e2a3b, iid.Data1);
+    assert_eq!(0x3f6b, iid.Data2);
+    assert_eq!(0x4a1e, iid.Data3);
+    assert_eq!([0x9a, 0x5e, 0x0d, 0x7b, 0x34, 0x52, 0x21, 0x01], iid.Data4);
+}
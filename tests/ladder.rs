@@ -0,0 +1,89 @@
+//! Exercise [mcom::Rc::cast_first] against Rust-implemented COM objects implementing part of a ladder.  Runs on any host.
+
+use mcom::{ComObject, Rc};
+use mcom::abi::*;
+use mcom::errors::ProtocolViolation;
+use mcom::ladder::{Rung1, Rung2, Rung3};
+
+use std::ptr::null_mut;
+
+
+
+mcom::interface! {
+    #[uuid(0x3e9d7b40, 0x6c21, 0x4a58, 0x91, 0x0f, 0x2d, 0x4b, 0x6e, 0x8a, 0xc1, 0x01)]
+    pub interface IVersion(IVersionVtbl, IVersionImpl): IUnknown(IUnknownVtbl) {
+        fn Get() -> u32;
+    }
+
+    #[uuid(0x3e9d7b40, 0x6c21, 0x4a58, 0x91, 0x0f, 0x2d, 0x4b, 0x6e, 0x8a, 0xc1, 0x02)]
+    pub interface IVersion2(IVersion2Vtbl, IVersion2Impl): IVersion(IVersionVtbl) {}
+
+    #[uuid(0x3e9d7b40, 0x6c21, 0x4a58, 0x91, 0x0f, 0x2d, 0x4b, 0x6e, 0x8a, 0xc1, 0x03)]
+    pub interface IVersion3(IVersion3Vtbl, IVersion3Impl): IVersion2(IVersion2Vtbl) {}
+}
+
+struct Version1;
+mcom::implement!(Version1: IVersion);
+impl IVersionImpl for Version1 { fn Get(&self) -> Result<u32, HRESULT> { Ok(1) } }
+
+struct Version2;
+mcom::implement!(Version2: IVersion2);
+impl IVersionImpl for Version2 { fn Get(&self) -> Result<u32, HRESULT> { Ok(2) } }
+impl IVersion2Impl for Version2 {}
+
+/// Violates COM's rules by failing `QueryInterface(IVersion3)`, but writing it's (un-`AddRef`ed) pointer anyways.
+#[repr(C)] struct Broken {
+    vtbl:   *const IUnknownVtbl,
+}
+
+impl Broken {
+    const VTBL : IUnknownVtbl = IUnknownVtbl { QueryInterface: Self::query_interface, AddRef: Self::add_ref, Release: Self::release };
+
+    /// Immortal:  leaked, and not reference counted.
+    fn create() -> Rc<IUnknown> { unsafe { Rc::from_raw(Box::into_raw(Box::new(Broken { vtbl: &Self::VTBL })).cast()) } }
+
+    unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        if IsEqualIID(&*riid, &IUnknown::uuidof()) { *ppv = this.cast(); return S_OK }
+        *ppv = if IsEqualIID(&*riid, &IVersion3::uuidof()) { this.cast() } else { null_mut() };
+        E_NOINTERFACE
+    }
+
+    unsafe extern "system" fn add_ref(_this: *mut IUnknown) -> ULONG { 2 }
+    unsafe extern "system" fn release(_this: *mut IUnknown) -> ULONG { 1 }
+}
+
+
+
+#[test] fn selection() {
+    let v1 : Rc<IUnknown> = ComObject::new(Version1).into_rc();
+    let v2 : Rc<IUnknown> = ComObject::new(Version2).into_rc();
+
+    match v2.cast_first::<(IVersion3, IVersion2, IVersion)>().unwrap() {
+        Rung3::B(v2b) => { assert!(v2b.same_object(&v2)); assert_eq!(2, v2b.Get().unwrap()) },
+        other => panic!("expected IVersion2, got {:?}", other),
+    }
+
+    let rung = v1.cast_first::<(IVersion3, IVersion2, IVersion)>().unwrap();
+    assert_eq!(2, rung.index());
+    assert!(rung.as_unknown().same_object(&v1));
+    assert!(matches!(rung, Rung3::C(ref v) if v.Get().unwrap() == 1));
+
+    // order of preference, not order of derivation
+    assert_eq!(0, v2.cast_first::<(IVersion, IVersion2)>().unwrap().index());
+}
+
+#[test] fn none() {
+    let v1 : Rc<IUnknown> = ComObject::new(Version1).into_rc();
+    let err = v1.cast_first::<(IVersion3, IVersion2)>().unwrap_err();
+    assert_eq!(E_NOINTERFACE, err.hresult());
+    assert_eq!("IUnknown::QueryInterface", err.method());
+    assert!(v1.try_cast_first::<(IVersion3,)>().is_none());
+    assert!(matches!(v1.try_cast_first::<(IVersion,)>(), Some(Rung1::A(_))));
+}
+
+#[test] fn protocol_violation() {
+    let broken = Broken::create();
+    let err = broken.cast_first::<(IVersion3, IUnknown)>().unwrap_err();
+    assert_eq!((E_NOINTERFACE, Some(ProtocolViolation::FailedWithInterface)), (err.hresult(), err.protocol_violation()), "shouldn't have moved on past the broken IVersion3");
+    assert!(matches!(broken.cast_first::<(IVersion2, IUnknown)>(), Ok(Rung2::B(_))));
+}
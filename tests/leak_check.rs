@@ -0,0 +1,124 @@
+//! Exercise [mcom::debug]'s reference tracking.  Runs on any host with `--features leak-check`.
+#![cfg(feature = "leak-check")]
+
+use mcom::{ComObject, Rc};
+use mcom::object::Aggregated;
+use mcom::abi::*;
+use mcom::debug::{self, LiveReference, assert_no_leaks};
+
+use std::thread;
+
+
+
+mcom::interface! {
+    #[uuid(0x6e3d0a52, 0x91b7, 0x4f2c, 0xb8, 0x04, 0x7a, 0x1c, 0x55, 0xe9, 0x23, 0x6d)]
+    pub interface IFoo(IFooVtbl, IFooImpl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x6e3d0a52, 0x91b7, 0x4f2c, 0xb8, 0x04, 0x7a, 0x1c, 0x55, 0xe9, 0x23, 0x6e)]
+    pub interface IBar(IBarVtbl, IBarImpl): IUnknown(IUnknownVtbl) {}
+}
+
+struct Foo;
+mcom::implement!(Foo: IFoo, IBar);
+impl IFooImpl for Foo {}
+impl IBarImpl for Foo {}
+
+struct Outer { inner: Aggregated }
+mcom::implement!(Outer: IFoo; aggregate inner: IBar);
+impl IFooImpl for Outer {}
+
+struct Inner;
+mcom::implement!(Inner: IBar);
+impl IBarImpl for Inner {}
+
+fn mine() -> Vec<LiveReference> {
+    let thread = thread::current().id();
+    debug::live_references().into_iter().filter(|r| r.thread() == thread).collect()
+}
+
+
+
+#[test] fn tracked() {
+    let before = mine().len();
+    let foo : Rc<IFoo> = ComObject::new(Foo).into_rc();    let line = line!();
+    let live = mine();
+    assert_eq!(before + 1, live.len());
+    let r = live.last().unwrap();
+    assert_eq!(foo.as_ptr().cast(), r.pointer());
+    assert_eq!(file!(), r.location().file());
+    assert_eq!(line, r.location().line());
+    assert_eq!(None, r.escaped());
+
+    let bar = foo.try_cast::<IBar>().unwrap();
+    let foo2 = foo.clone();
+    let live = mine();
+    assert_eq!(before + 3, live.len());
+    assert_eq!(live[before].identity(), live[before + 1].identity(), "IFoo and IBar should share an identity");
+    assert_ne!(live[before].pointer(), live[before + 1].pointer());
+
+    drop((foo, foo2, bar));
+    assert_eq!(before, mine().len());
+}
+
+#[test] fn escaped() {
+    assert_no_leaks(|| {
+        let foo : Rc<IFoo> = ComObject::new(Foo).into_rc();
+        let raw = foo.clone().into_raw();                   let line = line!();
+        let r = mine().into_iter().find(|r| r.escaped().is_some()).unwrap();
+        assert_eq!(raw.cast(), r.pointer());
+        assert_eq!(line, r.escaped().unwrap().line());
+
+        let adopted = unsafe { Rc::from_raw(raw) };
+        assert!(mine().iter().all(|r| r.escaped().is_none()), "from_raw should re-adopt the escaped reference");
+        drop((foo, adopted));
+    });
+}
+
+#[test] fn out_params() {
+    assert_no_leaks(|| {
+        let foo : Rc<IFoo> = ComObject::new(Foo).into_rc();
+        let weak = foo.downgrade().unwrap();
+        assert!(weak.upgrade().is_some());
+        drop((foo, weak));
+    });
+}
+
+#[test] fn aggregated() {
+    assert_no_leaks(|| {
+        let outer = ComObject::new(Outer { inner: Aggregated::new() });
+        let inner = unsafe { ComObject::new_aggregated(Inner, &outer.to_rc()) };
+        assert!(outer.inner.set(inner.into_rc()).is_ok());
+        let foo : Rc<IFoo> = outer.into_rc();
+        assert!(foo.try_cast::<IBar>().is_some());
+        drop(foo);
+    });
+}
+
+#[test] fn com_array() {
+    assert_no_leaks(|| {
+        let foo : Rc<IFoo> = ComObject::new(Foo).into_rc();
+        let array = vec![Some(foo.clone()), None].into_iter().collect::<mcom::ComArray<_>>();
+        let (ptr, len) = array.into_raw();                  let line = line!();
+        assert_eq!(Some(line), mine().into_iter().find_map(|r| r.escaped()).map(|l| l.line()));
+
+        let array = unsafe { mcom::ComArray::from_raw(ptr, len) };
+        assert!(mine().iter().all(|r| r.escaped().is_none()), "from_raw should re-adopt the escaped references");
+        drop((foo, array));
+    });
+}
+
+#[test] fn threads() {
+    let foo : Rc<IFoo> = ComObject::new(Foo).into_rc();
+    let raw = foo.clone().into_raw() as usize;
+    assert_no_leaks(|| { thread::spawn(|| { Rc::leak(ComObject::new(Foo).into_rc::<IFoo>()); }).join().unwrap() }); // other threads are ignored
+    drop(unsafe { Rc::from_raw(raw as *mut IFoo) });
+    drop(foo);
+}
+
+#[test] #[should_panic = "2 COM reference(s) to 1 object(s) outlived `assert_no_leaks` scope"] fn leaked() {
+    assert_no_leaks(|| {
+        let foo : Rc<IFoo> = ComObject::new(Foo).into_rc();
+        let _ = Rc::leak(foo.try_cast::<IBar>().unwrap());
+        let _ = foo.clone().into_raw();
+    });
+}
@@ -0,0 +1,64 @@
+//! Exercise [mcom::names] and the [Debug] output of [mcom::Rc].  Runs on any host.
+#![cfg(feature = "alloc")] // names::register
+
+use mcom::{ComObject, Rc};
+use mcom::abi::*;
+use mcom::names::{self, Name};
+use mcom::refcount;
+
+
+
+mcom::interface! {
+    #[uuid(0x0c5a7e93, 0x1d2b, 0x4f68, 0x8e, 0x37, 0xa9, 0x40, 0x6b, 0xd1, 0x52, 0xf4)]
+    pub interface IRegistered(IRegisteredVtbl, IRegisteredImpl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x0c5a7e93, 0x1d2b, 0x4f68, 0x8e, 0x37, 0xa9, 0x40, 0x6b, 0xd1, 0x52, 0xf5)]
+    pub interface IAnonymous(IAnonymousVtbl, IAnonymousImpl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x0c5a7e93, 0x1d2b, 0x4f68, 0x8e, 0x37, 0xa9, 0x40, 0x6b, 0xd1, 0x52, 0xf6)]
+    pub interface IRenamed(IRenamedVtbl): IUnknown(IUnknownVtbl) {}
+}
+
+struct Object;
+mcom::implement!(Object: IRegistered, IAnonymous, IAgileObject);
+impl IRegisteredImpl for Object {}
+impl IAnonymousImpl for Object {}
+impl IAgileObjectImpl for Object {}
+
+
+
+#[test] fn lookup() {
+    assert_eq!(Some("IAgileReference"), names::lookup(&IAgileReference::uuidof()));
+    assert_eq!("IWeakReference",        Name(&IWeakReference::uuidof()).to_string());
+
+    assert_eq!(None, names::lookup(&IAnonymous::uuidof()));
+    assert_eq!("{0c5a7e93-1d2b-4f68-8e37-a9406bd152f5}", Name(&IAnonymous::uuidof()).to_string());
+
+    names::register_interface::<IRenamed>();
+    assert_eq!(Some("IRenamed"), names::lookup(&IRenamed::uuidof()));
+    names::register(IRenamed::uuidof(), "IRenamed2");
+    assert_eq!(Some("IRenamed2"), names::lookup(&IRenamed::uuidof()), "later registrations should take precedence");
+    names::register(IUnknown::uuidof(), "IUnknown2");
+    assert_eq!(Some("IUnknown2"), names::lookup(&IUnknown::uuidof()), "registrations should take precedence over mcom's own names");
+}
+
+#[test] fn rc() {
+    names::register_interface::<IRegistered>();
+    let registered : Rc<IRegistered> = ComObject::new(Object).into_rc();
+    let anonymous = registered.try_cast::<IAnonymous>().unwrap();
+    let agile = registered.try_cast::<IAgileObject>().unwrap();
+
+    assert_eq!(format!("Rc<IRegistered>({:?})", registered.as_ptr()), format!("{:?}", registered));
+    assert_eq!(format!("Rc<{{0c5a7e93-1d2b-4f68-8e37-a9406bd152f5}}>({:?})", anonymous.as_ptr()), format!("{:?}", anonymous));
+    assert_eq!(format!("Rc<IAgileObject>({:?})", agile.as_ptr()), format!("{:?}", agile));
+
+    #[derive(Debug)] #[allow(dead_code)] struct Holder { rc: Rc<IRegistered> }
+    let holder = Holder { rc: registered.clone() };
+    assert_eq!(format!("Holder {{ rc: Rc<IRegistered>({:?}) }}", registered.as_ptr()), format!("{:?}", holder));
+
+    let prev = refcount::set_debug_refcounts(true);
+    assert_eq!(format!("Rc<IRegistered>({:?}, refcount: 4)", registered.as_ptr()), format!("{:?}", registered));
+    drop(holder);
+    assert_eq!(format!("Rc<IRegistered>({:?}, refcount: 3)", registered.as_ptr()), format!("{:?}", registered));
+    refcount::set_debug_refcounts(prev);
+}
@@ -0,0 +1,177 @@
+//! Exercise [mcom::ComObject] and [mcom::implement!].  Runs on any host.
+
+use mcom::ComObject;
+use mcom::abi::*;
+
+use std::ptr::null_mut;
+use std::sync::atomic::{AtomicU32, Ordering::*};
+
+
+
+mcom::interface! {
+    #[uuid(0x6a1f0c2e, 0x5d3b, 0x4c8a, 0x8e, 0x21, 0x4f, 0x90, 0x1b, 0x7c, 0x3d, 0x01)]
+    pub interface ICounter(ICounterVtbl, ICounterImpl): IUnknown(IUnknownVtbl) {
+        fn Add(amount: u32);
+        fn Get() -> u32;
+        unsafe fn GetRaw(value: *mut u32) -> HRESULT;
+    }
+
+    #[uuid(0x6a1f0c2e, 0x5d3b, 0x4c8a, 0x8e, 0x21, 0x4f, 0x90, 0x1b, 0x7c, 0x3d, 0x02)]
+    pub interface ICounter2(ICounter2Vtbl, ICounter2Impl): ICounter(ICounterVtbl) {
+        fn Reset();
+    }
+
+    #[uuid(0x6a1f0c2e, 0x5d3b, 0x4c8a, 0x8e, 0x21, 0x4f, 0x90, 0x1b, 0x7c, 0x3d, 0x03)]
+    pub interface INamed(INamedVtbl, INamedImpl): IUnknown(IUnknownVtbl) {
+        fn Len() -> usize;
+    }
+}
+
+mcom::interface! {
+    #[uuid(0x12345678, 0x9abc, 0xdef0, 1, 2, 3, 4, 5, 6, 7, 8)]
+    pub interface INotImplemented(INotImplementedVtbl): IUnknown(IUnknownVtbl) {}
+}
+
+struct Counter {
+    value:  AtomicU32,
+    name:   &'static str,
+    drops:  &'static AtomicU32,
+}
+
+mcom::implement!(Counter: ICounter2, INamed);
+
+impl Drop for Counter {
+    fn drop(&mut self) { self.drops.fetch_add(1, Relaxed); }
+}
+
+impl ICounterImpl for Counter {
+    fn Add(&self, amount: u32) -> Result<(), HRESULT> {
+        let value = self.value.load(Relaxed).checked_add(amount).ok_or(E_INVALIDARG)?;
+        self.value.store(value, Relaxed);
+        Ok(())
+    }
+
+    fn Get(&self) -> Result<u32, HRESULT> { Ok(self.value.load(Relaxed)) }
+
+    unsafe fn GetRaw(&self, value: *mut u32) -> HRESULT {
+        if value.is_null() { return E_POINTER }
+        *value = self.value.load(Relaxed);
+        S_OK
+    }
+}
+
+impl ICounter2Impl for Counter {
+    fn Reset(&self) -> Result<(), HRESULT> { self.value.store(0, Relaxed); Ok(()) }
+}
+
+impl INamedImpl for Counter {
+    fn Len(&self) -> Result<usize, HRESULT> { Ok(self.name.len()) }
+}
+
+fn create(drops: &'static AtomicU32) -> ComObject<Counter> {
+    ComObject::new(Counter { value: AtomicU32::new(0), name: "counter", drops })
+}
+
+unsafe fn refs<I: mcom::ComInterface>(rc: &mcom::Rc<I>) -> ULONG {
+    let unk = rc.as_ptr().cast::<IUnknown>();
+    (*unk).AddRef();
+    (*unk).Release()
+}
+
+
+
+#[test] fn methods() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let counter : mcom::Rc<ICounter2> = create(&DROPS).into_rc();
+    counter.Add(3).unwrap();
+    counter.Add(4).unwrap();
+    assert_eq!(7, counter.Get().unwrap());
+
+    let err = counter.Add(u32::MAX).unwrap_err();
+    assert_eq!(E_INVALIDARG, err.hresult());
+
+    let mut raw = 0;
+    assert_eq!(S_OK, unsafe { counter.GetRaw(&mut raw) });
+    assert_eq!(7, raw);
+    assert_eq!(E_POINTER, unsafe { counter.GetRaw(null_mut()) });
+    assert_eq!(E_POINTER, unsafe { ((*counter.lpVtbl).parent.Get)(counter.as_ptr().cast(), null_mut()) });
+
+    counter.Reset().unwrap();
+    assert_eq!(0, counter.Get().unwrap());
+
+    let named = counter.try_cast::<INamed>().unwrap();
+    assert_eq!(7, named.Len().unwrap());
+}
+
+#[test] fn identity() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let object = create(&DROPS);
+    let counter2 : mcom::Rc<ICounter2> = object.to_rc();
+    let named    : mcom::Rc<INamed>    = object.to_rc();
+    let unk      : mcom::Rc<IUnknown>  = object.to_rc();
+    assert_ne!(counter2.as_ptr().cast::<()>(), named.as_ptr().cast());
+
+    // QI(IUnknown) returns the same pointer from every interface
+    for u in [counter2.try_cast::<IUnknown>(), named.try_cast::<IUnknown>(), unk.try_cast::<IUnknown>()] {
+        assert_eq!(unk.as_ptr(), u.unwrap().as_ptr());
+    }
+
+    // reflexive, symmetric, transitive
+    assert_eq!(named.as_ptr(), named.try_cast::<INamed>().unwrap().as_ptr());
+    assert_eq!(named.as_ptr(), counter2.try_cast::<INamed>().unwrap().as_ptr());
+    assert_eq!(counter2.as_ptr(), named.try_cast::<ICounter2>().unwrap().as_ptr());
+    assert_eq!(counter2.as_ptr(), named.try_cast::<ICounter>().unwrap().try_cast::<ICounter2>().unwrap().as_ptr());
+
+    // ancestor interfaces share their derived interface's vtable
+    assert_eq!(counter2.as_ptr().cast(), unk.try_cast::<ICounter>().unwrap().as_ptr());
+
+    assert!(unk.try_cast::<INotImplemented>().is_none());
+    let mut ppv = 1 as *mut c_void;
+    assert_eq!(E_NOINTERFACE, unsafe { unk.QueryInterface(&INotImplemented::uuidof(), &mut ppv) });
+    assert!(ppv.is_null());
+    assert_eq!(E_POINTER, unsafe { unk.QueryInterface(&IUnknown::uuidof(), null_mut()) });
+}
+
+#[test] fn refcounts() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let object = create(&DROPS);
+    let counter : mcom::Rc<ICounter> = object.to_rc::<ICounter2>().up();
+    assert_eq!(2, unsafe { refs(&counter) });
+    let named = counter.try_cast::<INamed>().unwrap();
+    assert_eq!(3, unsafe { refs(&named) });
+    let object2 = object.clone();
+    assert_eq!(4, unsafe { refs(&counter) });
+
+    drop((object, object2, named));
+    assert_eq!(1, unsafe { refs(&counter) });
+    assert_eq!(0, DROPS.load(Relaxed));
+    drop(counter);
+    assert_eq!(1, DROPS.load(Relaxed));
+}
+
+#[test] fn drop_once() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let object = create(&DROPS);
+    assert_eq!("counter", object.name);
+    let rcs : Vec<mcom::Rc<IUnknown>> = (0..10).map(|_| object.to_rc()).collect();
+    let rc : mcom::Rc<INamed> = object.into();
+    drop(rcs);
+    assert_eq!(0, DROPS.load(Relaxed));
+    drop(rc);
+    assert_eq!(1, DROPS.load(Relaxed));
+}
+
+#[test] fn threads() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let object = create(&DROPS);
+    let threads : Vec<_> = (0..4).map(|_| {
+        let object = object.clone();
+        std::thread::spawn(move || for _ in 0..1000 {
+            let counter : mcom::Rc<ICounter> = object.to_rc::<ICounter2>().up();
+            drop(counter.try_cast::<INamed>().unwrap());
+        })
+    }).collect();
+    for t in threads { t.join().unwrap(); }
+    drop(object);
+    assert_eq!(1, DROPS.load(Relaxed));
+}
@@ -0,0 +1,193 @@
+//! Exercise [mcom::Rc] against a hand-rolled, Rust-implemented COM object.  Runs on any host.
+
+use mcom::abi::*;
+use mcom::errors::{MethodHResult, ProtocolViolation};
+
+use std::ptr::null_mut;
+use std::sync::atomic::{AtomicU32, Ordering::*};
+
+
+
+#[repr(C)] struct Object {
+    vtbl:   *const IUnknownVtbl,
+    refs:   AtomicU32,
+    drops:  *const AtomicU32,
+}
+
+impl Object {
+    const VTBL : IUnknownVtbl = IUnknownVtbl { QueryInterface: Self::query_interface, AddRef: Self::add_ref, Release: Self::release };
+
+    fn create(drops: &AtomicU32) -> mcom::Rc<IUnknown> {
+        let obj = Box::new(Object { vtbl: &Self::VTBL, refs: AtomicU32::new(1), drops });
+        unsafe { mcom::Rc::from_raw(Box::into_raw(obj).cast()) }
+    }
+
+    unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+        if ppv.is_null() { return E_POINTER }
+        if IsEqualIID(&*riid, &IUnknown::uuidof()) {
+            Self::add_ref(this);
+            *ppv = this.cast();
+            S_OK
+        } else if IsEqualIID(&*riid, &IOutOfMemory::uuidof()) {
+            *ppv = null_mut();
+            E_OUTOFMEMORY
+        } else if IsEqualIID(&*riid, &ISucceedsWithNull::uuidof()) {
+            *ppv = null_mut();
+            S_OK
+        } else if IsEqualIID(&*riid, &IFailsWithPointer::uuidof()) {
+            *ppv = this.cast(); // n.b. not AddRef'ed
+            E_NOINTERFACE
+        } else if IsEqualIID(&*riid, &IFailsWithOwnedPointer::uuidof()) {
+            Self::add_ref(this);
+            *ppv = this.cast();
+            E_NOINTERFACE
+        } else {
+            *ppv = null_mut();
+            E_NOINTERFACE
+        }
+    }
+
+    unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG {
+        (*this.cast::<Object>()).refs.fetch_add(1, Relaxed) + 1
+    }
+
+    unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG {
+        let this = this.cast::<Object>();
+        let refs = (*this).refs.fetch_sub(1, Release) - 1;
+        if refs == 0 {
+            (*(*this).drops).fetch_add(1, Relaxed);
+            drop(Box::from_raw(this));
+        }
+        refs
+    }
+}
+
+mcom::interface! {
+    #[uuid(0x12345678, 0x9abc, 0xdef0, 1, 2, 3, 4, 5, 6, 7, 8)]
+    pub interface INotImplemented(INotImplementedVtbl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x12345679, 0x9abc, 0xdef0, 1, 2, 3, 4, 5, 6, 7, 8)]
+    pub interface IOutOfMemory(IOutOfMemoryVtbl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x1234567a, 0x9abc, 0xdef0, 1, 2, 3, 4, 5, 6, 7, 8)]
+    pub interface ISucceedsWithNull(ISucceedsWithNullVtbl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x1234567b, 0x9abc, 0xdef0, 1, 2, 3, 4, 5, 6, 7, 8)]
+    pub interface IFailsWithPointer(IFailsWithPointerVtbl): IUnknown(IUnknownVtbl) {}
+
+    #[uuid(0x1234567c, 0x9abc, 0xdef0, 1, 2, 3, 4, 5, 6, 7, 8)]
+    pub interface IFailsWithOwnedPointer(IFailsWithOwnedPointerVtbl): IUnknown(IUnknownVtbl) {}
+}
+
+
+
+#[test] fn clone_drop() {
+    let drops = AtomicU32::new(0);
+    let a = Object::create(&drops);
+    let b = a.clone();
+    assert_eq!(a.as_ptr(), b.as_ptr());
+    drop(a);
+    assert_eq!(0, drops.load(Relaxed));
+    drop(b);
+    assert_eq!(1, drops.load(Relaxed));
+}
+
+#[test] fn try_cast() {
+    let drops = AtomicU32::new(0);
+    let unk = Object::create(&drops);
+    let unk2 = unk.try_cast::<IUnknown>().unwrap();
+    assert_eq!(unk.as_ptr(), unk2.as_ptr());
+    assert!(unk.try_cast::<INotImplemented>().is_none());
+    drop((unk, unk2));
+    assert_eq!(1, drops.load(Relaxed));
+}
+
+#[test] fn cast() {
+    let drops = AtomicU32::new(0);
+    let unk = Object::create(&drops);
+    assert_eq!(unk.as_ptr(), unk.cast::<IUnknown>().unwrap().as_ptr());
+
+    let err = unk.cast::<INotImplemented>().err().unwrap();
+    assert_eq!(("IUnknown::QueryInterface", E_NOINTERFACE), (err.method(), err.hresult()));
+    assert!(!err.is_protocol_violation());
+
+    let err = unk.cast::<IOutOfMemory>().err().unwrap();
+    assert_eq!(("IUnknown::QueryInterface", E_OUTOFMEMORY), (err.method(), err.hresult()));
+
+    let err = unk.cast::<ISucceedsWithNull>().err().unwrap();
+    assert_eq!(("IUnknown::QueryInterface", S_OK, Some(ProtocolViolation::SucceededWithoutInterface)), (err.method(), err.hresult(), err.protocol_violation()));
+    assert_eq!(E_POINTER, HRESULT::from(err), "converting into an HRESULT shouldn't report success");
+
+    let err = unk.cast::<IFailsWithPointer>().err().unwrap();
+    assert_eq!(("IUnknown::QueryInterface", E_NOINTERFACE, Some(ProtocolViolation::FailedWithInterface)), (err.method(), err.hresult(), err.protocol_violation()));
+    assert_eq!("IUnknown::QueryInterface failed with an interface (HRESULT == 0x80004002)", err.to_string());
+    assert!(unk.try_cast::<IFailsWithPointer>().is_none());
+
+    drop(unk);
+    assert_eq!(1, drops.load(Relaxed), "protocol violations shouldn't have been released");
+}
+
+#[test] fn from_out() {
+    let drops = AtomicU32::new(0);
+    let unk = Object::create(&drops);
+    let unk2 = unsafe { mcom::Rc::<IUnknown>::from_out("IUnknown::QueryInterface", |pp| unk.QueryInterface(&IUnknown::uuidof(), (pp as *mut *mut IUnknown).cast())) }.unwrap();
+    assert_eq!(unk.as_ptr(), unk2.as_ptr());
+
+    let err = unsafe { mcom::Rc::<IOutOfMemory>::from_out_iid("IUnknown::QueryInterface", |iid, ppv| unk.QueryInterface(iid, ppv)) }.err().unwrap();
+    assert_eq!(("IUnknown::QueryInterface", E_OUTOFMEMORY), (err.method(), err.hresult()));
+
+    let err = unsafe { mcom::Rc::<ISucceedsWithNull>::from_out_iid("IUnknown::QueryInterface", |iid, ppv| unk.QueryInterface(iid, ppv)) }.err().unwrap();
+    assert_eq!(("IUnknown::QueryInterface", E_POINTER), (err.method(), err.hresult()));
+
+    let err = unsafe { mcom::Rc::<IFailsWithOwnedPointer>::from_out_iid("IUnknown::QueryInterface", |iid, ppv| unk.QueryInterface(iid, ppv)) }.err().unwrap();
+    assert_eq!(("IUnknown::QueryInterface", E_NOINTERFACE), (err.method(), err.hresult()));
+
+    let mut out = mcom::OutPtr::<IUnknown>::new();
+    assert!(out.is_null());
+    assert_eq!(S_OK, unsafe { unk.QueryInterface(&IUnknown::uuidof(), out.as_mut_void()) });
+    assert!(!out.is_null());
+    drop(out);
+
+    drop((unk, unk2));
+    assert_eq!(1, drops.load(Relaxed), "leftover out parameters should've been released exactly once");
+}
+
+#[cfg(feature = "alloc")] // Rc::from_out_array
+#[test] fn slices() {
+    let drops = AtomicU32::new(0);
+    let unks = vec![Object::create(&drops), Object::create(&drops)];
+    let ptrs = mcom::Rc::slice_as_ptrs(&unks);
+    assert_eq!([unks[0].as_ptr(), unks[1].as_ptr()], unsafe { [*ptrs, *ptrs.add(1)] });
+
+    let opts = vec![None, Some(unks[0].clone())];
+    let ptrs = mcom::Rc::option_slice_as_ptrs(&opts);
+    assert_eq!([null_mut(), unks[0].as_ptr()], unsafe { [*ptrs, *ptrs.add(1)] });
+
+    let get = |hr| unsafe { mcom::Rc::<IUnknown>::from_out_array("GetUnknowns", 3, |pp| {
+        *pp = unks[0].clone().into_raw();
+        *pp.add(2) = unks[1].clone().into_raw();
+        hr
+    })};
+    let err = get(E_OUTOFMEMORY).err().unwrap();
+    assert_eq!(("GetUnknowns", E_OUTOFMEMORY), (err.method(), err.hresult()));
+    let got = get(S_OK).unwrap();
+    assert_eq!(vec![Some(unks[0].as_ptr()), None, Some(unks[1].as_ptr())], got.iter().map(|unk| unk.as_ref().map(|unk| unk.as_ptr())).collect::<Vec<_>>());
+
+    drop((unks, opts, got));
+    assert_eq!(2, drops.load(Relaxed), "partial failures should've released what was written");
+}
+
+#[test] fn into_raw_from_raw() {
+    let drops = AtomicU32::new(0);
+    let raw = Object::create(&drops).into_raw();
+    assert_eq!(0, drops.load(Relaxed));
+    drop(unsafe { mcom::Rc::from_raw(raw) });
+    assert_eq!(1, drops.load(Relaxed));
+}
+
+#[test] fn method_hresult() {
+    assert!(MethodHResult::check("IUnknown::QueryInterface", S_FALSE).is_ok());
+    let err = MethodHResult::check("IUnknown::QueryInterface", E_NOINTERFACE).unwrap_err();
+    assert_eq!(E_NOINTERFACE, err.hresult());
+    assert_eq!("IUnknown::QueryInterface failed with HRESULT == 0x80004002", err.to_string());
+}
@@ -0,0 +1,101 @@
+//! Exercise [mcom::refcount] against an object with scripted `AddRef` results.  Runs on any host.
+
+use mcom::Rc;
+use mcom::abi::*;
+use mcom::refcount::{self, OverflowAction, OverflowPolicy};
+
+use std::cell::Cell;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr::null_mut;
+use std::sync::{Mutex, MutexGuard};
+
+
+
+/// An [IUnknown] whose `AddRef` returns whatever `next` says.  Never freed.
+#[repr(C)] struct Scripted {
+    vtbl:   &'static IUnknownVtbl,
+    next:   Cell<ULONG>,
+    adds:   Cell<u32>,
+}
+
+impl Scripted {
+    fn create(next: ULONG) -> Rc<IUnknown> {
+        static VTBL : IUnknownVtbl = IUnknownVtbl { QueryInterface: query_interface, AddRef: add_ref, Release: release };
+        let this = Box::leak(Box::new(Scripted { vtbl: &VTBL, next: Cell::new(next), adds: Cell::new(0) }));
+        unsafe { Rc::from_raw((this as *mut Scripted).cast()) }
+    }
+
+    fn get(unk: &Rc<IUnknown>) -> &Scripted { unsafe { &*unk.as_ptr().cast() } }
+}
+
+unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+    // n.b. no AddRef:  it'd consume the script, and the object is never freed anyways
+    if IsEqualIID(&*riid, &IUnknown::uuidof()) { *ppv = this.cast(); S_OK } else { *ppv = null_mut(); E_NOINTERFACE }
+}
+unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG { let this = &*this.cast::<Scripted>(); this.adds.set(this.adds.get() + 1); this.next.get() }
+unsafe extern "system" fn release(_this: *mut IUnknown) -> ULONG { 1 }
+
+/// The policy is process-wide:  serialize tests that change it, and restore it afterwards.
+fn with_policy<R>(policy: OverflowPolicy, f: impl FnOnce() -> R) -> R {
+    static LOCK : Mutex<()> = Mutex::new(());
+    let _lock : MutexGuard<()> = LOCK.lock().unwrap_or_else(|poison| poison.into_inner());
+    let prev = refcount::set_overflow_policy(policy);
+    let r = catch_unwind(AssertUnwindSafe(f));
+    refcount::set_overflow_policy(prev);
+    r.unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
+
+fn panics(f: impl FnOnce()) -> bool { catch_unwind(AssertUnwindSafe(f)).is_err() }
+
+
+
+#[test] fn default() {
+    assert_eq!(OverflowPolicy::DEFAULT, OverflowPolicy::default());
+    assert_eq!(OverflowAction::Ignore, OverflowPolicy::DEFAULT.action);
+    assert_eq!(OverflowAction::DebugAssert, OverflowPolicy::DEBUG_ASSERT.action);
+}
+
+#[test] fn ignore() {
+    with_policy(OverflowPolicy::DEFAULT, || {
+        let unk = Scripted::create(0);
+        let _a = unk.clone();
+        Scripted::get(&unk).next.set(u32::MAX);
+        let _b = unk.clone();
+        assert_eq!(2, Scripted::get(&unk).adds.get(), "adoption shouldn't have been probed");
+    });
+}
+
+#[cfg(debug_assertions)]
+#[test] fn debug_assert() {
+    with_policy(OverflowPolicy { action: OverflowAction::DebugAssert, threshold: 100 }, || {
+        let unk = Scripted::create(2);
+        assert_eq!(1, Scripted::get(&unk).adds.get(), "adoption should've been probed");
+
+        let s = Scripted::get(&unk);
+        s.next.set(100);    assert!(!panics(|| drop(unk.clone())), "at threshold");
+        s.next.set(101);    assert!( panics(|| drop(unk.clone())), "past threshold");
+        s.next.set(0);      assert!( panics(|| drop(unk.clone())), "wrapped");
+        s.next.set(3);      assert!(!panics(|| drop(unk.clone())));
+
+        let raw = unk.clone().into_raw();
+        s.next.set(101);
+        assert!(panics(|| drop(unsafe { Rc::from_raw(raw) })), "adoption should be checked too"); // n.b. leaks, but Scripted doesn't care
+    });
+}
+
+#[test] fn abort() {
+    if std::env::var_os("MCOM_TEST_REFCOUNT_ABORT").is_some() {
+        refcount::set_overflow_policy(OverflowPolicy { action: OverflowAction::Abort, threshold: 10 });
+        let unk = Scripted::create(11);
+        drop(unk); // unreachable if we aborted
+        std::process::exit(0);
+    }
+
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "abort", "--test-threads", "1", "--nocapture"])
+        .env("MCOM_TEST_REFCOUNT_ABORT", "1")
+        .output().unwrap();
+    assert!(!output.status.success(), "child process should've aborted");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("COM reference count overflow:  AddRef returned 11 (0x0000000b), aborting"), "stderr: {}", stderr);
+}
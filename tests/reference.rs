@@ -0,0 +1,74 @@
+//! Exercise [mcom::Ref] as an interface parameter of Rust-implemented COM objects.  Runs on any host.
+
+use mcom::{ComObject, Rc, Ref};
+use mcom::abi::*;
+
+use std::cell::RefCell;
+use std::mem::size_of;
+
+
+
+mcom::interface! {
+    #[uuid(0x6d2a4c10, 0x3e5f, 0x4a7b, 0x8c, 0x9d, 0x0e, 0x1f, 0x2a, 0x3b, 0x4c, 0x5e)]
+    pub interface IHolder(IHolderVtbl, IHolderImpl): IUnknown(IUnknownVtbl) {
+        fn Hold(unk: Option<Ref<'_, IUnknown>>);
+        fn Count() -> u32;
+    }
+}
+
+struct Holder(RefCell<Vec<Rc<IUnknown>>>);
+mcom::implement!(Holder: IHolder);
+
+impl IHolderImpl for Holder {
+    fn Hold(&self, unk: Option<Ref<'_, IUnknown>>) -> Result<(), HRESULT> {
+        let unk = unk.ok_or(E_POINTER)?;
+        assert!(unk.as_rc().try_cast::<IHolder>().is_some());
+        self.0.borrow_mut().push(unk.to_owned());
+        Ok(())
+    }
+
+    fn Count(&self) -> Result<u32, HRESULT> { Ok(self.0.borrow().len() as _) }
+}
+
+unsafe fn refs<I: mcom::ComInterface>(rc: &Rc<I>) -> ULONG {
+    let unk = rc.as_ptr().cast::<IUnknown>();
+    (*unk).AddRef();
+    (*unk).Release()
+}
+
+
+
+#[test] fn abi() {
+    assert_eq!(size_of::<*mut IUnknown>(), size_of::<Ref<IUnknown>>());
+    assert_eq!(size_of::<*mut IUnknown>(), size_of::<Option<Ref<IUnknown>>>());
+}
+
+#[test] fn parameter() {
+    let holder : Rc<IHolder> = ComObject::new(Holder(RefCell::new(Vec::new()))).into_rc();
+    let other  : Rc<IHolder> = ComObject::new(Holder(RefCell::new(Vec::new()))).into_rc();
+    let unk = other.up_ref();
+
+    assert_eq!(1, unsafe { refs(unk) });
+    holder.Hold(Some(Ref::new(unk))).unwrap();
+    holder.Hold(Some(unk.into())).unwrap();
+    assert_eq!(3, unsafe { refs(unk) }, "each Hold should've AddRef'ed exactly once");
+    assert_eq!(E_POINTER, holder.Hold(None).unwrap_err().hresult());
+    assert_eq!(2, holder.Count().unwrap());
+
+    drop(holder);
+    assert_eq!(1, unsafe { refs(unk) });
+}
+
+#[test] fn borrow() {
+    let holder : Rc<IHolder> = ComObject::new(Holder(RefCell::new(Vec::new()))).into_rc();
+    let r = unsafe { Ref::from_raw(holder.as_ptr()) };
+    assert_eq!(holder.as_ptr(), r.as_ptr());
+    assert_eq!(0, r.Count().unwrap());
+    assert!(Rc::ptr_eq(&holder, r.as_rc()));
+    assert!(unsafe { Ref::<IHolder>::from_raw_opt(std::ptr::null_mut()) }.is_none());
+
+    #[cfg(feature = "alloc")] { // names::register_interface
+        mcom::names::register_interface::<IHolder>();
+        assert_eq!(format!("Ref<IHolder>({:?})", holder.as_ptr()), format!("{:?}", r));
+    }
+}
@@ -0,0 +1,162 @@
+//! Exercise tear-off interfaces of [mcom::ComObject]s.  Runs on any host.
+
+use mcom::ComObject;
+use mcom::abi::*;
+use mcom::object::{Aggregated, Class};
+
+use std::sync::atomic::{AtomicU32, Ordering::*};
+
+
+
+mcom::interface! {
+    #[uuid(0x9e4b7d10, 0x6a2f, 0x4d53, 0xb1, 0x08, 0x3c, 0x5e, 0x72, 0x0a, 0x9f, 0x01)]
+    pub interface ICounter(ICounterVtbl, ICounterImpl): IUnknown(IUnknownVtbl) {
+        fn Add(amount: u32);
+    }
+
+    #[uuid(0x9e4b7d10, 0x6a2f, 0x4d53, 0xb1, 0x08, 0x3c, 0x5e, 0x72, 0x0a, 0x9f, 0x02)]
+    pub interface IDiagnostics(IDiagnosticsVtbl, IDiagnosticsImpl): IUnknown(IUnknownVtbl) {
+        fn Total() -> u32;
+    }
+
+    #[uuid(0x9e4b7d10, 0x6a2f, 0x4d53, 0xb1, 0x08, 0x3c, 0x5e, 0x72, 0x0a, 0x9f, 0x03)]
+    pub interface IDiagnostics2(IDiagnostics2Vtbl, IDiagnostics2Impl): IDiagnostics(IDiagnosticsVtbl) {
+        fn Calls() -> u32;
+    }
+
+    #[uuid(0x9e4b7d10, 0x6a2f, 0x4d53, 0xb1, 0x08, 0x3c, 0x5e, 0x72, 0x0a, 0x9f, 0x04)]
+    pub interface IOuter(IOuterVtbl, IOuterImpl): IUnknown(IUnknownVtbl) {}
+}
+
+struct Counter {
+    total:  AtomicU32,
+    calls:  AtomicU32,
+    drops:  &'static AtomicU32,
+}
+
+mcom::implement!(Counter: ICounter; tearoff IDiagnostics2);
+
+impl ICounterImpl for Counter {
+    fn Add(&self, amount: u32) -> Result<(), HRESULT> {
+        self.total.fetch_add(amount, Relaxed);
+        self.calls.fetch_add(1, Relaxed);
+        Ok(())
+    }
+}
+
+impl IDiagnosticsImpl for Counter {
+    fn Total(&self) -> Result<u32, HRESULT> { Ok(self.total.load(Relaxed)) }
+}
+
+impl IDiagnostics2Impl for Counter {
+    fn Calls(&self) -> Result<u32, HRESULT> { Ok(self.calls.load(Relaxed)) }
+}
+
+impl Drop for Counter {
+    fn drop(&mut self) { self.drops.fetch_add(1, Relaxed); }
+}
+
+struct Outer {
+    inner:  Aggregated,
+    drops:  &'static AtomicU32,
+}
+
+mcom::implement!(Outer: IOuter; aggregate inner: IDiagnostics2);
+
+impl IOuterImpl for Outer {}
+
+impl Drop for Outer {
+    fn drop(&mut self) { self.drops.fetch_add(10, Relaxed); }
+}
+
+fn create(drops: &'static AtomicU32) -> mcom::Rc<ICounter> {
+    ComObject::new(Counter { total: AtomicU32::new(0), calls: AtomicU32::new(0), drops }).into_rc()
+}
+
+unsafe fn refs<I: mcom::ComInterface>(rc: &mcom::Rc<I>) -> ULONG {
+    let unk = rc.as_ptr().cast::<IUnknown>();
+    (*unk).AddRef();
+    (*unk).Release()
+}
+
+
+
+#[test] fn no_vtable() {
+    assert_eq!(2 * std::mem::size_of::<*const c_void>(), std::mem::size_of::<<Counter as Class>::Vtbls>());
+}
+
+#[test] fn methods() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let counter = create(&DROPS);
+    counter.Add(3).unwrap();
+    counter.Add(4).unwrap();
+
+    let diag = counter.try_cast::<IDiagnostics2>().unwrap();
+    assert_eq!(7, diag.Total().unwrap());
+    assert_eq!(2, diag.Calls().unwrap());
+    assert_eq!(7, counter.try_cast::<IDiagnostics>().unwrap().Total().unwrap());
+}
+
+#[test] fn identity() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let counter = create(&DROPS);
+    let unk = counter.try_cast::<IUnknown>().unwrap();
+
+    let diag = counter.try_cast::<IDiagnostics2>().unwrap();
+    assert_ne!(unk.as_ptr(), diag.as_ptr().cast());
+    assert_eq!(unk.as_ptr(), diag.try_cast::<IUnknown>().unwrap().as_ptr());
+    assert_eq!(counter.as_ptr(), diag.try_cast::<ICounter>().unwrap().as_ptr());
+
+    // querying a tear-off for (an ancestor of) itself returns itself
+    assert_eq!(diag.as_ptr(), diag.try_cast::<IDiagnostics2>().unwrap().as_ptr());
+    assert_eq!(diag.as_ptr().cast(), diag.try_cast::<IDiagnostics>().unwrap().as_ptr());
+
+    // querying the main object allocates another tear-off, with the same identity
+    let diag2 = unk.try_cast::<IDiagnostics>().unwrap();
+    assert_ne!(diag.as_ptr().cast(), diag2.as_ptr());
+    assert_eq!(unk.as_ptr(), diag2.try_cast::<IUnknown>().unwrap().as_ptr());
+}
+
+#[test] fn lifetime() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let counter = create(&DROPS);
+    assert_eq!(1, unsafe { refs(&counter) });
+
+    let diag = counter.try_cast::<IDiagnostics2>().unwrap();
+    assert_eq!(1, unsafe { refs(&diag) });
+    assert_eq!(2, unsafe { refs(&counter) }); // the tear-off holds a strong reference to the main object
+    let diag2 = diag.clone();
+    assert_eq!(2, unsafe { refs(&diag) });
+    assert_eq!(2, unsafe { refs(&counter) });
+
+    drop(counter);
+    drop(diag);
+    assert_eq!(0, DROPS.load(Relaxed));
+    let counter = diag2.try_cast::<ICounter>().unwrap();
+    drop(diag2);
+    assert_eq!(0, DROPS.load(Relaxed));
+    assert_eq!(1, unsafe { refs(&counter) });
+    drop(counter);
+    assert_eq!(1, DROPS.load(Relaxed));
+}
+
+#[test] fn aggregated() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let outer = ComObject::new(Outer { inner: Aggregated::new(), drops: &DROPS });
+    let inner = unsafe { ComObject::new_aggregated(Counter { total: AtomicU32::new(0), calls: AtomicU32::new(0), drops: &DROPS }, &outer.to_rc()) };
+    assert!(outer.inner.set(inner.into_rc()).is_ok());
+    let outer : mcom::Rc<IOuter> = outer.into_rc();
+    let unk = outer.try_cast::<IUnknown>().unwrap();
+
+    let diag = outer.try_cast::<IDiagnostics2>().unwrap();
+    assert_eq!(unk.as_ptr(), diag.try_cast::<IUnknown>().unwrap().as_ptr());
+    assert_eq!(outer.as_ptr(), diag.try_cast::<IOuter>().unwrap().as_ptr());
+
+    // the tear-off keeps the outer object (and thus the inner object) alive
+    drop((outer, unk));
+    assert_eq!(0, DROPS.load(Relaxed));
+    assert!(diag.try_cast::<IOuter>().is_some());
+    assert_eq!(0, diag.Total().unwrap());
+    drop(diag);
+    assert_eq!(11, DROPS.load(Relaxed));
+}
@@ -0,0 +1,99 @@
+//! Exercise the `validate` feature against deliberately broken, Rust-implemented COM objects.  Runs on any host with `--features validate`.
+#![cfg(feature = "validate")]
+
+use mcom::Rc;
+use mcom::abi::*;
+
+use std::ptr::{null, null_mut};
+
+
+
+mcom::interface! {
+    #[uuid(0x0d4f8b63, 0x27a1, 0x4c5e, 0xb9, 0x10, 0x6e, 0x3a, 0x52, 0xf7, 0x8c, 0x01)]
+    pub interface IFoo(IFooVtbl): IUnknown(IUnknownVtbl) {}
+}
+
+/// How [Broken] answers `QueryInterface`.
+#[derive(Clone, Copy)] enum Answer {
+    /// IUnknown and IFoo with `this`
+    Valid,
+    /// IUnknown with `this`, but not IFoo
+    UnknownOnly,
+    /// Nothing at all
+    Nothing,
+    /// IUnknown with `this`, IFoo with a different object
+    Elsewhere,
+}
+
+/// An [IUnknown] (and maybe [IFoo]) with a possibly broken vtable.  Never freed, and doesn't actually count references.
+#[repr(C)] struct Broken {
+    vtbl:   *const Vtbl,
+    answer: Answer,
+}
+
+/// [IUnknownVtbl], but with nullable entries.
+#[repr(C)] struct Vtbl {
+    query_interface:    Option<unsafe extern "system" fn(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT>,
+    add_ref:            Option<unsafe extern "system" fn(this: *mut IUnknown) -> ULONG>,
+    release:            Option<unsafe extern "system" fn(this: *mut IUnknown) -> ULONG>,
+}
+
+static VALID    : Vtbl = Vtbl { query_interface: Some(query_interface), add_ref: Some(add_ref), release: Some(release) };
+static NO_ADDREF: Vtbl = Vtbl { query_interface: Some(query_interface), add_ref: None,          release: Some(release) };
+
+impl Broken {
+    fn create(vtbl: *const Vtbl, answer: Answer) -> *mut IFoo {
+        (Box::leak(Box::new(Broken { vtbl, answer })) as *mut Broken).cast()
+    }
+}
+
+unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, ppv: *mut *mut c_void) -> HRESULT {
+    let (unknown, ifoo) = (IsEqualIID(&*riid, &IUnknown::uuidof()), IsEqualIID(&*riid, &IFoo::uuidof()));
+    let result : *mut IUnknown = match (*this.cast::<Broken>()).answer {
+        _ if !unknown && !ifoo                  => null_mut(),
+        Answer::Valid                           => this,
+        Answer::UnknownOnly if unknown          => this,
+        Answer::UnknownOnly | Answer::Nothing   => null_mut(),
+        Answer::Elsewhere if unknown            => this,
+        Answer::Elsewhere                       => Broken::create(&VALID, Answer::Valid).cast(),
+    };
+    *ppv = result.cast();
+    if result.is_null() { E_NOINTERFACE } else { S_OK }
+}
+
+unsafe extern "system" fn add_ref(_this: *mut IUnknown) -> ULONG { 1 }
+unsafe extern "system" fn release(_this: *mut IUnknown) -> ULONG { 1 }
+
+
+
+#[test] fn valid() {
+    let ptr = Broken::create(&VALID, Answer::Valid);
+    let _ = unsafe { Rc::from_raw(ptr) };
+    let _ = unsafe { Rc::borrow_ptr(&ptr) };
+    let _ = unsafe { mcom::Ref::from_raw(ptr) };
+}
+
+#[test] #[should_panic = "vtable pointer is null"] fn null_vtable() {
+    let _ = unsafe { Rc::from_raw(Broken::create(null(), Answer::Valid)) };
+}
+
+#[test] #[should_panic = "vtable entry 1 (AddRef) is null"] fn null_add_ref() {
+    let _ = unsafe { Rc::from_raw(Broken::create(&NO_ADDREF, Answer::Valid)) };
+}
+
+#[test] #[should_panic = "QueryInterface(IUnknown) failed with HRESULT == 0x80004002"] fn no_unknown() {
+    let _ = unsafe { Rc::borrow_ptr(&Broken::create(&VALID, Answer::Nothing)) };
+}
+
+#[test] #[should_panic = "failed with HRESULT == 0x80004002 - wrong interface?"] fn wrong_interface() {
+    let _ = unsafe { Rc::from_raw(Broken::create(&VALID, Answer::UnknownOnly)) };
+}
+
+#[test] #[should_panic = "returned a different pointer"] fn different_pointer() {
+    let _ = unsafe { Rc::borrow_ptr(&Broken::create(&VALID, Answer::Elsewhere)) };
+}
+
+#[test] #[should_panic = "adopted as IFoo at tests/validate.rs"] fn location() {
+    mcom::names::register_interface::<IFoo>();
+    let _ = unsafe { Rc::from_raw(Broken::create(&VALID, Answer::UnknownOnly)) };
+}
@@ -0,0 +1,136 @@
+//! Exercise [mcom::Weak] against [mcom::ComObject]s.  Runs on any host.
+
+use mcom::{ComObject, Weak};
+use mcom::abi::*;
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, Ordering::*};
+
+
+
+mcom::interface! {
+    #[uuid(0x51c2e7a8, 0x0d94, 0x4b3f, 0x86, 0x1a, 0x2f, 0x6d, 0x05, 0xe3, 0x7b, 0x01)]
+    pub interface INode(INodeVtbl, INodeImpl): IUnknown(IUnknownVtbl) {
+        fn Id() -> u32;
+    }
+}
+
+struct Node {
+    id:         u32,
+    parent:     Mutex<Option<Weak<INode>>>,
+    children:   Mutex<Vec<ComObject<Node>>>,
+    drops:      &'static AtomicU32,
+}
+
+mcom::implement!(Node: INode);
+
+// Rc/Weak are !Send + !Sync, but these tests don't share them between threads
+unsafe impl Send for Node {}
+unsafe impl Sync for Node {}
+
+impl INodeImpl for Node {
+    fn Id(&self) -> Result<u32, HRESULT> { Ok(self.id) }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) { self.drops.fetch_add(1, Relaxed); }
+}
+
+fn node(id: u32, drops: &'static AtomicU32) -> Node {
+    Node { id, parent: Mutex::new(None), children: Mutex::new(Vec::new()), drops }
+}
+
+fn create(id: u32, drops: &'static AtomicU32) -> ComObject<Node> {
+    ComObject::new(node(id, drops))
+}
+
+
+
+#[test] fn upgrade() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let node : mcom::Rc<INode> = create(1, &DROPS).into_rc();
+    let weak = node.downgrade().unwrap();
+    let weak2 = weak.clone();
+
+    let strong = weak.upgrade().unwrap();
+    assert_eq!(node.as_ptr(), strong.as_ptr());
+    assert_eq!(1, strong.Id().unwrap());
+    drop(strong);
+
+    let unk = Weak::<IUnknown>::new(node.up_ref()).unwrap().upgrade().unwrap();
+    assert_eq!(node.try_cast::<IUnknown>().unwrap().as_ptr(), unk.as_ptr());
+    drop(unk);
+
+    drop(node);
+    assert_eq!(1, DROPS.load(Relaxed));
+    assert!(weak.upgrade().is_none());
+    assert!(weak2.upgrade().is_none());
+}
+
+#[test] fn source_identity() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let node : mcom::Rc<INode> = create(1, &DROPS).into_rc();
+    let unk = node.try_cast::<IUnknown>().unwrap();
+    let source = node.try_cast::<IWeakReferenceSource>().unwrap();
+    assert_eq!(unk.as_ptr(), source.try_cast::<IUnknown>().unwrap().as_ptr());
+    assert_eq!(node.as_ptr(), source.try_cast::<INode>().unwrap().as_ptr());
+    assert_eq!(source.as_ptr(), source.try_cast::<IWeakReferenceSource>().unwrap().as_ptr());
+
+    // the weak reference is it's own object, which can't be used to reach the strong object via QueryInterface
+    let weak = source.GetWeakReference().unwrap().unwrap();
+    assert!(weak.try_cast::<INode>().is_none());
+    assert_eq!(weak.as_ptr(), weak.try_cast::<IWeakReference>().unwrap().as_ptr());
+    drop((unk, source, node));
+    assert_eq!(1, DROPS.load(Relaxed)); // weak reference doesn't keep the object alive
+
+    let mut ppv = 1 as *mut c_void;
+    assert_eq!(S_OK, unsafe { weak.Resolve(&INode::uuidof(), &mut ppv) });
+    assert!(ppv.is_null());
+}
+
+#[test] fn observer_graph() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let parent = create(1, &DROPS);
+    let parent_rc : mcom::Rc<INode> = parent.to_rc();
+    for id in 2 ..= 4 {
+        let child = create(id, &DROPS);
+        *child.parent.lock().unwrap() = Some(parent_rc.downgrade().unwrap());
+        parent.children.lock().unwrap().push(child);
+    }
+    drop(parent_rc);
+
+    let child = parent.children.lock().unwrap()[1].clone();
+    let child_parent = child.parent.lock().unwrap().as_ref().unwrap().upgrade().unwrap();
+    assert_eq!(1, child_parent.Id().unwrap());
+    drop(child_parent);
+
+    drop(parent);
+    assert_eq!(3, DROPS.load(Relaxed)); // parent + 2 children no longer referenced
+    assert!(child.parent.lock().unwrap().as_ref().unwrap().upgrade().is_none());
+    drop(child);
+    assert_eq!(4, DROPS.load(Relaxed));
+}
+
+#[test] fn aggregated() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    let outer : mcom::Rc<IUnknown> = create(1, &DROPS).into_rc();
+    let inner : mcom::Rc<IUnknown> = unsafe { ComObject::new_aggregated(node(2, &DROPS), &outer) }.into_rc();
+    let err = inner.downgrade().err().unwrap();
+    assert_eq!(E_NOINTERFACE, err.hresult());
+    drop((inner, outer));
+    assert_eq!(2, DROPS.load(Relaxed));
+}
+
+#[test] fn threads() {
+    static DROPS : AtomicU32 = AtomicU32::new(0);
+    for id in 0 .. 100 {
+        let node = create(id, &DROPS);
+        let weak = Weak::new(&node.to_rc::<INode>()).unwrap();
+        let thread = std::thread::spawn(move || drop(node));
+        while let Some(strong) = weak.upgrade() {
+            assert_eq!(id, strong.Id().unwrap());
+        }
+        thread.join().unwrap();
+    }
+    assert_eq!(100, DROPS.load(Relaxed));
+}